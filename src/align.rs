@@ -0,0 +1,120 @@
+/// How the typed text lines up with the target, counted along the cheapest
+/// edit path between them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Alignment {
+    pub matches: usize,
+    /// A wrong character typed in place of the right one.
+    pub substitutions: usize,
+    /// An extra character that isn't in the target.
+    pub insertions: usize,
+    /// A target character that was skipped.
+    pub omissions: usize,
+}
+
+impl Alignment {
+    #[must_use]
+    pub fn errors(&self) -> usize {
+        self.substitutions + self.insertions + self.omissions
+    }
+
+    /// Matches as a percentage of everything typed or missed.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        let total = self.matches + self.errors();
+
+        if total == 0 {
+            return 100.0;
+        }
+
+        self.matches as f64 / total as f64 * 100.0
+    }
+}
+
+/// Aligns `typed` against `target` by edit distance, so a single skipped or
+/// doubled character counts once instead of desyncing everything after it.
+#[must_use]
+pub fn align(typed: &[char], target: &[char]) -> Alignment {
+    let (rows, cols) = (typed.len() + 1, target.len() + 1);
+    let mut costs = vec![0; rows * cols];
+
+    for i in 0..rows {
+        for j in 0..cols {
+            costs[i * cols + j] = match (i, j) {
+                (0, _) => j,
+                (_, 0) => i,
+                _ => {
+                    let replace = usize::from(typed[i - 1] != target[j - 1]);
+                    (costs[(i - 1) * cols + j - 1] + replace)
+                        .min(costs[(i - 1) * cols + j] + 1)
+                        .min(costs[i * cols + j - 1] + 1)
+                }
+            };
+        }
+    }
+
+    // Walk back from the end, preferring matches and substitutions so equal
+    // length texts keep their positional pairing.
+    let mut alignment = Alignment::default();
+    let (mut i, mut j) = (typed.len(), target.len());
+
+    while i > 0 || j > 0 {
+        let cost = costs[i * cols + j];
+
+        if i > 0 && j > 0 {
+            let replace = usize::from(typed[i - 1] != target[j - 1]);
+
+            if cost == costs[(i - 1) * cols + j - 1] + replace {
+                if replace == 0 {
+                    alignment.matches += 1;
+                } else {
+                    alignment.substitutions += 1;
+                }
+
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+
+        if i > 0 && cost == costs[(i - 1) * cols + j] + 1 {
+            alignment.insertions += 1;
+            i -= 1;
+        } else {
+            alignment.omissions += 1;
+            j -= 1;
+        }
+    }
+
+    alignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn align_str(typed: &str, target: &str) -> Alignment {
+        let typed: Vec<_> = typed.chars().collect();
+        let target: Vec<_> = target.chars().collect();
+        align(&typed, &target)
+    }
+
+    #[test]
+    fn counts_substitutions() {
+        let alignment = align_str("hallo", "hello");
+        assert_eq!(alignment.matches, 4);
+        assert_eq!(alignment.substitutions, 1);
+        assert_eq!(alignment.errors(), 1);
+    }
+
+    #[test]
+    fn recovers_from_skipped_and_extra_characters() {
+        let alignment = align_str("helo world", "hello world");
+        assert_eq!(alignment.omissions, 1);
+        assert_eq!(alignment.matches, 10);
+
+        let alignment = align_str("helllo world", "hello world");
+        assert_eq!(alignment.insertions, 1);
+        assert_eq!(alignment.matches, 11);
+    }
+}
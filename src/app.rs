@@ -1,237 +1,4901 @@
+mod achievements;
+mod alert;
+mod archive;
+mod ascii;
+mod best;
+mod big;
+mod breaks;
+mod browser;
+mod caret;
+mod certificate;
+mod challenge;
+mod chunks;
+mod command;
+mod compare;
+mod confetti;
+mod daily;
+mod date;
+mod difficulty;
+mod duel;
+mod export;
+mod goal;
+mod graph;
+mod histogram;
+mod history;
+mod http;
+mod json;
+mod keyboard;
+mod keymap;
+mod keystrokes;
+mod ladder;
+mod leaderboard;
+mod log;
+mod menu;
+mod monkeytype;
+mod numbers;
+mod overlay;
+mod picks;
+mod playlist;
+mod png;
+mod podium;
+mod replay;
+mod report;
+mod resume;
+mod screen;
+mod session;
+mod settings;
+mod shell;
+mod simulate;
+mod source;
+mod stats;
+mod store;
+mod strings;
+mod symbols;
+mod sync;
+mod template;
+mod theme;
 mod timer;
+mod tutorial;
+mod validity;
+mod vocab;
+mod webhook;
 mod words;
+mod wrap;
 
-use crate::Args;
+use crate::{
+    args::{
+        Alert, Align, CardFormat, Charset, Command as Subcommand, Cursor, Funbox, Glyphs,
+        GoalAction, Grace, Hand, HistoryAction, HistoryFilter, ImportFrom, Mode, Panel, Spaces,
+        Start, ThemeName, Unit, WpmFormula,
+    },
+    config::Config,
+    paths::Paths,
+    Args,
+};
+use achievements::Unlocked;
+use arboard::Clipboard;
+use best::Bests;
+use breaks::Breaks;
+use browser::Browser;
+use caret::Caret;
+use challenge::Challenge;
+use chunks::Progress;
+use clap::ArgMatches;
+use command::Command;
+use confetti::Confetti;
 use crossterm::{
     cursor::SetCursorStyle,
-    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
-    execute,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyEventState, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
+    execute, terminal,
 };
+use date::Date;
+use duel::{Duel, Message};
+use goal::Goal;
+use history::{History, Record};
+use keyboard::Finger;
+use keymap::{Action, Key, Keymap, Screen};
+use leaderboard::Leaderboard;
+use log::Log;
+use menu::Menu;
+use picks::Picks;
+use playlist::Playlist;
+use rand::{rngs::StdRng, SeedableRng};
 use ratatui::{
     layout::{Constraint, Flex, Layout, Position, Rect},
-    style::{Style, Stylize as RatatuiStylize},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Padding, Paragraph, Widget, Wrap},
+    widgets::{Block, BorderType, Clear, Padding, Paragraph, Widget, Wrap},
     DefaultTerminal, Frame,
 };
-use std::io;
+use resume::Saved;
+use screen::{Context, Screen as _};
+use session::Run;
+use settings::Settings;
+use source::Source;
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    fmt::{self, Write as _},
+    fs,
+    io::{self, Write as _},
+    num::NonZeroUsize,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::Stdio,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+use strings::{text, Text};
+use theme::Theme;
 use timer::Timer;
-use words::Words;
+use typers::{align::Alignment, Attempt, Scoring};
+use validity::Flag;
+use vocab::Schedule;
+use words::{Filter, Transform, Words};
+
+/// What `--screen-reader` last wrote about, to tell what changed since.
+#[derive(Clone, PartialEq)]
+struct Shown {
+    state: State,
+    words: Vec<String>,
+    typed: usize,
+    started: bool,
+    notice: Option<&'static str>,
+}
+
+impl Shown {
+    fn is_typing(&self) -> bool {
+        matches!(
+            self.state,
+            State::Preview { .. } | State::Ready { .. } | State::Playing
+        )
+    }
+}
 
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy)]
+enum Button {
+    Retry,
+    New,
+    Quit,
+}
+
+impl Button {
+    const ALL: [Self; 3] = [Self::Retry, Self::New, Self::Quit];
+
+    fn label(self) -> String {
+        match self {
+            Self::Retry => format!("[{} · r]", text(Text::Retry)),
+            Self::New => format!("[{} · tab]", text(Text::New)),
+            Self::Quit => format!("[{} · q]", text(Text::Quit)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum State {
+    Menu,
+    Settings,
+    Preview {
+        until: Instant,
+    },
+    /// Showing the text until Enter or any key is pressed, or the
+    /// countdown runs out, for `--start`.
+    Ready {
+        until: Option<Instant>,
+    },
     Playing,
     Finished,
+    Summary,
+    /// Asking whether to continue a test quit part way through.
+    Resume,
+    /// The pages explaining typers on its first run.
+    Tutorial {
+        page: usize,
+    },
+    /// Looking through the finished tests.
+    History,
+    Achievements,
+    /// Comparing the best speeds of the profiles.
+    Leaderboard,
     Exit,
 }
 
+#[expect(clippy::struct_excessive_bools)]
 pub struct App {
     timer: Timer,
     state: State,
-    first_draw: bool,
     args: Args,
+    keymap: Keymap,
+    theme: Theme,
+    help: bool,
+    /// What has been entered on the command line, while it is open.
+    command: Option<String>,
+    menu: Menu,
+    config: Config,
+    settings: Settings,
+    browser: Browser,
+    achievements: Unlocked,
+    leaderboard: Leaderboard,
+    source: Source,
+    /// The usual word source, put aside for a test of words from the
+    /// history.
+    put_aside: Option<Source>,
+    /// Where the words from the history came from, while typing them.
+    replayed: Option<String>,
     typed: Vec<char>,
-    words: Vec<&'static str>,
+    times: Vec<Duration>,
+    /// Where wrong characters were erased with backspace during this test,
+    /// once for every erasure.
+    corrected: Vec<usize>,
+    words: Vec<String>,
+    clipboard: Option<Clipboard>,
+    notice: Option<&'static str>,
+    /// Whether today's daily challenge has been finished before.
+    daily_done: bool,
+    /// Tests finished so far when running `--tests`, `--versus` or
+    /// `--roster`.
+    session: Vec<Run>,
+    /// Groups the tests of a `--tests` session in the history.
+    session_id: String,
+    /// On the untracked warm-up run before the real test.
+    warming_up: bool,
+    log: Option<Log>,
+    /// A test from an earlier run that can be resumed.
+    saved: Option<Saved>,
+    /// Whether the test was stopped by `--max-time` before the end.
+    incomplete: bool,
+    /// How often everything typed was erased back to the start.
+    restarts: usize,
+    /// Characters and backspaces pressed during the test, including the
+    /// ones erased since.
+    keystrokes: usize,
+    /// Tests in a row reaching the `--certify` goal so far.
+    certified: Vec<Run>,
+    /// The students taking turns with `--roster`.
+    roster: Vec<String>,
+    /// Where the caret was last drawn, for it to glide on from.
+    caret: Cell<Option<Caret>>,
+    /// When the words were first drawn ready to type, which the reaction to
+    /// them is timed from.
+    shown: Cell<Option<Instant>>,
+    /// How long after the words were shown the first key came.
+    reaction: Option<Duration>,
+    /// Keystroke times of the ghost being raced with `--ghost`, or of the
+    /// rival with `--rival`.
+    ghost: Option<Vec<Duration>>,
+    /// The speed the `--rival` types at this test, once there's a history
+    /// of the mode to tune it from.
+    rival: Option<f64>,
+    /// The finger the words are picked for, after drilling the weakest one.
+    drill: Option<Finger>,
+    /// Letters typed in a row in the opposite case to the text.
+    swapped_case: usize,
+    /// Shown over the text while the keys look to be in a bad state.
+    warning: Option<&'static str>,
+    /// When the event being handled was read, which keystrokes are timed by.
+    received: Instant,
+    /// Whether anything on screen changed since the last frame.
+    dirty: bool,
+    /// The results of the last finished test, printed on exit.
+    last_summary: Option<String>,
+    /// The usual word source, put aside while drilling mistakes.
+    mistake_drill: Option<Source>,
+    /// Typing time since the last break, with `--break-after`.
+    breaks: Option<Breaks>,
+    /// The connection to the opponent with `typers duel`.
+    duel: Option<Duel>,
+    /// Falling over the results after a new personal best.
+    confetti: Option<Confetti>,
+    /// When the podium of a duel started rising, once both ends finished.
+    podium: Option<Instant>,
+    /// How the finished test compares with the others of its mode in the
+    /// history, and how the goal is coming along.
+    standing: Vec<String>,
+    /// The id the finished test was saved to the history under, to write a
+    /// note on.
+    recorded: Option<String>,
+    /// When the last wrong key lit up the border with `--flash-errors`.
+    flash: Option<Instant>,
+    /// The tests being worked through with `typers playlist`.
+    playlist: Option<Playlist>,
+    /// Results still being posted to the `--webhook`.
+    webhooks: Vec<JoinHandle<()>>,
+    /// When the `--overlay` file was last written during the test.
+    overlay_at: Option<Instant>,
+    /// Typing the test that ends the tutorial, setting the first personal
+    /// best.
+    calibrating: bool,
 }
 
 impl App {
-    pub fn new(args: Args) -> Self {
-        let words = Words::generate(args.words);
-        let typed = Vec::with_capacity(words.len());
+    pub fn new(mut args: Args, config: Config) -> io::Result<Self> {
+        set_up(&mut args)?;
+        let (mut duel, duel_words) = duel(&mut args)?;
+
+        let playlist = playlist(&args, &config)?;
 
-        Self {
+        if let Some(playlist) = &playlist {
+            playlist.apply(&mut args);
+            args.menu = false;
+        }
+
+        let ghost = args.ghost.as_deref().map(Saved::read).transpose()?;
+        let source = match (&ghost, duel_words) {
+            (Some(ghost), _) => Source::Text(ghost.words.clone()),
+            (None, Some(words)) => Source::Text(words),
+            (None, None) => source(&args)?,
+        };
+        let warming_up = args.warmup && !args.menu;
+        let words = if warming_up {
+            warmup_words(&args)
+        } else {
+            source.generate(args.words)
+        };
+        let daily_done = args.is_daily() && daily::is_done();
+        let log = args.log_file.as_deref().map(Log::create).transpose()?;
+        let browser = browser(&args)?;
+        let saved = if duel.is_some() || playlist.is_some() {
+            None
+        } else {
+            Saved::load()
+        };
+        let roster = roster(args.roster.as_deref())?;
+        let breaks = args
+            .break_after
+            .map(|mins| Breaks::new(Duration::from_mins(mins)));
+
+        if let Some(duel) = duel.as_mut().filter(|duel| duel.host) {
+            duel.send(&Message::Words(words.clone()));
+        }
+
+        let mut app = Self {
             timer: Timer::default(),
-            state: State::Playing,
-            first_draw: true,
+            state: opening_state(&args, saved.is_some()),
+            theme: theme(&args, &config),
+            keymap: Keymap::new(args.keymap),
             args,
-            typed,
+            help: false,
+            command: None,
+            menu: Menu::default(),
+            settings: Settings::new(config.clone()),
+            browser,
+            achievements: Unlocked::load(),
+            leaderboard: Leaderboard::default(),
+            config,
+            source,
+            put_aside: None,
+            replayed: None,
+            typed: Vec::with_capacity(words.len()),
+            times: Vec::new(),
+            corrected: Vec::new(),
             words,
+            clipboard: None,
+            notice: None,
+            daily_done,
+            session: Vec::new(),
+            session_id: history::new_id(),
+            warming_up,
+            log,
+            saved,
+            incomplete: false,
+            restarts: 0,
+            keystrokes: 0,
+            certified: Vec::new(),
+            roster,
+            caret: Cell::new(None),
+            shown: Cell::new(None),
+            reaction: None,
+            ghost: ghost.map(|ghost| ghost.times()),
+            rival: None,
+            drill: None,
+            swapped_case: 0,
+            warning: None,
+            received: Instant::now(),
+            dirty: true,
+            last_summary: None,
+            mistake_drill: None,
+            breaks,
+            duel,
+            confetti: None,
+            podium: None,
+            standing: Vec::new(),
+            recorded: None,
+            flash: None,
+            playlist,
+            webhooks: Vec::new(),
+            overlay_at: None,
+            calibrating: false,
+        };
+
+        app.tune_rival();
+        if app.state == State::Leaderboard {
+            app.open_leaderboard();
         }
+
+        Ok(app)
     }
 
+    /// Quits, keeping a test in progress to offer resuming it next time.
     fn exit(&mut self) {
+        if self.state == State::Playing && !self.typed.is_empty() && !self.warming_up {
+            let _ = Saved::new(&self.words, &self.typed, &self.times).save();
+        }
+
         self.state = State::Exit;
     }
 
+    fn resume(&mut self) {
+        let Some(saved) = self.saved.take() else {
+            return;
+        };
+
+        Saved::clear();
+        self.warming_up = false;
+        let (typed, times) = (saved.typed(), saved.times());
+        self.words = saved.words;
+        self.retry();
+        self.typed = typed;
+        self.times = times;
+        self.timer
+            .resume(self.times.last().copied().unwrap_or_default());
+        self.state = State::Playing;
+    }
+
+    /// Drops the saved test and carries on as if there had been none.
+    fn discard(&mut self) {
+        self.saved = None;
+        Saved::clear();
+        self.state = if self.args.menu {
+            State::Menu
+        } else {
+            start_state(&self.args)
+        };
+    }
+
+    /// Leaves a test for the menu it was started from, if any.
+    fn leave(&mut self) {
+        self.calibrating = false;
+
+        if self.args.menu {
+            self.state = State::Menu;
+        } else {
+            self.exit();
+        }
+    }
+
+    /// Saves the edited settings and applies them to the current session.
+    fn save_settings(&mut self) -> io::Result<()> {
+        let config = self.settings.draft.clone();
+
+        if let Err(err) = config.save() {
+            self.notice = Some(match err.kind() {
+                io::ErrorKind::PermissionDenied => "config is not writable",
+                _ => "couldn't save config",
+            });
+            return Ok(());
+        }
+
+        config.apply(&mut self.args, None);
+        self.theme = theme(&self.args, &config);
+        self.keymap = Keymap::new(self.args.keymap);
+        self.config = config;
+        self.notice = None;
+        self.state = State::Menu;
+
+        execute!(io::stdout(), cursor_style(&self.args))
+    }
+
+    fn open_history(&mut self) {
+        match History::load_recent() {
+            Ok(history) => {
+                self.browser = Browser::new(history, History::archived_months());
+                self.notice = None;
+                self.state = State::History;
+            }
+            Err(_) => self.notice = Some("couldn't read the history"),
+        }
+    }
+
+    fn open_achievements(&mut self) {
+        self.achievements = Unlocked::load();
+        self.notice = None;
+        self.state = State::Achievements;
+    }
+
+    fn open_leaderboard(&mut self) {
+        self.leaderboard = Leaderboard::load(self.args.profile.as_deref());
+        self.notice = None;
+        self.state = State::Leaderboard;
+    }
+
+    fn handle_menu(&mut self, action: Action) {
+        match self.menu.handle(action, &mut self.args) {
+            Some(menu::Choice::Start) => {
+                let _ = Picks::new(&self.args).save();
+                self.start_from_menu();
+            }
+            Some(menu::Choice::Profile) => self.switch_profile(),
+            Some(menu::Choice::History) => self.open_history(),
+            Some(menu::Choice::Achievements) => self.open_achievements(),
+            Some(menu::Choice::Leaderboard) => self.open_leaderboard(),
+            Some(menu::Choice::Settings) => {
+                self.settings = Settings::new(self.config.clone());
+                self.notice = None;
+                self.state = State::Settings;
+            }
+            None => {}
+        }
+    }
+
+    fn handle_browser(&mut self, action: Action) {
+        match self.browser.handle(action, &mut self.args) {
+            Some(browser::Choice::Replay(words, from)) => self.type_from_history(words, from),
+            Some(browser::Choice::Notice(notice)) => self.notice = Some(notice),
+            None => self.notice = None,
+        }
+    }
+
+    /// Starts a test of `words` from the history, going back to the usual
+    /// words for the test after.
+    fn type_from_history(&mut self, words: Vec<String>, from: String) {
+        let source = std::mem::replace(&mut self.source, Source::Text(words));
+        self.put_aside.get_or_insert(source);
+        self.replayed = Some(from);
+        self.warming_up = false;
+        self.words = self.source.generate(self.args.words);
+        self.retry();
+    }
+
+    /// Moves over to the profile picked in the menu, with its own settings.
+    fn switch_profile(&mut self) {
+        Paths::set_profile(self.args.profile.as_deref());
+
+        let config = Config::load().unwrap_or_else(|_| {
+            self.notice = Some("invalid config in that profile, using the defaults");
+            Config::default()
+        });
+
+        config.apply(&mut self.args, None);
+        use_history_store(&config);
+        self.theme = theme(&self.args, &config);
+        self.keymap = Keymap::new(self.args.keymap);
+        self.config = config;
+        self.daily_done = daily::is_done();
+    }
+
+    /// Leaves the tutorial for a first test, its speed the one to beat, or
+    /// straight for the menu.
+    fn end_tutorial(&mut self, calibrate: bool) {
+        let _ = tutorial::mark_done();
+
+        if calibrate {
+            self.calibrating = true;
+            self.start_from_menu();
+        } else {
+            self.state = State::Menu;
+        }
+    }
+
+    /// Rebuilds the word source from the options picked in the menu and
+    /// starts a fresh test.
+    fn start_from_menu(&mut self) {
+        self.session.clear();
+        self.session_id = history::new_id();
+        self.drill = None;
+        self.end_mistake_drill();
+
+        if let Source::Random(_) = self.source {
+            self.source = Source::Random(generator(&self.args));
+        }
+
+        if self.args.warmup {
+            self.warming_up = true;
+            self.words = warmup_words(&self.args);
+            self.retry();
+        } else {
+            self.reset();
+        }
+    }
+
+    /// Moves on to new words, leaving the warm-up if it was still going.
     fn reset(&mut self) {
-        self.words = Words::generate(self.args.words);
-        self.typed = Vec::with_capacity(self.words().len());
-        self.first_draw = true;
+        self.warming_up = false;
+
+        if let Some(source) = self.put_aside.take() {
+            self.source = source;
+            self.replayed = None;
+        }
+
+        // A generator command can fail between tests, keep the old words
+        // rather than starting an empty test.
+        let words = self.source.generate(self.args.words);
+        if !words.is_empty() {
+            self.words = words;
+        }
+
+        self.retry();
+    }
+
+    /// Starts over on the same words.
+    fn retry(&mut self) {
+        self.typed = Vec::with_capacity(self.words().chars().count());
+        self.times.clear();
+        self.corrected.clear();
+        self.incomplete = false;
+        self.restarts = 0;
+        self.keystrokes = 0;
+        self.caret.set(None);
+        self.shown.set(None);
+        self.reaction = None;
+        self.confetti = None;
+        self.podium = None;
+        self.standing.clear();
+        self.recorded = None;
+        self.flash = None;
+        self.swapped_case = 0;
+        self.warning = None;
+        self.notice = None;
+        self.timer = Timer::default();
+        self.state = start_state(&self.args);
+
+        if let Some(duel) = &mut self.duel {
+            duel.next_race();
+        }
+
+        self.tune_rival();
+    }
+
+    /// Sets the `--rival` to type evenly a little faster than the last tests
+    /// of the mode, or has it sit this test out without any.
+    #[expect(clippy::cast_possible_truncation)]
+    fn tune_rival(&mut self) {
+        if !self.args.rival || self.warming_up {
+            return;
+        }
+
+        self.rival = History::load_recent()
+            .ok()
+            .and_then(|history| history.recent_speed(&self.mode(), RIVAL_TESTS))
+            .map(|wpm| wpm * RIVAL_LEAD);
+
+        // Five characters to a word.
+        self.ghost = self.rival.map(|wpm| {
+            let each = Duration::from_secs_f64(12.0 / wpm.max(1.0));
+            (1..=self.words().chars().count() as u32)
+                .map(|n| each * n)
+                .collect()
+        });
+
+        if self.rival.is_none() {
+            self.notice = Some("no tests of this mode yet for a rival to go by");
+        }
+    }
+
+    /// Memory mode hides the text once the preview is over.
+    fn is_hidden(&self) -> bool {
+        self.args.memory.is_some() && !matches!(self.state, State::Preview { .. })
     }
 
     fn is_finished(&self) -> bool {
-        self.typed.len() >= self.words().len()
+        self.typed.len() >= self.words().chars().count()
     }
 
     fn words(&self) -> String {
         self.words.join(" ")
     }
 
-    #[expect(clippy::cast_precision_loss)]
+    fn scoring(&self) -> Scoring {
+        scoring(&self.args)
+    }
+
+    /// The test as typed, against `target`, the words as characters.
+    fn attempt<'a>(&'a self, target: &'a [char]) -> Attempt<'a> {
+        Attempt {
+            target,
+            typed: &self.typed,
+            times: &self.times,
+            incomplete: self.incomplete,
+        }
+    }
+
     fn wpm(&self) -> f64 {
-        let elapsed = self.timer.duration();
-        (self.words().len() / 5) as f64 / elapsed.as_secs_f64() * 60.0
+        let target: Vec<char> = self.words().chars().collect();
+        self.scoring()
+            .wpm(&self.attempt(&target), self.timer.duration())
+    }
+
+    /// How many keystrokes at the start `--grace` leaves out of the results.
+    fn graced(&self) -> usize {
+        let target: Vec<char> = self.words().chars().collect();
+        self.scoring().graced(&self.attempt(&target))
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn cpm(&self) -> f64 {
+        let chars = if self.incomplete {
+            self.typed.len()
+        } else {
+            self.words().chars().count()
+        };
+
+        chars as f64 / self.timer.duration().as_secs_f64() * 60.0
     }
 
     #[expect(clippy::cast_precision_loss)]
+    fn kps(&self) -> f64 {
+        self.keystrokes as f64 / self.timer.duration().as_secs_f64()
+    }
+
+    fn alignment(&self) -> Alignment {
+        let target: Vec<char> = self.words().chars().collect();
+        self.scoring().alignment(&self.attempt(&target))
+    }
+
     fn accuracy(&self) -> f64 {
-        let words = self.words();
+        self.alignment().accuracy()
+    }
 
-        let correct: Vec<_> = self
-            .typed
+    fn errors(&self) -> usize {
+        self.typed
             .iter()
-            .zip(words.chars())
-            .filter(|(c, target)| *c == target)
-            .collect();
+            .zip(self.words().chars())
+            .filter(|(c, target)| **c != *target)
+            .count()
+    }
 
-        (correct.len() as f64 / words.len() as f64) * 100.0
+    fn live_wpm(&self) -> f64 {
+        self.scoring().live_wpm(&self.typed, self.timer.elapsed())
     }
 
-    fn time_ms(&self) -> u128 {
-        let elapsed = self.timer.duration();
-        elapsed.as_millis()
+    /// The speeds picked with `--units` so far into the test.
+    #[expect(clippy::cast_precision_loss)]
+    fn live_speeds(&self) -> String {
+        let elapsed = self.timer.elapsed().as_secs_f64();
+        let per_second = |count: usize| {
+            if elapsed == 0.0 {
+                0.0
+            } else {
+                count as f64 / elapsed
+            }
+        };
+
+        self.args
+            .units
+            .iter()
+            .map(|unit| match unit {
+                Unit::Wpm => format!("{:.0} wpm", self.live_wpm()),
+                Unit::Cpm => format!("{:.0} cpm", per_second(self.typed.len()) * 60.0),
+                Unit::Kps => format!("{:.1} kps", per_second(self.keystrokes)),
+            })
+            .collect::<Vec<_>>()
+            .join(" · ")
     }
 
-    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
-        execute!(io::stdout(), SetCursorStyle::BlinkingBar)?;
+    #[expect(clippy::cast_precision_loss)]
+    fn live_accuracy(&self) -> f64 {
+        if self.typed.is_empty() {
+            return 100.0;
+        }
 
-        while self.state != State::Exit {
-            terminal.draw(|frame| {
-                self.draw(frame);
-                self.first_draw = false;
-            })?;
+        let correct = self.typed.len() - self.errors();
+        correct as f64 / self.typed.len() as f64 * 100.0
+    }
 
-            if self.state == State::Playing {
-                terminal.show_cursor()?;
-            }
+    /// The finished test's results for `--format`.
+    fn template_values(&self) -> Vec<(&'static str, String)> {
+        let speeds = stats::word_speeds(&self.words, &self.times);
+        let consistency = stats::consistency(&speeds).unwrap_or_default();
 
-            self.handle_events()?;
+        vec![
+            ("wpm", format!("{:.0}", self.wpm())),
+            ("cpm", format!("{:.0}", self.cpm())),
+            ("kps", format!("{:.1}", self.kps())),
+            ("acc", format!("{:.0}", self.accuracy())),
+            ("consistency", format!("{consistency:.0}")),
+            (
+                "time",
+                format!("{:.1}", self.timer.duration().as_secs_f64()),
+            ),
+            ("words", self.words.len().to_string()),
+            ("errors", self.alignment().errors().to_string()),
+            ("date", Date::today().to_string()),
+            ("flags", self.flag_labels()),
+            ("grace", self.graced().to_string()),
+            (
+                "difficulty",
+                format!("{:.0}", difficulty::rate(&self.words())),
+            ),
+            (
+                "reaction",
+                self.reaction
+                    .map(|reaction| reaction.as_millis().to_string())
+                    .unwrap_or_default(),
+            ),
+            (
+                "endurance",
+                self.endurance()
+                    .map(|held| format!("{held:.0}"))
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+
+    /// The results of the last finished test, to print once the terminal is
+    /// restored.
+    pub fn summary(&self) -> Option<String> {
+        if !self.roster.is_empty() && self.session.len() == self.roster.len() {
+            return Some(session::table(&self.roster, &self.session).join("\n"));
         }
 
-        Ok(())
+        let summary = self.last_summary.clone()?;
+
+        match self.breaks_taken() {
+            Some(breaks) if !self.args.json => Some(format!("{summary}\n{breaks}")),
+            _ => Some(summary),
+        }
     }
 
-    fn finish_screen(&self, frame: &mut Frame) {
-        let area = center(frame.area(), Constraint::Length(40), Constraint::Length(11));
+    /// How many of the suggested breaks were taken, once any were.
+    fn breaks_taken(&self) -> Option<String> {
+        let (taken, suggested) = self.breaks.as_ref()?.adherence()?;
+        Some(format!("breaks taken: {taken} of {suggested}"))
+    }
 
-        let title = format!("{} words", self.words.len()).yellow();
+    /// The finished test's results as JSON with `--json`, or in the
+    /// `--format` template.
+    fn summary_line(&self) -> String {
+        let values = self.template_values();
 
-        let block = Block::bordered()
-            .title(title)
-            .border_style(Style::default().yellow())
-            .border_type(BorderType::Rounded)
-            .padding(Padding::uniform(2));
+        if self.args.json {
+            template::json(&values)
+        } else {
+            let template = self.args.format.as_deref().unwrap_or(template::SUMMARY);
+            template::render(template, &values)
+        }
+    }
 
-        let inner = block.inner(area);
+    /// A compact summary of the finished test for sharing.
+    fn card(&self) -> String {
+        if let Some(template) = &self.args.format {
+            return template::render(template, &self.template_values());
+        }
 
-        block.render(area, frame.buffer_mut());
+        let speeds = stats::word_speeds(&self.words, &self.times);
+        let consistency = stats::consistency(&speeds).unwrap_or_default();
 
-        let layout = Layout::vertical([Constraint::Length(1); 3])
-            .flex(Flex::SpaceBetween)
-            .areas::<3>(inner);
+        let wpm = match self.args.wpm_formula {
+            WpmFormula::Chars => "wpm",
+            WpmFormula::Words => "wpm (words)",
+        };
 
-        let time = format!("Time: {}ms", self.time_ms());
-        Paragraph::new(time).render(layout[0], frame.buffer_mut());
+        format!(
+            "typers · {} words\n{:.0} {wpm} · {:.0}% acc · {consistency:.0}% consistency\n{}",
+            self.words.len(),
+            self.wpm(),
+            self.accuracy(),
+            Date::today(),
+        )
+    }
+
+    fn copy_card(&mut self) {
+        let card = self.card();
 
-        let wpm = format!("WPM: {}", self.wpm());
-        Paragraph::new(wpm).render(layout[1], frame.buffer_mut());
+        let copied = match &mut self.clipboard {
+            Some(clipboard) => clipboard.set_text(card),
+            None => Clipboard::new()
+                .and_then(|clipboard| self.clipboard.insert(clipboard).set_text(card)),
+        };
 
-        let accuracy = format!("Accuracy: {}%", self.accuracy());
-        Paragraph::new(accuracy).render(layout[2], frame.buffer_mut());
+        self.notice = Some(match copied {
+            Ok(()) => "copied to clipboard",
+            Err(_) => "clipboard unavailable",
+        });
     }
 
-    #[expect(clippy::cast_possible_truncation)]
-    fn playing_screen(&self, frame: &mut Frame) {
-        let words = self.words();
+    /// Writes the words as they were given, so `--file` can load the same
+    /// test again.
+    fn save_words(&mut self) {
+        let saved = write_file(self.args.save.as_deref(), "saved.txt", &self.words());
 
-        let area = center(
-            frame.area(),
-            Constraint::Length(words.len() as u16),
-            Constraint::Percentage(100),
-        );
+        self.notice = Some(match saved {
+            Ok(()) => "words saved",
+            Err(_) => "couldn't save words",
+        });
+    }
 
-        let block = Block::new().padding(Padding::top(area.height / 2));
+    fn export_replay(&mut self) {
+        let page = replay::html(&self.words(), &self.typed, &self.times);
+        let exported = write_file(self.args.replay.as_deref(), "replay.html", &page);
 
-        let mut typed: Vec<Span> = self
-            .typed
-            .iter()
-            .zip(words.chars())
-            .map(|(c, target)| {
-                if target == *c {
-                    Span::raw(target.to_string()).white()
-                } else {
-                    Span::raw(target.to_string()).red()
-                }
-            })
-            .collect();
+        self.notice = Some(match exported {
+            Ok(()) => "replay exported",
+            Err(_) => "couldn't export replay",
+        });
+    }
 
-        typed.push(Span::raw(words[typed.len()..].to_string()));
+    fn export_keystrokes(&mut self) {
+        let csv = keystrokes::csv(&self.words(), &self.typed, &self.times);
 
-        let typed: Vec<Span> = typed.into_iter().collect();
+        self.notice = Some(match write_file(None, "keystrokes.csv", &csv) {
+            Ok(()) => "keystrokes exported",
+            Err(_) => "couldn't export keystrokes",
+        });
+    }
 
-        Paragraph::new(Line::from(typed))
-            .block(block)
-            .wrap(Wrap { trim: true })
-            .render(area, frame.buffer_mut());
+    /// Writes `note` on the finished test in the history.
+    fn write_note(&mut self, note: &str) {
+        let Some(id) = &self.recorded else {
+            self.notice = Some("no result in the history to note");
+            return;
+        };
+
+        let written = History::load().and_then(|mut history| {
+            history.write_note(id, note)?;
+            history.save()
+        });
+
+        self.notice = Some(match (written, note.is_empty()) {
+            (Ok(()), false) => "note saved",
+            (Ok(()), true) => "note taken off",
+            (Err(_), _) => "couldn't save the note",
+        });
+    }
 
-        if self.first_draw {
-            frame.set_cursor_position(Position::new(area.x, area.height / 2));
+    fn export_ghost(&mut self) {
+        let exported = Saved::new(&self.words, &self.typed, &self.times)
+            .to_toml()
+            .and_then(|ghost| write_file(None, "ghost.toml", &ghost));
+
+        self.notice = Some(match exported {
+            Ok(()) => "ghost saved",
+            Err(_) => "couldn't save ghost",
+        });
+    }
+
+    /// Starts a test at the next longer length preset from the config,
+    /// going back round to the shortest after the longest.
+    fn next_preset(&mut self) {
+        let mut presets: Vec<usize> = self.config.presets.values().copied().collect();
+        presets.sort_unstable();
+
+        let next = presets
+            .iter()
+            .find(|words| **words > self.args.words)
+            .or(presets.first());
+
+        if let Some(&words) = next {
+            self.args.words = words;
+            self.start_from_menu();
         }
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        match self.state {
-            State::Playing => self.playing_screen(frame),
-            State::Finished => self.finish_screen(frame),
-            State::Exit => unreachable!(),
+    /// The name of the length preset the test is at, if any.
+    fn preset(&self) -> Option<&str> {
+        self.config
+            .presets
+            .iter()
+            .find(|(_, words)| **words == self.args.words)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Switches to words that work the finger with the most mistakes.
+    fn drill_weakest_finger(&mut self) {
+        let Some(finger) = stats::weakest(&self.finger_stats()) else {
+            self.notice = Some("too few keystrokes to pick a finger");
+            return;
+        };
+
+        self.drill = Some(finger);
+        self.source = Source::Random(generator(&self.args).filter(Filter::Finger(finger)));
+        self.reset();
+    }
+
+    /// Starts a test of the words that have been slowest or most often
+    /// typed wrong across the history.
+    fn practice_weak_words(&mut self) {
+        let words = History::load().unwrap_or_default().weak_words();
+
+        if words.is_empty() {
+            self.notice = Some("no slow or missed words in the history yet");
+            return;
         }
+
+        self.type_from_history(words, "the slowest and most missed words".to_string());
     }
 
-    fn handle_key_event(&mut self, key_event: KeyEvent) {
-        match self.state {
-            State::Playing => match key_event.code {
-                KeyCode::Esc => self.exit(),
-                KeyCode::Tab => self.reset(),
-                KeyCode::Char(c) => {
-                    if !self.timer.is_started() {
-                        self.timer.start();
-                    }
+    fn finger_stats(&self) -> Vec<stats::FingerStats> {
+        let text: Vec<char> = self.words().chars().collect();
+        stats::finger_stats(&text, &self.typed, &self.times)
+    }
 
-                    self.typed.push(c);
+    /// How many characters the ghost has typed by now.
+    fn ghost_at(&self) -> Option<usize> {
+        let times = self.ghost.as_ref()?;
+        let elapsed = self.timer.elapsed();
 
-                    if self.is_finished() {
-                        self.state = State::Finished;
-                        self.timer.end();
-                    }
-                }
-                _ => {}
-            },
-            State::Finished => match key_event.code {
-                KeyCode::Char('q') | KeyCode::Enter => self.exit(),
-                _ => {}
-            },
-            State::Exit => unreachable!(),
+        Some(times.partition_point(|time| *time <= elapsed))
+    }
+
+    /// How far the ghost or the opponent of a duel has got.
+    fn rival_at(&self) -> Option<usize> {
+        self.ghost_at()
+            .or_else(|| self.duel.as_ref().map(|duel| duel.opponent_at))
+    }
+
+    /// How long until the ghost's next keystroke, while it is still going.
+    fn ghost_wait(&self) -> Option<Duration> {
+        if self.state != State::Playing || !self.timer.is_started() {
+            return None;
         }
+
+        let next = self.ghost.as_ref()?.get(self.ghost_at()?)?;
+        Some(next.saturating_sub(self.timer.elapsed()))
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        match event::read()? {
-            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
-                self.handle_key_event(key_event);
+    fn time_ms(&self) -> u128 {
+        let elapsed = self.timer.duration();
+        elapsed.as_millis()
+    }
+
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        execute!(io::stdout(), cursor_style(&self.args), EnableMouseCapture)?;
+
+        let result = self.main_loop(terminal);
+
+        execute!(
+            io::stdout(),
+            DisableMouseCapture,
+            SetCursorStyle::DefaultUserShape
+        )?;
+        result
+    }
+
+    /// Runs the tests as plain lines of text rather than a drawn screen,
+    /// for `--screen-reader`: the words on a line of their own, what is
+    /// typed echoed after them, and a line for each change in between.
+    pub fn run_plain(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        leave_raw_mode_on_panic();
+        let result = self.plain_loop();
+        terminal::disable_raw_mode()?;
+        result
+    }
+
+    fn plain_loop(&mut self) -> io::Result<()> {
+        let mut out = io::stdout();
+        let mut before = None;
+
+        while self.state != State::Exit {
+            let now = Shown {
+                state: self.state,
+                words: self.words.clone(),
+                typed: self.typed.len(),
+                started: self.timer.is_started(),
+                notice: self.notice,
+            };
+
+            if before.as_ref() != Some(&now) {
+                // Raw mode leaves newlines without their carriage return.
+                let text = self.announcement(before.as_ref(), &now);
+                write!(out, "{}", text.replace('\n', "\r\n"))?;
+                out.flush()?;
+                before = Some(now);
             }
-            _ => {}
-        };
 
-        Ok(())
+            self.handle_events()?;
+        }
+
+        writeln!(out, "\r")
     }
-}
 
-fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
-    let [area] = Layout::horizontal([horizontal])
-        .flex(Flex::Center)
-        .areas(area);
+    /// What to write for the change from `before` to `now`.
+    fn announcement(&self, before: Option<&Shown>, now: &Shown) -> String {
+        let mut text = String::new();
+        let moved = before.is_none_or(|before| before.state != now.state);
+        let new_test = before.is_none_or(|before| {
+            !before.is_typing()
+                || before.words != now.words
+                || (now.typed < before.typed && !now.started)
+        });
 
-    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
+        if now.is_typing() && new_test {
+            let _ = writeln!(text, "\n{}", self.words());
+        }
 
-    area
+        let started = before.is_some_and(|before| before.started && !new_test);
+        if now.state == State::Playing && now.started && !started {
+            text.push_str("started\n");
+        }
+
+        if let Some(before) = before.filter(|_| !new_test) {
+            if now.typed > before.typed {
+                text.extend(&self.typed[before.typed..now.typed]);
+            } else {
+                text.push_str(&"\u{8} \u{8}".repeat(before.typed - now.typed));
+            }
+        }
+
+        match now.state {
+            State::Preview { until } if moved => {
+                let left = until.saturating_duration_since(Instant::now());
+                let _ = writeln!(text, "memorize it, {}s", left.as_secs() + 1);
+            }
+            State::Ready { until: None } if moved && self.args.start == Start::AnyKey => {
+                text.push_str("press any key to begin\n");
+            }
+            State::Ready { until: None } if moved => text.push_str("press enter to start\n"),
+            State::Ready { until: Some(until) } if moved && self.is_resting() => {
+                let left = until.saturating_duration_since(Instant::now());
+                let _ = writeln!(text, "rest, {}s", left.as_secs() + 1);
+            }
+            State::Ready { until: Some(until) } if moved => {
+                let left = until.saturating_duration_since(Instant::now());
+                let _ = writeln!(text, "starting in {}s", left.as_secs() + 1);
+            }
+            State::Finished if moved => {
+                let _ = writeln!(text, "\nfinished");
+
+                for stat in self.stats() {
+                    let _ = writeln!(text, "{stat}");
+                }
+
+                text.push_str("r retry, tab new test, q quit\n");
+            }
+            State::Summary if moved => {
+                let _ = writeln!(text, "\nsession over");
+
+                if let Some(summary) = self.summary() {
+                    let _ = writeln!(text, "{summary}");
+                }
+
+                text.push_str("enter quit\n");
+            }
+            State::Resume if moved => {
+                text.push_str("a test was left unfinished, y resume, n start fresh\n");
+            }
+            _ => {}
+        }
+
+        if let Some(notice) = now.notice {
+            if before.is_none_or(|before| before.notice != now.notice) {
+                let _ = writeln!(text, "{notice}");
+            }
+        }
+
+        text
+    }
+
+    fn main_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        /// Frames slower than this are logged.
+        const SLOW_FRAME: Duration = Duration::from_millis(8);
+
+        while self.state != State::Exit {
+            if self.dirty {
+                let drawing = Instant::now();
+                terminal.draw(|frame| self.draw(frame))?;
+                self.dirty = false;
+
+                let took = drawing.elapsed();
+                if took > SLOW_FRAME {
+                    self.log("frame", format_args!("ms={}", took.as_millis()));
+                }
+            }
+
+            let state = self.state;
+            self.handle_events()?;
+
+            let to = self.state;
+            if to != state {
+                self.log("state", format_args!("from={state:?} to={to:?}"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// How the race against the `--ghost` or `--rival` went.
+    fn ghost_result(&self) -> Option<String> {
+        let times = self.ghost.as_ref()?;
+        let finished = times.len() == self.words().chars().count();
+
+        if let Some(wpm) = self.rival {
+            let ahead = times.last()?.as_secs_f64() - self.timer.duration().as_secs_f64();
+            let result = if ahead >= 0.0 { "won" } else { "lost" };
+            return Some(format!(
+                "Rival: {wpm:.0} wpm ({result} by {:.1}s)",
+                ahead.abs()
+            ));
+        }
+
+        Some(match times.last() {
+            Some(ghost) if finished => {
+                let ahead = ghost.as_secs_f64() - self.timer.duration().as_secs_f64();
+                let result = if ahead >= 0.0 { "won" } else { "lost" };
+                format!(
+                    "Ghost: {}ms ({result} by {:.1}s)",
+                    ghost.as_millis(),
+                    ahead.abs()
+                )
+            }
+            _ => "Ghost: didn't finish".to_string(),
+        })
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn stats(&self) -> Vec<String> {
+        let mut stats = vec![format!("{}: {}ms", text(Text::Time), self.time_ms())];
+
+        if let Some(reaction) = self.reaction {
+            stats.push(format!(
+                "{}: {}ms",
+                text(Text::Reaction),
+                reaction.as_millis()
+            ));
+        }
+
+        for unit in &self.args.units {
+            stats.push(match unit {
+                Unit::Wpm => format!("WPM ({}): {}", self.args.wpm_formula.label(), self.wpm()),
+                Unit::Cpm => format!("CPM: {:.0}", self.cpm()),
+                Unit::Kps => format!("Keystrokes/s: {:.1}", self.kps()),
+            });
+        }
+
+        stats.push(format!("{}: {}%", text(Text::Accuracy), self.accuracy()));
+        stats.push(format!(
+            "{}: {:.0}/100",
+            text(Text::Difficulty),
+            difficulty::rate(&self.words())
+        ));
+
+        if let Some(held) = self.endurance() {
+            stats.push(format!("Endurance: {held:.0}% of the opening pace"));
+        }
+
+        match (self.args.grace, self.graced()) {
+            (_, 0) => {}
+            (Some(Grace::Word), _) => stats.push("Grace: first word left out".to_string()),
+            (_, graced) => stats.push(format!("Grace: first {graced} keystrokes left out")),
+        }
+
+        stats.extend(self.ghost_result());
+        stats.extend(self.standing.iter().cloned());
+
+        let alignment = self.alignment();
+
+        // Only the kinds that happened, so the line fits the box.
+        let errors: Vec<_> = [
+            (alignment.substitutions, "wrong"),
+            (alignment.insertions, "extra"),
+            (alignment.omissions, "missed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, kind)| format!("{count} {kind}"))
+        .collect();
+
+        if !errors.is_empty() {
+            stats.push(format!("Errors: {}", errors.join(" · ")));
+        }
+
+        let corrected = self.corrected.len();
+        let made = corrected + alignment.errors();
+
+        if made > 0 {
+            stats.push(format!(
+                "Corrected: {corrected} of {made} ({:.0}%)",
+                corrected as f64 / made as f64 * 100.0
+            ));
+        }
+
+        let speeds = stats::word_speeds(&self.words, &self.times);
+        let fastest = speeds.iter().max_by(|a, b| a.wpm.total_cmp(&b.wpm));
+        let slowest = speeds.iter().min_by(|a, b| a.wpm.total_cmp(&b.wpm));
+
+        if let (Some(fastest), Some(slowest)) = (fastest, slowest) {
+            stats.push(format!(
+                "{}: {} ({:.0} wpm)",
+                text(Text::Fastest),
+                fastest.word,
+                fastest.wpm
+            ));
+            stats.push(format!(
+                "{}: {} ({:.0} wpm)",
+                text(Text::Slowest),
+                slowest.word,
+                slowest.wpm
+            ));
+        }
+
+        if let Source::Chunks { file, words } = &self.source {
+            let progress = Progress::load(file);
+
+            if let Some((wpm, accuracy)) = progress.averages() {
+                stats.push(format!(
+                    "File: {:.0}% · {wpm:.0} wpm · {accuracy:.0}% acc",
+                    progress.percent(words)
+                ));
+            }
+        }
+
+        if let Some(duel) = &self.duel {
+            stats.push(match duel.opponent_done {
+                Some((run, time)) => {
+                    let ahead = time.as_secs_f64() - self.timer.duration().as_secs_f64();
+                    let result = if ahead >= 0.0 { "won" } else { "lost" };
+                    format!(
+                        "{}: {:.0} wpm ({result} by {:.1}s)",
+                        text(Text::Opponent),
+                        run.wpm,
+                        ahead.abs()
+                    )
+                }
+                None if duel.gone => format!("{}: left", text(Text::Opponent)),
+                None => format!("{}: still typing", text(Text::Opponent)),
+            });
+        }
+
+        let flags = self.flag_labels();
+        if !flags.is_empty() {
+            stats.push(format!("Flagged: {}", flags.replace(',', " · ")));
+        }
+
+        stats
+    }
+
+    fn resume_screen(&self, frame: &mut Frame) {
+        let Some(saved) = &self.saved else {
+            return;
+        };
+
+        let typed = saved.typed.chars().count();
+        let total = saved.words.join(" ").chars().count();
+
+        let block = Block::bordered()
+            .title(Span::styled("resume previous test?", self.theme.accent))
+            .title_bottom(Span::styled("y resume · n start fresh", self.theme.muted))
+            .border_style(self.theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        let area = center(frame.area(), Constraint::Length(40), Constraint::Length(5));
+
+        Paragraph::new(format!("{typed} of {total} characters typed"))
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    fn tutorial_screen(&self, frame: &mut Frame, page: usize) {
+        let (title, lines) = tutorial::page(page, &self.keymap);
+        let hint = if page + 1 < tutorial::PAGES {
+            format!("{}/{} · enter next · esc skip", page + 1, tutorial::PAGES)
+        } else {
+            format!(
+                "{}/{} · enter start the test · esc skip",
+                page + 1,
+                tutorial::PAGES
+            )
+        };
+
+        let block = Block::bordered()
+            .title(Span::styled(title, self.theme.accent))
+            .title_bottom(Span::styled(hint, self.theme.muted))
+            .border_style(self.theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        let area = center(
+            frame.area(),
+            Constraint::Length(TUTORIAL_WIDTH),
+            Constraint::Length(TUTORIAL_HEIGHT),
+        );
+
+        Paragraph::new(lines.into_iter().map(Line::raw).collect::<Vec<_>>())
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Both players' results side by side, averaged over the rounds of a
+    /// series, the winner's box highlighted.
+    #[expect(clippy::cast_precision_loss)]
+    fn versus_screen(&self, frame: &mut Frame) {
+        const WIDTH: u16 = 24;
+
+        let wins = session::wins(&self.session);
+        let winner = match wins {
+            [first, second] if first > second => Some(0),
+            [first, second] if second > first => Some(1),
+            _ => None,
+        };
+        let series = self.args.best_of.is_some();
+
+        let area = center(
+            frame.area(),
+            Constraint::Length(WIDTH * 2 + 2),
+            Constraint::Length(6 + u16::from(series)),
+        );
+        let [left, _, right] = Layout::horizontal([
+            Constraint::Length(WIDTH),
+            Constraint::Length(2),
+            Constraint::Length(WIDTH),
+        ])
+        .areas(area);
+
+        for (player, area) in [left, right].into_iter().enumerate() {
+            let runs: Vec<Run> = self
+                .session
+                .iter()
+                .skip(player)
+                .step_by(2)
+                .copied()
+                .collect();
+            let Some(summary) = session::summarize(&runs) else {
+                continue;
+            };
+            let accuracy = runs.iter().map(|run| run.accuracy).sum::<f64>() / runs.len() as f64;
+
+            let (style, title) = match winner {
+                Some(winner) if winner == player => {
+                    (self.theme.accent, format!("player {} wins", player + 1))
+                }
+                Some(_) => (self.theme.muted, format!("player {}", player + 1)),
+                None => (self.theme.accent, format!("player {} draws", player + 1)),
+            };
+
+            let mut lines = vec![
+                Line::raw(format!("WPM: {:.0}", summary.mean)),
+                Line::raw(format!("Accuracy: {accuracy:.0}%")),
+            ];
+
+            if series {
+                lines.push(Line::raw(format!("Rounds won: {}", wins[player])));
+            }
+
+            let block = Block::bordered()
+                .title(Span::styled(title, style))
+                .border_style(style)
+                .border_type(BorderType::Rounded)
+                .padding(Padding::uniform(1));
+
+            Paragraph::new(lines)
+                .block(block)
+                .render(area, frame.buffer_mut());
+        }
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    fn summary_screen(&self, frame: &mut Frame) {
+        let Some(summary) = session::summarize(&self.session) else {
+            return;
+        };
+
+        let first = self.session.first().map_or(0.0, |run| run.accuracy);
+        let last = self.session.last().map_or(0.0, |run| run.accuracy);
+        let speeds: Vec<_> = self
+            .session
+            .iter()
+            .map(|run| format!("{:.0}", run.wpm))
+            .collect();
+
+        let mut lines = vec![
+            Line::raw(format!("Mean WPM: {:.0}", summary.mean)),
+            Line::raw(format!("Median WPM: {:.0}", summary.median)),
+            Line::raw(format!("Best WPM: {:.0}", summary.best)),
+            Line::raw(format!(
+                "Accuracy: {first:.0}% → {last:.0}% ({:+.0}%)",
+                summary.accuracy_change
+            )),
+            Line::styled(speeds.join(" · "), self.theme.muted),
+        ];
+
+        if self.args.intervals.is_some() {
+            let accuracies: Vec<_> = self
+                .session
+                .iter()
+                .map(|run| format!("{:.0}%", run.accuracy))
+                .collect();
+            lines.push(Line::styled(accuracies.join(" · "), self.theme.muted));
+        }
+
+        if let Some(breaks) = self.breaks_taken() {
+            lines.push(Line::raw(breaks));
+        }
+
+        let block = Block::bordered()
+            .title(Span::styled(
+                format!(
+                    "{} · {} tests",
+                    match (&self.playlist, self.args.intervals) {
+                        (Some(playlist), _) => &playlist.name,
+                        (None, Some(_)) => "intervals",
+                        (None, None) => "session",
+                    },
+                    self.session.len()
+                ),
+                self.theme.accent,
+            ))
+            .title_bottom(Span::styled("enter quit", self.theme.muted))
+            .border_style(self.theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1));
+
+        let area = center(
+            frame.area(),
+            Constraint::Length(40),
+            Constraint::Length(lines.len() as u16 + 2),
+        );
+
+        Paragraph::new(lines)
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Every student's result with `--roster`, fastest first.
+    #[expect(clippy::cast_possible_truncation)]
+    fn class_screen(&self, frame: &mut Frame) {
+        let lines: Vec<Line> = session::table(&self.roster, &self.session)
+            .into_iter()
+            .map(Line::raw)
+            .collect();
+
+        let block = Block::bordered()
+            .title(Span::styled(
+                format!("class · {} students", self.roster.len()),
+                self.theme.accent,
+            ))
+            .title_bottom(Span::styled("enter quit", self.theme.muted))
+            .border_style(self.theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1));
+
+        let area = center(
+            frame.area(),
+            Constraint::Length(50),
+            Constraint::Length(lines.len() as u16 + 2),
+        );
+
+        Paragraph::new(lines)
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// Where the results box and the row of buttons below it go. Shared by
+    /// drawing and mouse handling so clicks land on what was drawn.
+    #[expect(clippy::cast_possible_truncation)]
+    fn results_layout(&self, screen: Rect) -> (Rect, Padding, Vec<(Button, Rect)>) {
+        if self.is_compact(screen) {
+            let line = center(screen, Constraint::Percentage(100), Constraint::Length(1));
+            return (line, Padding::ZERO, Vec::new());
+        }
+
+        let stats = self.stats().len() as u16;
+
+        // Drop the spacing between stats when the terminal can't fit it.
+        let full = stats * 2 + 5;
+        let (height, padding) = if screen.width < 40 || screen.height < full + 1 {
+            (stats + 2, Padding::horizontal(1))
+        } else {
+            (full, Padding::uniform(2))
+        };
+
+        let [area, row] = Layout::vertical([Constraint::Length(height), Constraint::Length(1)])
+            .areas(center(
+                screen,
+                Constraint::Length(40),
+                Constraint::Length(height + 1),
+            ));
+
+        let buttons = Layout::horizontal(
+            Button::ALL.map(|button| Constraint::Length(button.label().chars().count() as u16)),
+        )
+        .flex(Flex::Center)
+        .spacing(2)
+        // The labels can take more than the box in some languages.
+        .split(Rect {
+            x: screen.x,
+            width: screen.width,
+            ..row
+        });
+
+        let buttons = Button::ALL
+            .into_iter()
+            .zip(buttons.iter().copied())
+            .collect();
+
+        (area, padding, buttons)
+    }
+
+    /// Whether the results go on a single line, by `--compact-results` or
+    /// because the box and its buttons don't fit.
+    #[expect(clippy::cast_possible_truncation)]
+    fn is_compact(&self, screen: Rect) -> bool {
+        self.args.compact_results || screen.height < self.stats().len() as u16 + 3
+    }
+
+    /// The results as a line in the middle of the screen, followed by the
+    /// notice if there is one.
+    fn compact_results(&self, frame: &mut Frame) {
+        let (area, _, _) = self.results_layout(frame.area());
+        let summary = template::render(template::SUMMARY, &self.template_values());
+
+        let mut spans = vec![Span::styled(summary, self.theme.accent)];
+        if let Some(notice) = self.notice {
+            spans.push(Span::styled(format!(" · {notice}"), self.theme.muted));
+        }
+
+        Paragraph::new(Line::from(spans))
+            .centered()
+            .render(area, frame.buffer_mut());
+    }
+
+    fn finish_screen(&self, frame: &mut Frame) {
+        if self.is_compact(frame.area()) {
+            self.compact_results(frame);
+            return;
+        }
+
+        let stats = self.stats();
+        let (area, padding, buttons) = self.results_layout(frame.area());
+
+        let title = Span::styled(format!("{} words", self.words.len()), self.theme.accent);
+
+        let block = Block::bordered()
+            .title(title)
+            .border_style(self.theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(padding);
+
+        let block = match self.notice {
+            Some(notice) => block.title_bottom(Span::styled(notice, self.theme.muted)),
+            None => block,
+        };
+
+        let inner = block.inner(area);
+
+        block.render(area, frame.buffer_mut());
+
+        let layout = Layout::vertical(vec![Constraint::Length(1); stats.len()])
+            .flex(Flex::SpaceBetween)
+            .split(inner);
+
+        for (stat, area) in stats.into_iter().zip(layout.iter()) {
+            Paragraph::new(stat).render(*area, frame.buffer_mut());
+        }
+
+        for (button, area) in &buttons {
+            Paragraph::new(Span::styled(button.label(), self.theme.muted))
+                .render(*area, frame.buffer_mut());
+        }
+
+        let speeds = stats::word_speeds(&self.words, &self.times);
+
+        let mut details: Vec<Line> = Vec::new();
+
+        if let Some(typing) = self.breaks.as_ref().and_then(Breaks::due) {
+            details.push(Line::styled(
+                format!(" {} min typed, time for a break ", typing.as_secs() / 60),
+                self.theme.warning,
+            ));
+        }
+
+        for panel in &self.args.panels {
+            match panel {
+                Panel::Review => details.push(Line::from(self.review_spans())),
+                Panel::Words => details.push(
+                    speeds
+                        .iter()
+                        .flat_map(|speed| {
+                            [
+                                Span::raw(speed.word),
+                                Span::styled(format!(" {:.0}  ", speed.wpm), self.theme.muted),
+                            ]
+                        })
+                        .collect(),
+                ),
+                Panel::Rhythm => details.extend(self.rhythm()),
+                Panel::Fatigue if self.args.endurance.is_some() => details.push(self.fatigue()),
+                Panel::Fingers => details.push(self.fingers()),
+                Panel::Keys => match self.source {
+                    Source::Numbers => {
+                        details.push(self.char_accuracy("digits", |c| c.is_ascii_digit()));
+                    }
+                    Source::Symbols => {
+                        details.push(self.char_accuracy("symbols", |c| c.is_ascii_punctuation()));
+                    }
+                    _ => {}
+                },
+                Panel::Fatigue => {}
+            }
+        }
+
+        let below = buttons.first().map_or(area, |(_, row)| *row);
+        self.details(frame, below, &details);
+        self.podium(frame, area);
+    }
+
+    /// Stacks wrapped paragraphs of extra detail below the results.
+    #[expect(clippy::cast_possible_truncation)]
+    fn details(&self, frame: &mut Frame, results: Rect, details: &[Line]) {
+        let [_, below] =
+            Layout::vertical([Constraint::Length(results.bottom()), Constraint::Fill(1)])
+                .areas(frame.area());
+
+        let width = (self.words().chars().count() as u16)
+            .max(results.width)
+            .min(below.width);
+        let mut area = center(
+            below,
+            Constraint::Length(width),
+            Constraint::Percentage(100),
+        );
+
+        for detail in details {
+            let lines = wrap::wrap(detail, usize::from(width));
+            let [_, detail_area, rest] = Layout::vertical([
+                Constraint::Length(1),
+                Constraint::Length(lines.len() as u16),
+                Constraint::Fill(1),
+            ])
+            .areas(area);
+
+            Paragraph::new(lines).render(detail_area, frame.buffer_mut());
+            area = rest;
+        }
+    }
+
+    /// Accuracy on every character picked by `keep`, for drills on a few
+    /// keys.
+    fn char_accuracy(&self, label: &str, keep: impl Fn(char) -> bool) -> Line<'_> {
+        let text: Vec<char> = self.words().chars().collect();
+        let stats = stats::char_stats(&text, &self.typed, keep);
+
+        std::iter::once(Span::raw(format!("{label} ")))
+            .chain(stats.iter().flat_map(|stats| {
+                [
+                    Span::raw(stats.c.to_string()),
+                    Span::styled(
+                        format!(" {:.0}%  ", stats.accuracy()),
+                        self.theme.heat(stats.accuracy()),
+                    ),
+                ]
+            }))
+            .collect()
+    }
+
+    /// Accuracy and time per key for every finger used.
+    fn fingers(&self) -> Line<'_> {
+        std::iter::once(Span::raw("fingers "))
+            .chain(self.finger_stats().iter().flat_map(|stats| {
+                [
+                    Span::raw(stats.finger.name()),
+                    Span::styled(
+                        format!(" {:.0}% {}ms  ", stats.accuracy(), stats.time.as_millis()),
+                        self.theme.heat(stats.accuracy()),
+                    ),
+                ]
+            }))
+            .collect()
+    }
+
+    /// The speed over each stretch of an endurance run.
+    fn fatigue_speeds(&self) -> Vec<f64> {
+        const STRETCHES: usize = 20;
+        stats::fatigue(&self.times, self.timer.duration(), STRETCHES)
+    }
+
+    /// How much of its opening speed an endurance run held to the end.
+    fn endurance(&self) -> Option<f64> {
+        self.args.endurance?;
+        stats::endurance(&self.fatigue_speeds())
+    }
+
+    /// Bars of the speed from the start of an endurance run to its end.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn fatigue(&self) -> Line<'_> {
+        let speeds = self.fatigue_speeds();
+        let most = speeds.iter().copied().fold(0.0, f64::max);
+
+        let (Some(first), Some(last)) = (speeds.first(), speeds.last()) else {
+            return Line::default();
+        };
+
+        if most == 0.0 {
+            return Line::default();
+        }
+
+        let bars: String = speeds
+            .iter()
+            .map(|wpm| BARS[(wpm / most * (BARS.len() - 1) as f64).round() as usize])
+            .collect();
+
+        Line::from(vec![
+            Span::raw("fatigue "),
+            Span::styled(bars, self.theme.chart),
+            Span::styled(format!(" {first:.0} to {last:.0} wpm"), self.theme.axis),
+        ])
+    }
+
+    /// A histogram of the gaps between keystrokes, followed by the letter
+    /// transitions that took the longest.
+    fn rhythm(&self) -> Vec<Line<'_>> {
+        const BUCKETS: usize = 10;
+
+        let counts = stats::rhythm(&self.times, BUCKETS);
+        let Some(&most) = counts.iter().max().filter(|most| **most > 0) else {
+            return Vec::new();
+        };
+
+        let bars: String = counts
+            .iter()
+            .map(|count| BARS[count * (BARS.len() - 1) / most])
+            .collect();
+
+        let mut lines = vec![Line::from(vec![
+            Span::raw("rhythm "),
+            Span::styled(bars, self.theme.chart),
+            Span::styled(
+                format!(
+                    " 0-{}ms+",
+                    stats::RHYTHM_BUCKET.as_millis() * (BUCKETS as u128 - 1)
+                ),
+                self.theme.axis,
+            ),
+        ])];
+
+        let text: Vec<char> = self.words().chars().collect();
+        let hesitations = stats::hesitations(&text, &self.times, 3);
+
+        if !hesitations.is_empty() {
+            let shown = |c: char| if c == ' ' { '␣' } else { c };
+
+            lines.push(
+                std::iter::once(Span::raw("pauses "))
+                    .chain(hesitations.iter().flat_map(|hesitation| {
+                        [
+                            Span::raw(format!(
+                                "{}{}",
+                                shown(hesitation.from),
+                                shown(hesitation.to)
+                            )),
+                            Span::styled(
+                                format!(" {}ms  ", hesitation.pause.as_millis()),
+                                self.theme.muted,
+                            ),
+                        ]
+                    }))
+                    .collect(),
+            );
+        }
+
+        lines
+    }
+
+    /// One span per character of the text, colored by what was typed over it,
+    /// or over its word with `--word-errors`.
+    fn spans(&self) -> Vec<Span<'_>> {
+        let target: Vec<char> = self.words().chars().collect();
+        let mut typed = self.typed.iter();
+
+        let mut spans: Vec<Span> = target
+            .iter()
+            .map(|target| match typed.next() {
+                Some(c) if c == target => Span::styled(target.to_string(), self.theme.correct),
+                Some(_) => Span::styled(target.to_string(), self.theme.incorrect),
+                None => Span::styled(target.to_string(), self.theme.pending),
+            })
+            .collect();
+
+        if self.args.word_errors {
+            for (word, wrong) in self.word_mistakes() {
+                if wrong {
+                    for span in &mut spans[word] {
+                        span.style = self.theme.incorrect;
+                    }
+                }
+            }
+        }
+
+        spans
+    }
+
+    /// The text as typed for looking back over, with characters that were
+    /// only right after a correction told apart from the rest.
+    fn review_spans(&self) -> Vec<Span<'_>> {
+        let mut spans = self.spans();
+
+        for &at in &self.corrected {
+            let right = self
+                .typed
+                .get(at)
+                .is_some_and(|c| spans[at].content.starts_with(*c));
+
+            if right {
+                spans[at].style = self.theme.corrected;
+            }
+        }
+
+        spans
+    }
+
+    /// Where each word sits in the text, and whether any of its letters
+    /// were typed wrong.
+    fn word_mistakes(&self) -> Vec<(Range<usize>, bool)> {
+        let target: Vec<char> = self.words().chars().collect();
+        let mut start = 0;
+
+        self.words
+            .iter()
+            .map(|word| {
+                let word = start..start + word.chars().count();
+                let wrong = word
+                    .clone()
+                    .any(|i| self.typed.get(i).is_some_and(|c| *c != target[i]));
+
+                start = word.end + 1;
+                (word, wrong)
+            })
+            .collect()
+    }
+
+    fn wrong_words(&self) -> Vec<String> {
+        self.words
+            .iter()
+            .zip(self.word_mistakes())
+            .filter(|(_, (_, wrong))| *wrong)
+            .map(|(word, _)| word.clone())
+            .collect()
+    }
+
+    /// Starts a test of only the words typed wrong, coming back round to
+    /// them until they are all typed cleanly.
+    fn drill_mistakes(&mut self) {
+        let wrong = self.wrong_words();
+
+        if wrong.is_empty() {
+            self.notice = Some("no mistakes to drill");
+            return;
+        }
+
+        let source = std::mem::replace(&mut self.source, Source::Text(wrong.clone()));
+        self.mistake_drill.get_or_insert(source);
+        self.words = wrong;
+        self.retry();
+    }
+
+    /// Ends a round of the mistake drill, going again on whatever is still
+    /// wrong or back to the usual words once nothing is.
+    fn next_mistake_round(&mut self) {
+        if self.wrong_words().is_empty() {
+            self.end_mistake_drill();
+            self.notice = Some("mistakes fixed");
+        } else {
+            self.drill_mistakes();
+            self.notice = Some("again, the words still wrong");
+        }
+    }
+
+    fn end_mistake_drill(&mut self) {
+        if let Some(source) = self.mistake_drill.take() {
+            self.source = source;
+        }
+    }
+
+    /// What the playing screen shows: the text itself with the word being
+    /// typed picked out, or only the typed characters once memory mode hides
+    /// it.
+    fn visible_spans(&self) -> Vec<Span<'_>> {
+        if self.is_hidden() {
+            self.typed
+                .iter()
+                .map(|c| Span::raw(c.to_string()))
+                .collect()
+        } else {
+            let chars: Vec<char> = self.words().chars().collect();
+            let current = wrap::word_at(&chars, self.typed.len());
+            let mut spans = self.spans();
+
+            for span in &mut spans[current] {
+                span.style = span.style.patch(self.theme.current);
+            }
+
+            if let Some(span) = self.rival_at().and_then(|at| spans.get_mut(at)) {
+                span.style = span.style.patch(self.theme.ghost);
+            }
+
+            spans
+        }
+    }
+
+    fn playing_screen(&self, frame: &mut Frame) {
+        let area = if self.args.tape {
+            self.tape(frame)
+        } else {
+            self.paragraph(frame)
+        };
+
+        if let (Some(warning), State::Playing) = (self.warning, self.state) {
+            let banner_area = Rect {
+                y: area.y.saturating_sub(2),
+                height: 1,
+                ..frame.area()
+            };
+
+            Paragraph::new(Span::styled(format!(" {warning} "), self.theme.warning))
+                .centered()
+                .render(banner_area, frame.buffer_mut());
+        }
+
+        if self.args.big_word && !self.is_hidden() {
+            let big_area = Rect {
+                y: area.y.saturating_sub(big::HEIGHT + 2),
+                height: big::HEIGHT,
+                ..frame.area()
+            };
+
+            let lines: Vec<Line> = big::render(self.current_word())
+                .into_iter()
+                .map(Line::raw)
+                .collect();
+
+            Paragraph::new(lines)
+                .style(self.theme.accent)
+                .centered()
+                .render(big_area, frame.buffer_mut());
+        }
+
+        let status = match self.state {
+            State::Preview { .. } if self.args.reduced_motion => "memorize".to_string(),
+            State::Ready { until: Some(_) } if self.is_resting() && self.args.reduced_motion => {
+                "rest".to_string()
+            }
+            State::Ready { until: Some(until) } if self.is_resting() => {
+                let left = until.saturating_duration_since(Instant::now());
+                format!("rest · {}s", left.as_secs() + 1)
+            }
+            State::Ready { until: Some(_) } if self.args.reduced_motion => {
+                "starting shortly".to_string()
+            }
+            State::Preview { until } => {
+                let left = until.saturating_duration_since(Instant::now());
+                format!("memorize · {}s", left.as_secs() + 1)
+            }
+            State::Ready { until: Some(until) } => {
+                let left = until.saturating_duration_since(Instant::now());
+                format!("{} {}s", text(Text::StartingIn), left.as_secs() + 1)
+            }
+            State::Ready { until: None } if self.args.start == Start::AnyKey => {
+                text(Text::AnyKeyToBegin).to_string()
+            }
+            State::Ready { until: None } => text(Text::EnterToStart).to_string(),
+            State::Playing if self.args.live_stats && self.is_hidden() => self.live_speeds(),
+            State::Playing if self.args.live_stats => format!(
+                "{} · {:.0}% acc · {} errors",
+                self.live_speeds(),
+                self.live_accuracy(),
+                self.errors()
+            ),
+            _ => String::new(),
+        };
+
+        let status_area = Rect {
+            y: area.bottom() + 1,
+            height: 1,
+            ..area
+        }
+        .intersection(frame.area());
+
+        if self.is_focused() {
+            return;
+        }
+
+        Paragraph::new(Span::styled(status, self.theme.muted))
+            .style(self.fade())
+            .render(status_area, frame.buffer_mut());
+
+        let sparkline = self.args.sparkline && self.state == State::Playing;
+
+        if sparkline {
+            let sparkline_area = Rect {
+                y: area.bottom() + 2,
+                height: 1,
+                ..area
+            }
+            .intersection(frame.area());
+
+            Paragraph::new(self.sparkline())
+                .style(self.fade())
+                .render(sparkline_area, frame.buffer_mut());
+        }
+
+        if let Some(opponent) = self.opponent() {
+            let opponent_area = Rect {
+                y: area.bottom() + 2 + u16::from(sparkline),
+                height: 1,
+                ..area
+            }
+            .intersection(frame.area());
+
+            Paragraph::new(opponent)
+                .style(self.fade())
+                .render(opponent_area, frame.buffer_mut());
+        }
+    }
+
+    /// The opponent of a duel by name, with how far through the text they
+    /// are and how fast they're going.
+    fn opponent(&self) -> Option<Line<'_>> {
+        const WIDTH: usize = 20;
+
+        let duel = self.duel.as_ref()?;
+        let text: Vec<char> = self.words().chars().collect();
+        let at = duel.opponent_at.min(text.len());
+        let filled = at * WIDTH / text.len().max(1);
+
+        let speed = match duel.opponent_done {
+            Some((run, _)) => format!("done · {:.0} wpm", run.wpm),
+            None if duel.gone => "left".to_string(),
+            None => format!(
+                "{:.0} wpm",
+                self.scoring().live_wpm(&text[..at], self.timer.elapsed())
+            ),
+        };
+
+        Some(Line::from(vec![
+            Span::raw(format!("{} ", duel.opponent)),
+            Span::styled("█".repeat(filled), self.theme.accent),
+            Span::styled("░".repeat(WIDTH - filled), self.theme.muted),
+            Span::styled(
+                format!(" {}% · {speed}", at * 100 / text.len().max(1)),
+                self.theme.muted,
+            ),
+        ]))
+    }
+
+    /// Both ends of a finished duel on the podium, over the results.
+    #[expect(clippy::cast_possible_truncation)]
+    fn podium(&self, frame: &mut Frame, results: Rect) {
+        let (Some(start), Some(duel)) = (self.podium, &self.duel) else {
+            return;
+        };
+        let Some((run, time)) = duel.opponent_done else {
+            return;
+        };
+
+        let you = ("you", self.wpm());
+        let opponent = (duel.opponent.as_str(), run.wpm);
+        // A tie goes to this end, as in the results.
+        let places = if time >= self.timer.duration() {
+            [you, opponent]
+        } else {
+            [opponent, you]
+        };
+        let rise = if self.args.reduced_motion {
+            1.0
+        } else {
+            start.elapsed().as_secs_f64() / podium::RISE.as_secs_f64()
+        };
+
+        let rows = podium::render(&places, rise);
+        let height = rows.len() as u16;
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or_default() as u16;
+
+        if results.y < height + 1 {
+            return;
+        }
+
+        let area = center(
+            Rect {
+                y: results.y - height - 1,
+                height,
+                ..frame.area()
+            },
+            Constraint::Length(width),
+            Constraint::Length(height),
+        );
+
+        Paragraph::new(rows.into_iter().map(Line::raw).collect::<Vec<_>>())
+            .style(self.theme.accent)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// How long typing has stopped for in a test taken with `--focus`.
+    fn focus_idle(&self) -> Option<Duration> {
+        if !self.args.focus || self.state != State::Playing {
+            return None;
+        }
+
+        let last = self.times.last()?;
+        Some(self.timer.elapsed().saturating_sub(*last))
+    }
+
+    /// Whether `--focus` has everything but the text hidden while keys are
+    /// coming in.
+    fn is_focused(&self) -> bool {
+        self.focus_idle().is_some_and(|idle| idle < FOCUS_PAUSE)
+    }
+
+    /// Dims what `--focus` hid for a moment as it comes back after a pause.
+    fn fade(&self) -> Style {
+        let fading = !self.args.reduced_motion
+            && self
+                .focus_idle()
+                .is_some_and(|idle| idle < FOCUS_PAUSE + FOCUS_FADE);
+
+        if fading {
+            Style::new().add_modifier(Modifier::DIM)
+        } else {
+            Style::new()
+        }
+    }
+
+    /// Just the text, wrapped to the whole terminal from its top left and
+    /// scrolled to keep the caret's line on screen, for `--minimal`.
+    #[expect(clippy::cast_possible_truncation)]
+    fn minimal_screen(&self, frame: &mut Frame) {
+        let area = frame.area();
+        let spans = self.visible_spans();
+        let chars: Vec<char> = spans.iter().flat_map(|span| span.content.chars()).collect();
+        let lines = wrap::lines(&chars, usize::from(area.width), 0);
+
+        let (caret_line, column) = wrap::position(&chars, &lines, self.typed.len(), 0);
+        let first = caret_line.saturating_sub(usize::from(area.height) - 1);
+
+        let text: Vec<Line> = lines[first..]
+            .iter()
+            .take(usize::from(area.height))
+            .map(|range| spans[range.clone()].iter().cloned().collect())
+            .collect();
+
+        Paragraph::new(text).render(area, frame.buffer_mut());
+
+        if self.state == State::Playing && !self.help {
+            self.place_caret(
+                frame,
+                Position::new(area.x + column as u16, area.y + (caret_line - first) as u16),
+            );
+        }
+    }
+
+    /// The word the caret is in, or about to start.
+    fn current_word(&self) -> &str {
+        let text = self.words();
+        let index = text
+            .chars()
+            .take(self.typed.len())
+            .filter(|c| *c == ' ')
+            .count();
+
+        self.words.get(index).map_or("", String::as_str)
+    }
+
+    /// Bars of the speed over the last 15 seconds, drawn under the text with
+    /// `--sparkline`.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn sparkline(&self) -> Line<'_> {
+        const SAMPLES: usize = 30;
+
+        let pace = stats::pace(&self.times, self.timer.elapsed(), SAMPLES);
+        let most = pace.iter().copied().fold(0.0, f64::max);
+
+        if most == 0.0 {
+            return Line::default();
+        }
+
+        let bars: String = pace
+            .iter()
+            .map(|wpm| BARS[(wpm / most * (BARS.len() - 1) as f64).round() as usize])
+            .collect();
+
+        Line::from(vec![
+            Span::styled(bars, self.theme.chart),
+            Span::styled(format!(" {most:.0} wpm"), self.theme.axis),
+        ])
+    }
+
+    /// Renders the text as a wrapped paragraph, returning the area it takes
+    /// up.
+    #[expect(clippy::cast_possible_truncation)]
+    fn paragraph(&self, frame: &mut Frame) -> Rect {
+        let spacing = self.args.letter_spacing;
+        let cell = |c| wrap::width(c) + spacing;
+        let width: usize = self.words().chars().map(cell).sum();
+        let width = self
+            .args
+            .max_width
+            .map_or(width, |max| width.min(usize::from(max)));
+
+        let area = center(
+            frame.area(),
+            Constraint::Length(u16::try_from(width).unwrap_or(u16::MAX)),
+            Constraint::Percentage(100),
+        );
+
+        let spans = self.visible_spans();
+        let chars: Vec<char> = spans.iter().flat_map(|span| span.content.chars()).collect();
+        let lines = wrap::lines(&chars, usize::from(area.width), spacing);
+
+        let rows = usize::from(area.height).max(1);
+        let (caret_line, column) = wrap::position(&chars, &lines, self.typed.len(), spacing);
+
+        // The caret's line leads with `--lines-ahead`, so it stays on the
+        // same row as the text scrolls up through it. Lines past the end
+        // are left blank rather than moving everything below. Otherwise
+        // start at the middle of the screen, moving up when the text would
+        // run off the bottom and scrolling when even that isn't enough.
+        let (first, shown) = if let Some(ahead) = self.args.lines_ahead {
+            (caret_line, (usize::from(ahead.min(5)) + 1).min(rows))
+        } else {
+            let first = caret_line.saturating_sub(rows - 1);
+            (first, (lines.len() - first).min(rows))
+        };
+
+        let top = area.y + (area.height / 2).min(area.height - shown as u16);
+        let text_area = Rect {
+            y: top,
+            height: shown as u16,
+            ..area
+        };
+
+        // How far each line is pushed in from the left edge.
+        let indent = |range: &Range<usize>| match self.args.align {
+            Align::Left => 0,
+            Align::Center => {
+                let width: usize = chars[range.clone()].iter().map(|c| cell(*c)).sum();
+                usize::from(area.width).saturating_sub(width) / 2
+            }
+        };
+
+        let text: Vec<Line> = lines[first..]
+            .iter()
+            .take(shown)
+            .map(|range| {
+                let indent = Span::raw(" ".repeat(indent(range)));
+                let text = wrap::spaced(spans[range.clone()].iter().cloned(), spacing);
+                std::iter::once(indent).chain(text).collect()
+            })
+            .collect();
+
+        Paragraph::new(text).render(text_area, frame.buffer_mut());
+
+        if self.state == State::Playing && !self.help {
+            self.place_caret(
+                frame,
+                Position::new(
+                    area.x + (indent(&lines[caret_line]) + column) as u16,
+                    top + (caret_line - first) as u16,
+                ),
+            );
+        }
+
+        text_area
+    }
+
+    /// Puts the caret on its way to `target`, gliding there over the next
+    /// few frames unless `--instant-caret` is set.
+    fn place_caret(&self, frame: &mut Frame, target: Position) {
+        let now = Instant::now();
+        let caret = match self.caret.get() {
+            Some(mut caret) if !self.args.instant_caret && !self.args.reduced_motion => {
+                caret.move_to(target, now);
+                caret
+            }
+            _ => Caret::new(target, now),
+        };
+
+        self.caret.set(Some(caret));
+        frame.set_cursor_position(caret.position(now));
+    }
+
+    /// Renders the text as a single line scrolling through a fixed caret in
+    /// the middle of the screen, returning the area it takes up.
+    #[expect(clippy::cast_possible_truncation)]
+    fn tape(&self, frame: &mut Frame) -> Rect {
+        let area = frame.area();
+        let area = Rect {
+            y: area.y + area.height / 2,
+            height: 1,
+            ..area
+        };
+
+        let caret = usize::from(area.width / 2);
+        let at = self.typed.len();
+        let spans = self.visible_spans();
+        let spacing = self.args.letter_spacing;
+        let span_width = |span: &Span| {
+            span.content
+                .chars()
+                .map(|c| wrap::width(c) + spacing)
+                .sum::<usize>()
+        };
+
+        // Walk out from the caret in both directions until the row is full.
+        let mut before = 0;
+        let first = spans[..at.min(spans.len())]
+            .iter()
+            .rposition(|span| {
+                before += span_width(span);
+                before > caret
+            })
+            .map_or(0, |i| i + 1);
+        let before: usize = spans[first..at.min(spans.len())]
+            .iter()
+            .map(span_width)
+            .sum();
+
+        let mut after = caret - before;
+        let window = spans[first..]
+            .iter()
+            .take_while(|span| {
+                after += span_width(span);
+                after <= usize::from(area.width)
+            })
+            .cloned();
+
+        let padding = Span::raw(" ".repeat(caret - before));
+        let line: Line = std::iter::once(padding)
+            .chain(wrap::spaced(window, spacing))
+            .collect();
+        Paragraph::new(line).render(area, frame.buffer_mut());
+
+        if self.state == State::Playing && !self.help {
+            self.place_caret(frame, Position::new(area.x + caret as u16, area.y));
+        }
+
+        area
+    }
+
+    /// The smallest terminal the current screen can be drawn in without
+    /// clipping.
+    #[expect(clippy::cast_possible_truncation)]
+    fn min_size(&self) -> (u16, u16) {
+        match self.state {
+            // Words too long for the pane are broken wherever they reach the
+            // edge.
+            State::Preview { .. } | State::Ready { .. } | State::Playing if self.args.minimal => {
+                (1, 1)
+            }
+            State::Preview { .. } | State::Ready { .. } | State::Playing => {
+                let longest = self.words.iter().map(|word| word.chars().count()).max();
+                let longest = longest.unwrap_or_default() * (1 + self.args.letter_spacing);
+                let width = u16::try_from(longest + 1).unwrap_or(u16::MAX);
+                let height = if self.args.big_word {
+                    // The text sits in the middle, with the big word above.
+                    2 * (big::HEIGHT + 2) + 1
+                } else if self.args.sparkline {
+                    5
+                } else if self.args.live_stats || self.args.memory.is_some() {
+                    3
+                } else {
+                    1
+                };
+
+                (width, height)
+            }
+            State::Finished => (40, 8),
+            State::Summary if self.args.versus => (50, 6 + u16::from(self.args.best_of.is_some())),
+            State::Summary if !self.roster.is_empty() => (50, self.roster.len() as u16 + 4),
+            State::Summary => (40, 9),
+            State::Resume => (40, 5),
+            State::Tutorial { .. } => (TUTORIAL_WIDTH, TUTORIAL_HEIGHT),
+            State::Menu => (Menu::WIDTH, Menu::HEIGHT),
+            State::Settings => (Settings::WIDTH, Settings::HEIGHT),
+            State::History => (Browser::WIDTH, Browser::HEIGHT),
+            State::Achievements => (Unlocked::WIDTH, Unlocked::HEIGHT),
+            State::Leaderboard => (Leaderboard::WIDTH, Leaderboard::HEIGHT),
+            State::Exit => (0, 0),
+        }
+    }
+
+    fn too_small_screen(&self, frame: &mut Frame, (width, height): (u16, u16)) {
+        let area = frame.area();
+        let message = vec![
+            Line::raw("terminal too small"),
+            Line::raw(format!(
+                "need {width}x{height}, have {}x{}",
+                area.width, area.height
+            ))
+            .style(self.theme.muted),
+        ];
+
+        let area = center(area, Constraint::Percentage(100), Constraint::Length(4));
+
+        Paragraph::new(message)
+            .centered()
+            .wrap(Wrap { trim: true })
+            .render(area, frame.buffer_mut());
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        if self.state == State::Playing && self.shown.get().is_none() {
+            self.shown.set(Some(Instant::now()));
+        }
+
+        let (width, height) = self.min_size();
+        let area = frame.area();
+
+        if area.width < width || area.height < height {
+            self.too_small_screen(frame, (width, height));
+            return;
+        }
+
+        match self.state {
+            State::Menu => self.show(frame, &self.menu),
+            State::Settings => self.show(frame, &self.settings),
+            State::Preview { .. } | State::Ready { .. } | State::Playing if self.args.minimal => {
+                self.minimal_screen(frame);
+            }
+            State::Preview { .. } | State::Ready { .. } | State::Playing => {
+                self.playing_screen(frame);
+
+                if !self.is_focused() {
+                    self.status_bar(frame);
+                    self.flash_border(frame);
+                }
+            }
+            State::Finished => {
+                self.finish_screen(frame);
+
+                if let Some(confetti) = &self.confetti {
+                    let styles = [self.theme.accent, self.theme.correct, self.theme.corrected];
+                    let area = frame.area();
+                    confetti.render(frame.buffer_mut(), area, Instant::now(), &styles);
+                }
+            }
+            State::Summary if self.args.versus => self.versus_screen(frame),
+            State::Summary if !self.roster.is_empty() => self.class_screen(frame),
+            State::Summary => self.summary_screen(frame),
+            State::Resume => self.resume_screen(frame),
+            State::Tutorial { page } => self.tutorial_screen(frame, page),
+            State::History => self.show(frame, &self.browser),
+            State::Achievements => self.show(frame, &self.achievements),
+            State::Leaderboard => self.show(frame, &self.leaderboard),
+            State::Exit => unreachable!(),
+        }
+
+        if self.help {
+            self.help_overlay(frame);
+        }
+
+        self.command_line(frame);
+
+        if self.args.glyphs == Glyphs::Ascii {
+            ascii::degrade(frame.buffer_mut());
+        }
+    }
+
+    /// Draws `screen` where it goes in the terminal.
+    fn show(&self, frame: &mut Frame, screen: &impl screen::Screen) {
+        let context = Context {
+            args: &self.args,
+            theme: &self.theme,
+            notice: self.notice,
+        };
+        let area = screen.area(frame.area());
+        screen.render(frame, area, &context);
+    }
+
+    /// The keymap screen for the current state.
+    fn screen(&self) -> Option<Screen> {
+        match self.state {
+            State::Menu => Some(Screen::Menu),
+            State::Settings => Some(Screen::Settings),
+            State::Preview { .. } | State::Ready { .. } => Some(Screen::Preview),
+            State::Playing => Some(Screen::Playing),
+            State::Finished => Some(Screen::Finished),
+            State::Summary => Some(Screen::Summary),
+            State::Resume => Some(Screen::Resume),
+            State::Tutorial { .. } => Some(Screen::Tutorial),
+            State::History => Some(Screen::History),
+            State::Achievements => Some(Screen::Achievements),
+            State::Leaderboard => Some(Screen::Leaderboard),
+            State::Exit => None,
+        }
+    }
+
+    /// Whether `action` does anything in the current session.
+    fn is_available(&self, action: Action) -> bool {
+        match action {
+            // Both ends of a duel race the words the host picks, and only
+            // once the race is over.
+            Action::MoreWords
+            | Action::FewerWords
+            | Action::NextPreset
+            | Action::DrillFinger
+            | Action::DrillMistakes
+            | Action::PracticeWeakWords
+                if self.duel.is_some() =>
+            {
+                false
+            }
+            Action::Retry | Action::NewTest if self.duel.is_some() => self.state == State::Finished,
+            Action::Menu => self.args.menu,
+            Action::DrillFinger => matches!(self.source, Source::Random(_)),
+            Action::NextPreset => !self.config.presets.is_empty(),
+            Action::Note => self.recorded.is_some(),
+            _ => true,
+        }
+    }
+
+    fn handle_key_event(&mut self, key_event: KeyEvent) -> io::Result<()> {
+        let key = Key::from(key_event);
+
+        // Ctrl+C quits whatever has the keys, even the help or command line.
+        if key.ctrl && key.code == KeyCode::Char('c') {
+            self.exit();
+            return Ok(());
+        }
+
+        if self.help {
+            self.help = false;
+            return Ok(());
+        }
+
+        // Any key skips the confetti, without acting on the results.
+        if self.confetti.take().is_some() {
+            return Ok(());
+        }
+
+        if self.command.is_some() {
+            self.edit_command(key_event.code);
+            return Ok(());
+        }
+
+        let Some(screen) = self.screen() else {
+            return Ok(());
+        };
+
+        let action = self
+            .keymap
+            .action(screen, key)
+            .filter(|action| self.is_available(*action));
+
+        match (action, self.state) {
+            (Some(Action::Retry | Action::NewTest), _) if self.duel.is_some() => self.rematch(),
+            (Some(Action::Help), _) => self.help = true,
+            (Some(Action::Quit), _) => self.exit(),
+            (Some(Action::Back), State::History | State::Achievements | State::Leaderboard)
+                if !self.args.menu =>
+            {
+                self.exit();
+            }
+            (Some(action), State::History) if Browser::handles(action) => {
+                self.handle_browser(action);
+            }
+            (Some(Action::Leave), _) => self.leave(),
+            (Some(Action::NewTest), _) => self.reset(),
+            (Some(Action::Retry), _) => self.retry(),
+            (Some(action @ (Action::MoreWords | Action::FewerWords)), _) => {
+                self.args.words = menu::step_words(self.args.words, action == Action::MoreWords);
+                self.start_from_menu();
+            }
+            (Some(Action::Copy), _) => self.copy_card(),
+            (Some(Action::SaveWords), _) => self.save_words(),
+            (Some(Action::ExportReplay), _) => self.export_replay(),
+            (Some(Action::ExportGhost), _) => self.export_ghost(),
+            (Some(Action::Note), _) => self.command = Some("note ".to_string()),
+            (Some(Action::ExportKeystrokes), _) => self.export_keystrokes(),
+            (Some(Action::DrillFinger), _) => self.drill_weakest_finger(),
+            (Some(Action::DrillMistakes), _) => self.drill_mistakes(),
+            (Some(Action::PracticeWeakWords), _) => self.practice_weak_words(),
+            (Some(Action::NextPreset), _) => self.next_preset(),
+            (Some(Action::NextPage), State::Tutorial { page }) if page + 1 < tutorial::PAGES => {
+                self.state = State::Tutorial { page: page + 1 };
+            }
+            (Some(Action::NextPage), _) => self.end_tutorial(true),
+            (Some(Action::PreviousPage), State::Tutorial { page }) => {
+                self.state = State::Tutorial {
+                    page: page.saturating_sub(1),
+                };
+            }
+            (Some(Action::SkipTutorial), _) => self.end_tutorial(false),
+            (Some(Action::Menu | Action::Back), _) => self.state = State::Menu,
+            (Some(Action::SkipPreview), State::Ready { .. }) => self.begin(),
+            (Some(Action::SkipPreview), _) => self.end_preview(),
+            (Some(Action::Backspace), _) => self.backspace(),
+            (Some(Action::Resume), _) => self.resume(),
+            (Some(Action::Discard), _) => self.discard(),
+            (Some(Action::Save), _) => self.save_settings()?,
+            (Some(Action::Command), _) => self.command = Some(String::new()),
+            (Some(action), State::Menu) => self.handle_menu(action),
+            (Some(action), State::Settings) => {
+                self.settings.handle(action, &mut self.args);
+            }
+            // The key that starts the test isn't part of it.
+            (None, State::Ready { until: None }) if self.args.start == Start::AnyKey => {
+                self.begin();
+            }
+            (None, State::Playing) => {
+                if let (KeyCode::Char(c), false) = (key.code, key.ctrl) {
+                    if c == ' ' && self.is_stray_space() {
+                        return Ok(());
+                    }
+
+                    self.check_keys(key_event, c);
+                    self.type_char(c);
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn edit_command(&mut self, key: KeyCode) {
+        let Some(input) = &mut self.command else {
+            return;
+        };
+
+        match key {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace if input.is_empty() => self.command = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.command = None,
+            KeyCode::Enter => {
+                let input = self.command.take().unwrap_or_default();
+                self.run_command(&input);
+            }
+            _ => {}
+        }
+    }
+
+    fn run_command(&mut self, input: &str) {
+        match Command::parse(input) {
+            Some(Command::Quit) => self.exit(),
+            Some(Command::New) if self.state == State::Menu => self.start_from_menu(),
+            Some(Command::New) => self.reset(),
+            Some(Command::Retry) => self.retry(),
+            Some(Command::Menu) if self.is_available(Action::Menu) => self.state = State::Menu,
+            Some(Command::Words(n)) => {
+                self.args.words = n;
+                self.restart_if_testing();
+            }
+            Some(Command::Theme(name)) => {
+                self.args.theme = name;
+                self.theme = theme(&self.args, &self.config);
+            }
+            Some(Command::Tape) => self.args.tape = !self.args.tape,
+            Some(Command::LiveStats) => self.args.live_stats = !self.args.live_stats,
+            Some(Command::Note(note)) => self.write_note(&note),
+            Some(Command::RandomCase) => {
+                self.args.toggle_funbox(Funbox::RandomCase);
+                self.restart_if_testing();
+            }
+            Some(Command::Menu) | None => self.notice = Some("unknown command"),
+        }
+    }
+
+    /// Options that change the words start a new test with them, unless
+    /// they were changed from the menu where the next test starts anyway.
+    fn restart_if_testing(&mut self) {
+        if !matches!(self.state, State::Menu | State::Settings) {
+            self.start_from_menu();
+        }
+    }
+
+    /// Warns about Caps Lock or a held modifier before they turn the rest of
+    /// the test into mistakes. Terminals rarely report Caps Lock itself, so
+    /// a few letters in a row typed in the wrong case count as it being on.
+    fn check_keys(&mut self, key_event: KeyEvent, c: char) {
+        const SWAPPED_CASE: usize = 3;
+
+        let want = self.words().chars().nth(self.typed.len());
+        let swapped = want.is_some_and(|want| {
+            want != c && want.is_alphabetic() && want.to_lowercase().eq(c.to_lowercase())
+        });
+
+        if swapped {
+            self.swapped_case += 1;
+        } else if c.is_alphabetic() {
+            self.swapped_case = 0;
+        }
+
+        let caps_lock =
+            key_event.state.contains(KeyEventState::CAPS_LOCK) || self.swapped_case >= SWAPPED_CASE;
+        let modifier = key_event
+            .modifiers
+            .intersects(KeyModifiers::ALT | KeyModifiers::SUPER | KeyModifiers::META);
+
+        self.warning = if caps_lock {
+            Some("caps lock looks to be on")
+        } else if modifier {
+            Some("a modifier key is held down")
+        } else {
+            None
+        };
+    }
+
+    /// Whether `--restart-below` should start over, checked as each of the
+    /// first `--restart-within` words is typed.
+    fn fails_early(&self) -> bool {
+        let Some(threshold) = self.args.restart_below else {
+            return false;
+        };
+
+        let words = self.typed.iter().filter(|c| **c == ' ').count();
+
+        !self.warming_up
+            && self.mistake_drill.is_none()
+            && words <= self.args.restart_within
+            && self.live_accuracy() < f64::from(threshold)
+    }
+
+    /// Whether a space typed now would land at the start or after another
+    /// space where the text has none, which `--spaces lenient` ignores.
+    fn is_stray_space(&self) -> bool {
+        let lenient = self.args.spaces == Spaces::Lenient;
+        let expected = self.words().chars().nth(self.typed.len());
+
+        lenient && expected != Some(' ') && self.typed.last().is_none_or(|c| *c == ' ')
+    }
+
+    fn type_char(&mut self, c: char) {
+        if !self.timer.is_started() {
+            self.timer.start_at(self.received);
+
+            if let Some(breaks) = &mut self.breaks {
+                breaks.start(self.received);
+            }
+        }
+
+        if self.args.flash_errors
+            && !self.args.reduced_motion
+            && self.words().chars().nth(self.typed.len()) != Some(c)
+        {
+            self.flash = Some(self.received);
+        }
+
+        if self.keystrokes == 0 {
+            self.reaction = self
+                .shown
+                .get()
+                .map(|shown| self.received.saturating_duration_since(shown));
+        }
+
+        self.typed.push(c);
+        self.times.push(self.timer.elapsed_at(self.received));
+        self.keystrokes += 1;
+
+        if c == ' ' && self.fails_early() {
+            self.reset();
+            self.notice = Some("accuracy fell too low, started over");
+            return;
+        }
+
+        self.top_up_words();
+
+        if self.is_finished() {
+            self.finish();
+        }
+    }
+
+    /// Adds another test's worth of words as a sprint or endurance run nears
+    /// the end of the ones it has, so timed tests never run out while only
+    /// making the words that get typed.
+    fn top_up_words(&mut self) {
+        /// Words still to type when more are added.
+        const AHEAD: usize = 10;
+
+        let endless = matches!(
+            self.source,
+            Source::Random(_) | Source::Numbers | Source::Symbols
+        );
+
+        let timed = self.args.intervals.is_some() || self.args.endurance.is_some();
+
+        if !timed || !endless || self.warming_up {
+            return;
+        }
+
+        let typed = self.typed.iter().filter(|c| **c == ' ').count();
+
+        if typed + AHEAD >= self.words.len() {
+            let more = self.source.generate(self.args.words);
+            self.words.extend(more);
+        }
+    }
+
+    fn finish(&mut self) {
+        if self.warming_up && self.playlist.is_some() {
+            self.next_in_playlist();
+            return;
+        }
+
+        if self.warming_up {
+            self.reset();
+            return;
+        }
+
+        // Drill rounds are practice, kept out of results and sessions.
+        if self.mistake_drill.is_some() {
+            self.state = State::Finished;
+            self.timer.end_at(self.received);
+            self.next_mistake_round();
+            return;
+        }
+
+        self.state = State::Finished;
+        self.timer.end_at(self.received);
+        self.alert(Alert::Finish, || {
+            format!(
+                "test done · {:.0} wpm · {:.0}% acc",
+                self.wpm(),
+                self.accuracy()
+            )
+        });
+
+        let run = Run {
+            wpm: self.wpm(),
+            accuracy: self.accuracy(),
+        };
+
+        if let Some(duel) = &mut self.duel {
+            duel.progress(self.typed.len());
+            duel.send(&Message::Done(run, self.timer.duration()));
+
+            if duel.opponent_done.is_some() {
+                self.podium = Some(Instant::now());
+            }
+        }
+
+        if self.args.skip_invalid && !self.flags().is_empty() {
+            self.skip_flagged();
+            return;
+        }
+
+        self.last_summary = Some(self.summary_line());
+        self.run_hook();
+        self.post_webhook();
+        self.overlay_results();
+        self.climb_ladder();
+        self.certify();
+        self.review_vocab();
+        self.advance_chunk();
+        self.record_best();
+        self.record_history();
+
+        if std::mem::take(&mut self.calibrating) {
+            self.notice = Some("that's the speed to beat from here on");
+        }
+
+        self.track_breaks();
+
+        if self.args.is_daily() && !self.daily_done {
+            self.daily_done = daily::mark_done().is_ok();
+        }
+
+        self.next_in_session();
+    }
+
+    /// Keeps a test flagged with `--skip-invalid` out of the results. A
+    /// session has the same turn typed again rather than stall on it.
+    fn skip_flagged(&mut self) {
+        let in_session = self.args.versus
+            || !self.roster.is_empty()
+            || self.playlist.is_some()
+            || self.args.intervals.is_some()
+            || self.args.tests.is_some();
+
+        if in_session {
+            self.retry();
+            self.notice = Some("flagged, kept out of the results · type it again");
+        } else {
+            self.notice = Some("flagged, kept out of the results");
+        }
+    }
+
+    /// Counts the test towards the session being run, if any, and moves on
+    /// to its next test.
+    fn next_in_session(&mut self) {
+        if self.args.versus {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+
+            let rounds = self.args.best_of.map_or(1, NonZeroUsize::get);
+
+            if self.session.len() % 2 == 1 {
+                self.retry();
+                self.notice = Some("player 2's turn");
+            } else if session::is_decided(&self.session, rounds) {
+                self.end_session();
+            } else {
+                let round = &self.session[self.session.len() - 2..];
+                let notice = if round[1].wpm > round[0].wpm {
+                    "player 2 takes the round"
+                } else {
+                    "player 1 takes the round"
+                };
+
+                self.reset();
+                self.notice = Some(notice);
+            }
+        } else if !self.roster.is_empty() {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+
+            if self.session.len() < self.roster.len() {
+                self.retry();
+                self.notice = Some("next student's turn");
+            } else {
+                self.end_session();
+            }
+        } else if self.playlist.is_some() {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+            self.next_in_playlist();
+        } else if self.args.intervals.is_some() {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+            self.next_interval();
+        } else if let Some(tests) = self.args.tests {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+
+            if self.session.len() < tests.get() {
+                self.reset();
+            } else {
+                self.end_session();
+            }
+        }
+    }
+
+    /// Adds the test to the time typed since the last break, alerting when
+    /// that makes one due.
+    fn track_breaks(&mut self) {
+        let Some(breaks) = &mut self.breaks else {
+            return;
+        };
+
+        let due = breaks.due().is_some();
+        breaks.typed(self.timer.duration(), self.received);
+
+        if !due && breaks.due().is_some() {
+            self.alert(Alert::Break, || "time for a break".to_string());
+        }
+    }
+
+    /// Counts the test towards the `--certify` goal, saving a certificate
+    /// once enough in a row have reached it.
+    fn certify(&mut self) {
+        let Some(goal) = self.args.certify else {
+            return;
+        };
+
+        let (wpm, accuracy) = (self.wpm(), self.accuracy());
+        if !goal.is_met(wpm, accuracy) {
+            self.certified.clear();
+            return;
+        }
+
+        self.certified.push(Run { wpm, accuracy });
+        if self.certified.len() < goal.tests.get() {
+            return;
+        }
+
+        let text = certificate::render(goal, &self.certified, &Date::today().to_string());
+        let saved = write_file(self.args.certificate.as_deref(), "certificate.txt", &text);
+        self.certified.clear();
+
+        self.notice = Some(match saved {
+            Ok(()) => "goal reached, certificate saved",
+            Err(_) => "couldn't save the certificate",
+        });
+    }
+
+    /// Reschedules every term of a `--vocab` test by whether it was typed
+    /// right.
+    fn review_vocab(&mut self) {
+        if !matches!(self.source, Source::Vocab(_)) {
+            return;
+        }
+
+        let today = Date::epoch_days();
+        let mut schedule = Schedule::load();
+
+        for (term, (_, wrong)) in self.words.iter().zip(self.word_mistakes()) {
+            schedule.review(term, !wrong, today);
+        }
+
+        if schedule.save().is_err() {
+            self.notice = Some("couldn't save the vocabulary schedule");
+        }
+    }
+
+    /// Trades progress with the opponent of a duel, and acts on what they
+    /// sent.
+    fn sync_duel(&mut self) {
+        let Some(duel) = &mut self.duel else {
+            return;
+        };
+
+        if self.state == State::Playing {
+            duel.progress(self.typed.len());
+        }
+
+        let messages = duel.receive();
+        self.dirty |= !messages.is_empty();
+
+        for message in messages {
+            match message {
+                Message::Words(words) if !words.is_empty() => {
+                    self.words = words;
+                    self.retry();
+                }
+                Message::Rematch if self.duel.as_ref().is_some_and(|duel| duel.rematch) => {
+                    self.host_race();
+                }
+                Message::Rematch => self.notice = Some("opponent wants a rematch, r to race"),
+                Message::Done(..) if self.state == State::Finished => {
+                    self.podium = Some(Instant::now());
+                }
+                Message::Bye => self.notice = Some("opponent left"),
+                _ => {}
+            }
+        }
+    }
+
+    /// Asks for another race, which starts once both ends have asked.
+    fn rematch(&mut self) {
+        let Some(duel) = &mut self.duel else {
+            return;
+        };
+
+        if duel.gone {
+            self.notice = Some("opponent left");
+            return;
+        }
+
+        if !duel.rematch {
+            duel.rematch = true;
+            duel.send(&Message::Rematch);
+        }
+
+        if duel.opponent_rematch {
+            self.host_race();
+        } else {
+            self.notice = Some("waiting for the opponent");
+        }
+    }
+
+    /// Starts the next race on the host, on new words sent to the opponent.
+    fn host_race(&mut self) {
+        if !self.duel.as_ref().is_some_and(|duel| duel.host) {
+            return;
+        }
+
+        self.reset();
+
+        if let Some(duel) = &mut self.duel {
+            duel.send(&Message::Words(self.words.clone()));
+        }
+    }
+
+    /// Moves `--chunks` on past the words just typed.
+    fn advance_chunk(&mut self) {
+        let Source::Chunks { file, words } = &self.source else {
+            return;
+        };
+
+        let run = Run {
+            wpm: self.wpm(),
+            accuracy: self.accuracy(),
+        };
+        let mut progress = Progress::load(file);
+
+        if progress.advance(words, &self.words, &run) {
+            self.notice = match progress.save(file) {
+                Err(_) => Some("couldn't save progress through the file"),
+                Ok(()) if progress.words == 0 => Some("finished the file, back to its start"),
+                Ok(()) => None,
+            };
+        }
+    }
+
+    /// Keeps the fastest test of each kind, celebrating whenever one is
+    /// beaten. Only generated words are compared, other texts differ too
+    /// much from one test to the next, and flagged tests never count.
+    fn record_best(&mut self) {
+        if !self.flags().is_empty() {
+            return;
+        }
+
+        let kind = match self.source {
+            Source::Random(_) => "words",
+            Source::Numbers => "numbers",
+            Source::Symbols => "symbols",
+            _ => return,
+        };
+        let kind = format!("{kind} {}", self.words.len());
+
+        let mut bests = Bests::load();
+        let beat = bests.record(&kind, self.wpm());
+
+        if bests.save().is_err() {
+            self.notice = Some("couldn't save the personal best");
+        } else if beat {
+            self.notice = Some("new personal best");
+
+            if !self.args.reduced_motion {
+                self.confetti = Some(Confetti::new(self.received, &mut rand::thread_rng()));
+            }
+        }
+    }
+
+    /// Adds the test to the history, for `typers stats` to report on.
+    fn record_history(&mut self) {
+        let speeds = stats::word_speeds(&self.words, &self.times);
+        let record = Record {
+            mode: self.mode(),
+            wpm: self.wpm(),
+            accuracy: self.accuracy(),
+            consistency: stats::consistency(&speeds).unwrap_or_default(),
+            time: self.timer.duration().as_secs_f64(),
+            text: self.words(),
+            speeds: speeds
+                .iter()
+                .map(|speed| (speed.word.to_string(), speed.wpm))
+                .collect(),
+            missed: self.wrong_words(),
+            tags: self.args.tag.clone(),
+            // Tries after the first are kept, but not as the day's result.
+            daily: (self.args.is_daily() && !self.daily_done).then(|| Date::today().to_string()),
+            session: self.args.tests.map(|_| self.session_id.clone()),
+            flags: self
+                .flags()
+                .into_iter()
+                .map(|flag| flag.label().to_string())
+                .collect(),
+            endurance: self.endurance(),
+            source: self.attribution(),
+            ..Record::new()
+        };
+
+        if History::append(&record).is_err() {
+            self.notice = Some("couldn't save the result to the history");
+            return;
+        }
+
+        self.recorded = Some(record.id.clone());
+
+        if let Ok(history) = History::load_recent() {
+            let share = history.standing(&record).map(|(share, of)| {
+                format!("Better than: {share:.0}% of {} alike", history::tests(of))
+            });
+            let goal = Goal::load().and_then(|goal| goal.summary(&history, record.at));
+            self.standing = share.into_iter().chain(goal).collect();
+            self.unlock_achievements(&history, record.at);
+        }
+    }
+
+    /// Unlocks the achievements the recent `history` and the archive have
+    /// earned with the test finished `at`, telling of the first new one.
+    fn unlock_achievements(&mut self, history: &History, at: u64) {
+        let unlocked = self.achievements.unlock(history, History::archived(), at);
+        let Some(first) = unlocked.first().copied() else {
+            return;
+        };
+
+        self.notice = Some(match self.achievements.save() {
+            Ok(()) => first.toast(),
+            Err(_) => "couldn't save the achievements",
+        });
+    }
+
+    /// Steps the word count for the next test with `--ladder`.
+    fn climb_ladder(&mut self) {
+        let Some(threshold) = self.args.ladder else {
+            return;
+        };
+
+        let words = ladder::next(self.args.words, self.accuracy(), threshold);
+        let saved = ladder::save(words).is_ok();
+
+        self.notice = Some(match words.cmp(&self.args.words) {
+            _ if !saved => "couldn't save the ladder",
+            Ordering::Greater => "up a rung",
+            Ordering::Less => "down a rung",
+            Ordering::Equal => "holding the rung",
+        });
+        self.args.words = words;
+    }
+
+    /// Starts the `--on-finish` command without waiting on it. Its output
+    /// would draw over the screen, so it goes nowhere.
+    fn run_hook(&mut self) {
+        let Some(command) = &self.args.on_finish else {
+            return;
+        };
+
+        let spawned = shell::shell(command)
+            .env("TYPERS_WPM", format!("{:.0}", self.wpm()))
+            .env("TYPERS_ACC", format!("{:.0}", self.accuracy()))
+            .env("TYPERS_TIME_MS", self.time_ms().to_string())
+            .env("TYPERS_WORDS", self.words.len().to_string())
+            .env("TYPERS_ERRORS", self.alignment().errors().to_string())
+            .env("TYPERS_FLAGS", self.flag_labels())
+            .env(
+                "TYPERS_DIFFICULTY",
+                format!("{:.0}", difficulty::rate(&self.words())),
+            )
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        if spawned.is_err() {
+            self.notice = Some("couldn't run the finish hook");
+        }
+    }
+
+    /// Posts the results in the background, as `--json` prints them.
+    fn post_webhook(&mut self) {
+        let Some(url) = &self.args.webhook else {
+            return;
+        };
+
+        let Some(url) = http::Url::parse(url) else {
+            self.notice = Some("the webhook needs an http:// url");
+            return;
+        };
+
+        self.webhooks.retain(|sending| !sending.is_finished());
+        let json = template::json(&self.template_values());
+        self.webhooks.push(webhook::send(url, json));
+    }
+
+    /// Brings the `--overlay` file up to date with the test being typed,
+    /// every `overlay::STEP` at most.
+    fn update_overlay(&mut self) {
+        if self.state != State::Playing || !self.timer.is_started() {
+            self.overlay_at = None;
+            return;
+        }
+
+        let now = Instant::now();
+
+        if self
+            .overlay_at
+            .is_some_and(|at| now.duration_since(at) < overlay::STEP)
+        {
+            return;
+        }
+
+        self.overlay_at = Some(now);
+
+        let wpm = format!("{:.0}", self.live_wpm());
+        let acc = format!("{:.0}", self.live_accuracy());
+        let text = format!("{wpm} wpm · {acc}% acc");
+        let values = vec![
+            ("state", "typing".to_string()),
+            ("wpm", wpm),
+            ("acc", acc),
+            ("time", format!("{:.1}", self.timer.elapsed().as_secs_f64())),
+            ("typed", self.typed.len().to_string()),
+            ("length", self.words().chars().count().to_string()),
+        ];
+
+        self.write_overlay(&values, &text);
+    }
+
+    /// Puts the finished test's results in the `--overlay` file, as the
+    /// line printed on exit shows them.
+    fn overlay_results(&mut self) {
+        if self.args.overlay.is_none() {
+            return;
+        }
+
+        let mut values = vec![("state", "done".to_string())];
+        values.extend(self.template_values());
+
+        let template = self.args.format.as_deref().unwrap_or(template::SUMMARY);
+        let text = template::render(template, &values);
+
+        self.write_overlay(&values, &text);
+    }
+
+    fn write_overlay(&mut self, values: &[(&str, String)], text: &str) {
+        let Some(path) = &self.args.overlay else {
+            return;
+        };
+
+        let contents = overlay::contents(path, values, text);
+
+        if overlay::write(path, &contents).is_err() {
+            self.notice = Some("couldn't write the overlay file");
+        }
+    }
+
+    /// Gives results still being posted to the webhook a moment to get
+    /// there before exiting, without holding up the exit for the retries
+    /// of a server that is down.
+    pub fn wait_for_webhooks(&self) {
+        const WAIT: Duration = Duration::from_secs(3);
+
+        let start = Instant::now();
+
+        while self.webhooks.iter().any(|sending| !sending.is_finished()) && start.elapsed() < WAIT {
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    fn backspace(&mut self) {
+        let Some(c) = self.typed.pop() else {
+            return;
+        };
+
+        self.times.pop();
+        self.keystrokes += 1;
+
+        if self.words().chars().nth(self.typed.len()) != Some(c) {
+            self.corrected.push(self.typed.len());
+        }
+
+        if self.typed.is_empty() {
+            self.restarts += 1;
+        }
+    }
+
+    /// What makes the test's result not count as steady typing.
+    fn flags(&self) -> Vec<Flag> {
+        validity::flags(&self.times, self.restarts)
+    }
+
+    fn flag_labels(&self) -> String {
+        let labels: Vec<_> = self.flags().into_iter().map(Flag::label).collect();
+        labels.join(",")
+    }
+
+    /// Lists the keys bound on the current screen over whatever is drawn,
+    /// along with the modifiers the test runs with.
+    #[expect(clippy::cast_possible_truncation)]
+    fn help_overlay(&self, frame: &mut Frame) {
+        let Some(screen) = self.screen() else {
+            return;
+        };
+
+        let mut lines: Vec<Line> = self
+            .keymap
+            .bindings(screen)
+            .into_iter()
+            .filter(|(_, action)| self.is_available(*action))
+            .map(|(keys, action)| {
+                let keys: Vec<String> = keys.into_iter().map(keymap::key_name).collect();
+                Line::from(vec![
+                    Span::styled(format!("{:<12}", keys.join(" / ")), self.theme.accent),
+                    Span::raw(action.description()),
+                ])
+            })
+            .collect();
+
+        let modifiers = self.modifiers();
+        lines.push(Line::raw(""));
+        lines.push(
+            Line::raw(if modifiers.is_empty() {
+                "no modifiers".to_string()
+            } else {
+                modifiers.join(" · ")
+            })
+            .style(self.theme.muted),
+        );
+
+        let area = center(
+            frame.area(),
+            Constraint::Length(44),
+            Constraint::Length(lines.len() as u16 + 4),
+        );
+
+        let block = Block::bordered()
+            .title(Span::styled("keys", self.theme.accent))
+            .title_bottom(Span::styled("any key to close", self.theme.muted))
+            .border_style(self.theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        Clear.render(area, frame.buffer_mut());
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+
+    /// The options that change how a test looks or plays.
+    fn modifiers(&self) -> Vec<String> {
+        let mut modifiers = Vec::new();
+
+        if let Some(secs) = self.args.memory {
+            modifiers.push(format!("memory {secs}s"));
+        }
+
+        if self.args.tape {
+            modifiers.push("tape".to_string());
+        }
+
+        if let Some(preset) = self.preset() {
+            modifiers.push(preset.to_string());
+        }
+
+        if let Some(threshold) = self.args.ladder {
+            modifiers.push(format!("ladder {threshold}%"));
+        }
+
+        if self.mistake_drill.is_some() {
+            modifiers.push("mistakes drill".to_string());
+        }
+
+        if let Some(finger) = self.drill {
+            modifiers.push(format!("{} drill", finger.name()));
+        }
+
+        if let Some(hand) = self.args.hand {
+            modifiers.push(match hand {
+                Hand::Left => "left hand".to_string(),
+                Hand::Right => "right hand".to_string(),
+            });
+        }
+
+        for funbox in &self.args.funbox {
+            modifiers.push(match funbox {
+                Funbox::RandomCase => "random case".to_string(),
+            });
+        }
+
+        if self.args.live_stats {
+            modifiers.push("live stats".to_string());
+        }
+
+        if let Some(duel) = &self.duel {
+            modifiers.push(if duel.host { "duel · host" } else { "duel" }.to_string());
+        }
+
+        if let Some(tests) = self.args.tests {
+            modifiers.push(format!("test {}/{tests}", self.session.len() + 1));
+        }
+
+        if let Some(intervals) = self.args.intervals {
+            modifiers.push(format!(
+                "sprint {}/{}",
+                self.session.len() + 1,
+                intervals.rounds
+            ));
+        }
+
+        if let Some(playlist) = &self.playlist {
+            let (at, of) = playlist.progress();
+            modifiers.push(format!("{} {at}/{of}", playlist.name));
+        }
+
+        if let Some(student) = self.roster.get(self.session.len()) {
+            modifiers.push(format!(
+                "{student} {}/{}",
+                self.session.len() + 1,
+                self.roster.len()
+            ));
+        }
+
+        if self.args.versus {
+            modifiers.push(format!("player {}/2", self.session.len() % 2 + 1));
+
+            if let Some(rounds) = self.args.best_of {
+                let [first, second] = session::wins(&self.session);
+                modifiers.push(format!(
+                    "round {}/{rounds} · {first}–{second}",
+                    self.session.len() / 2 + 1
+                ));
+            }
+        }
+
+        modifiers
+    }
+
+    /// Where the words come from and how many there are.
+    fn mode(&self) -> String {
+        if self.warming_up {
+            return format!("warm-up · {} words", self.words.len());
+        }
+
+        if self.args.is_daily() {
+            let done = if self.daily_done { " · done" } else { "" };
+            return format!("daily {}{done}", Date::today());
+        }
+
+        if let Some(challenge) = challenge(&self.args) {
+            return format!("challenge {} · {} words", challenge.code, self.words.len());
+        }
+
+        // Sprints keep getting more words, so there's no count to give.
+        if let Some(intervals) = self.args.intervals {
+            return format!(
+                "intervals · {}s on, {}s rest",
+                intervals.sprint, intervals.rest
+            );
+        }
+
+        if let Some(minutes) = self.args.endurance {
+            return format!("endurance · {minutes} min");
+        }
+
+        match &self.source {
+            Source::Random(_) => format!("words {} · english", self.words.len()),
+            Source::Numbers => format!("numbers · {} words", self.words.len()),
+            Source::Symbols => format!("symbols · {} words", self.words.len()),
+            Source::Command(_) => format!("generator · {} words", self.words.len()),
+            Source::Vocab(_) => format!("vocab · {} terms", self.words.len()),
+            Source::Chunks { file, words } => format!(
+                "file · {:.0}% through · {} words",
+                Progress::load(file).percent(words),
+                self.words.len()
+            ),
+            Source::Text(_) => format!(
+                "{} · {} words",
+                if self.args.clipboard {
+                    "clipboard"
+                } else if self.put_aside.is_some() {
+                    "history"
+                } else if self.args.ghost.is_some() {
+                    "ghost"
+                } else if self.duel.is_some() {
+                    "host's words"
+                } else if !self.args.text.is_empty() {
+                    "text"
+                } else {
+                    "file"
+                },
+                self.words.len()
+            ),
+        }
+    }
+
+    /// Where the words came from, kept with the result to trace it back to,
+    /// unless typers made them up.
+    fn attribution(&self) -> Option<String> {
+        if self.put_aside.is_some() {
+            return self.replayed.clone();
+        }
+
+        let path = |path: &Path| {
+            fs::canonicalize(path)
+                .unwrap_or_else(|_| path.to_path_buf())
+                .display()
+                .to_string()
+        };
+
+        match &self.source {
+            _ if self.warming_up => None,
+            Source::Random(_) | Source::Numbers | Source::Symbols => None,
+            Source::Command(command) => Some(format!("generator {command}")),
+            Source::Vocab(_) => self.args.vocab.as_deref().map(path),
+            Source::Chunks { file, .. } => Some(path(file)),
+            Source::Text(_) if self.args.clipboard => Some("the clipboard".to_string()),
+            Source::Text(_) => self
+                .args
+                .ghost
+                .as_deref()
+                .or(self.args.file.as_deref())
+                .map(path)
+                .or_else(|| {
+                    self.duel
+                        .is_some()
+                        .then(|| "the host of a duel".to_string())
+                }),
+        }
+    }
+
+    /// Lights up the border above the status bar for a moment after a
+    /// wrong key.
+    fn flash_border(&self, frame: &mut Frame) {
+        if self.flash.is_none() {
+            return;
+        }
+
+        let area = frame.area();
+        let area = Rect {
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+
+        Block::bordered()
+            .border_style(self.theme.incorrect)
+            .border_type(BorderType::Rounded)
+            .render(area, frame.buffer_mut());
+    }
+
+    fn status_bar(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        if area.height < 3 {
+            return;
+        }
+
+        let area = Rect {
+            y: area.bottom() - 1,
+            height: 1,
+            ..area
+        };
+
+        let status = match self.notice {
+            Some(notice) => notice.to_string(),
+            None => std::iter::once(self.mode())
+                .chain(self.modifiers())
+                .collect::<Vec<_>>()
+                .join(" · "),
+        };
+
+        Paragraph::new(Span::styled(format!(" {status}"), self.theme.muted))
+            .style(self.fade())
+            .render(area, frame.buffer_mut());
+        Paragraph::new(Span::styled("F1 help ", self.theme.muted))
+            .style(self.fade())
+            .right_aligned()
+            .render(area, frame.buffer_mut());
+    }
+
+    /// The open command line along the bottom row, with the caret after it.
+    #[expect(clippy::cast_possible_truncation)]
+    fn command_line(&self, frame: &mut Frame) {
+        let Some(input) = &self.command else {
+            return;
+        };
+
+        let area = frame.area();
+        let area = Rect {
+            y: area.bottom() - 1,
+            height: 1,
+            ..area
+        };
+
+        let line = format!(":{input}");
+        let column = line.chars().map(wrap::width).sum::<usize>() as u16;
+
+        frame.render_widget(Clear, area);
+        Paragraph::new(line).render(area, frame.buffer_mut());
+        frame.set_cursor_position(Position::new(area.x + column.min(area.width - 1), area.y));
+    }
+
+    fn click(&mut self, position: Position) -> io::Result<()> {
+        if self.state != State::Finished {
+            return Ok(());
+        }
+
+        let (width, height) = terminal::size()?;
+        let (_, _, buttons) = self.results_layout(Rect::new(0, 0, width, height));
+
+        let clicked = buttons
+            .into_iter()
+            .find(|(_, area)| area.contains(position));
+
+        match clicked {
+            Some((Button::Retry, _)) => self.retry(),
+            Some((Button::New, _)) => self.reset(),
+            Some((Button::Quit, _)) => self.exit(),
+            None => {}
+        }
+
+        Ok(())
+    }
+
+    fn end_preview(&mut self) {
+        self.state = ready_state(&self.args);
+    }
+
+    /// Starts the timer on a test waiting for `--start`.
+    fn begin(&mut self) {
+        let now = Instant::now();
+        self.timer.start_at(now);
+
+        if let Some(breaks) = &mut self.breaks {
+            breaks.start(now);
+        }
+
+        self.state = State::Playing;
+    }
+
+    /// How long to wait for an event before the next tick, if anything on
+    /// screen is counting down.
+    fn timeout(&self) -> Option<Duration> {
+        /// Time between frames while the caret glides.
+        const FRAME: Duration = Duration::from_millis(16);
+
+        let gliding = self
+            .caret
+            .get()
+            .is_some_and(|caret| caret.is_moving(Instant::now()))
+            .then_some(FRAME);
+
+        let timeout = match self.state {
+            // Without the seconds ticking down there's nothing to redraw
+            // until the time is up.
+            State::Preview { until } | State::Ready { until: Some(until) }
+                if self.args.reduced_motion =>
+            {
+                Some(until.saturating_duration_since(Instant::now()))
+            }
+            State::Preview { until } | State::Ready { until: Some(until) } => Some(
+                until
+                    .saturating_duration_since(Instant::now())
+                    .min(Duration::from_millis(250)),
+            ),
+            State::Playing if self.timer.is_started() => {
+                let max_time = self
+                    .max_time()
+                    .map(|max| max.saturating_sub(self.timer.elapsed()));
+                let sparkline = self.args.sparkline.then_some(stats::PACE_STEP);
+                let overlay = self.args.overlay.as_ref().map(|_| overlay::STEP);
+
+                [max_time, sparkline, overlay].into_iter().flatten().min()
+            }
+            _ => None,
+        };
+
+        // The opponent's progress comes in on its own time.
+        let duel = self.duel.as_ref().map(|_| FRAME * 3);
+        let confetti = self.confetti.as_ref().map(|_| FRAME);
+        let rising = self
+            .podium
+            .filter(|start| !self.args.reduced_motion && start.elapsed() < podium::RISE)
+            .map(|_| FRAME);
+        let flash = self
+            .flash
+            .map(|at| (at + FLASH).saturating_duration_since(Instant::now()));
+
+        // Wakes up when a pause brings back what `--focus` hid, and when it
+        // has faded in.
+        let focus = self.focus_idle().and_then(|idle| {
+            [FOCUS_PAUSE, FOCUS_PAUSE + FOCUS_FADE]
+                .into_iter()
+                .find_map(|at| at.checked_sub(idle).filter(|left| !left.is_zero()))
+        });
+
+        [
+            timeout,
+            gliding,
+            self.ghost_wait(),
+            duel,
+            confetti,
+            rising,
+            flash,
+            focus,
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    fn max_time(&self) -> Option<Duration> {
+        self.args.max_time.map(Duration::from_secs)
+    }
+
+    fn tick(&mut self) {
+        if self
+            .confetti
+            .as_ref()
+            .is_some_and(|confetti| confetti.is_over(Instant::now()))
+        {
+            self.confetti = None;
+        }
+
+        if self.flash.is_some_and(|at| at.elapsed() >= FLASH) {
+            self.flash = None;
+        }
+
+        match self.state {
+            State::Preview { until } if Instant::now() >= until => self.end_preview(),
+            State::Ready { until: Some(until) } if Instant::now() >= until => self.begin(),
+            State::Playing
+                if self
+                    .max_time()
+                    .is_some_and(|max| self.timer.is_started() && self.timer.elapsed() >= max) =>
+            {
+                self.stop();
+            }
+            _ => {}
+        }
+
+        self.update_overlay();
+    }
+
+    /// Ends a test at the time cap. It shows its results but, being
+    /// unfinished, doesn't count towards sessions, the daily challenge or
+    /// the finish hook. Playlists and intervals are the exception, as their
+    /// timed tests are meant to end this way.
+    fn stop(&mut self) {
+        // An endurance run is meant to last until the time is up, with only
+        // what was typed of the words kept ahead counting.
+        if self.args.endurance.is_some() {
+            self.incomplete = true;
+            self.finish();
+            return;
+        }
+
+        self.incomplete = true;
+        self.state = State::Finished;
+        self.timer.end();
+        self.alert(Alert::TimeUp, || {
+            format!("time's up · {:.0} wpm", self.wpm())
+        });
+        self.overlay_results();
+
+        if self.playlist.is_some() {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+            self.next_in_playlist();
+            return;
+        }
+
+        if self.args.intervals.is_some() {
+            self.session.push(Run {
+                wpm: self.wpm(),
+                accuracy: self.accuracy(),
+            });
+            self.next_interval();
+            return;
+        }
+
+        self.notice = Some("incomplete, stopped at the time cap");
+    }
+
+    /// Rests before the next round of `--intervals`, its timer starting by
+    /// itself when the rest is over, or shows the summary after the last.
+    fn next_interval(&mut self) {
+        let Some(intervals) = self.args.intervals else {
+            return;
+        };
+
+        if self.session.len() >= intervals.rounds.get() {
+            self.end_session();
+            return;
+        }
+
+        self.reset();
+        self.state = State::Ready {
+            until: Some(Instant::now() + Duration::from_secs(intervals.rest)),
+        };
+    }
+
+    /// Whether this is the rest between two rounds of `--intervals`.
+    fn is_resting(&self) -> bool {
+        self.args.intervals.is_some()
+            && !self.session.is_empty()
+            && matches!(self.state, State::Ready { until: Some(_) })
+    }
+
+    /// Shows the summary once every test of a session is done.
+    fn end_session(&mut self) {
+        self.state = State::Summary;
+        self.alert(Alert::Session, || {
+            format!("session done · {} tests", self.session.len())
+        });
+    }
+
+    /// Rings the bell and notifies the desktop for `alert`, as picked with
+    /// `--bell` and `--notify`. Neither draws anything, so they can go
+    /// straight out between frames.
+    fn alert(&self, alert: Alert, message: impl FnOnce() -> String) {
+        let bell = self.args.bell.contains(&alert);
+        let notification = self.args.notify.contains(&alert).then(message);
+
+        if bell || notification.is_some() {
+            let mut stdout = io::stdout();
+            let sequence = alert::sequence(bell, notification.as_deref());
+            let _ = stdout
+                .write_all(sequence.as_bytes())
+                .and_then(|()| stdout.flush());
+        }
+    }
+
+    /// Sets up the next test of the playlist, or shows the summary after
+    /// the last one.
+    fn next_in_playlist(&mut self) {
+        let Some(playlist) = &mut self.playlist else {
+            return;
+        };
+
+        if playlist.advance().is_none() {
+            self.end_session();
+            return;
+        }
+
+        playlist.apply(&mut self.args);
+        let source = source(&self.args);
+        let failed = source.is_err();
+
+        if let Ok(source) = source {
+            self.source = source;
+        }
+
+        self.warming_up = self.args.warmup;
+        self.words = if self.warming_up {
+            warmup_words(&self.args)
+        } else {
+            self.source.generate(self.args.words)
+        };
+        self.retry();
+
+        if failed {
+            self.notice = Some("couldn't set up the test, kept the last words");
+        }
+    }
+
+    fn log(&mut self, kind: &str, message: fmt::Arguments) {
+        if let Some(log) = &mut self.log {
+            log.write(kind, message);
+        }
+    }
+
+    /// Handles the next event along with any already queued behind it, so a
+    /// burst of keystrokes isn't held up by drawing a frame after each one.
+    fn handle_events(&mut self) -> io::Result<()> {
+        self.sync_duel();
+
+        if let Some(timeout) = self.timeout() {
+            // Only widgets that change over time set a timeout, so waking up
+            // from one always needs a new frame.
+            if !event::poll(timeout)? {
+                self.tick();
+                self.dirty = true;
+                return Ok(());
+            }
+        }
+
+        loop {
+            self.handle_event(&event::read()?)?;
+
+            if self.state == State::Exit || !event::poll(Duration::ZERO)? {
+                return Ok(());
+            }
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> io::Result<()> {
+        self.received = Instant::now();
+        self.log("event", format_args!("{event:?}"));
+
+        let changed = match *event {
+            Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                self.handle_key_event(key_event)?;
+                true
+            }
+            // Held keys repeat through menus, but not into the typed text
+            // where they would only pile up mistakes.
+            Event::Key(key_event)
+                if key_event.kind == KeyEventKind::Repeat
+                    && (self.args.key_repeat || self.state != State::Playing) =>
+            {
+                self.handle_key_event(key_event)?;
+                true
+            }
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) => {
+                self.click(Position::new(column, row))?;
+                true
+            }
+            // Resizes need no handling of their own: every frame lays itself
+            // out from the current size, so redrawing reflows the text and
+            // moves the caret.
+            Event::Resize(..) => true,
+            // Key releases, mouse movement and the like leave the screen as
+            // it was.
+            _ => false,
+        };
+
+        let state = self.state;
+        self.tick();
+        self.dirty |= changed || self.state != state;
+
+        Ok(())
+    }
+}
+
+/// Size of the tutorial's pages.
+const TUTORIAL_WIDTH: u16 = 76;
+const TUTORIAL_HEIGHT: u16 = 16;
+
+/// Block characters from low to high, for bar charts in a line of text.
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// How many of the last tests of the mode the `--rival` is tuned from.
+const RIVAL_TESTS: usize = 10;
+
+/// How much faster than their average the `--rival` types.
+const RIVAL_LEAD: f64 = 1.05;
+
+/// How long the border stays lit after a wrong key with `--flash-errors`.
+const FLASH: Duration = Duration::from_millis(120);
+
+/// How long typing stops for before `--focus` brings back what it hid.
+const FOCUS_PAUSE: Duration = Duration::from_secs(1);
+
+/// How long what `--focus` hid stays dim as it comes back.
+const FOCUS_FADE: Duration = Duration::from_millis(300);
+
+fn cursor_style(args: &Args) -> SetCursorStyle {
+    if args.glyphs == Glyphs::Ascii {
+        return SetCursorStyle::DefaultUserShape;
+    }
+
+    match (args.cursor, args.steady_cursor || args.reduced_motion) {
+        (Cursor::Bar, false) => SetCursorStyle::BlinkingBar,
+        (Cursor::Bar, true) => SetCursorStyle::SteadyBar,
+        (Cursor::Block, false) => SetCursorStyle::BlinkingBlock,
+        (Cursor::Block, true) => SetCursorStyle::SteadyBlock,
+        (Cursor::Underline, false) => SetCursorStyle::BlinkingUnderScore,
+        (Cursor::Underline, true) => SetCursorStyle::SteadyUnderScore,
+    }
+}
+
+/// Without color the high-contrast theme is the only one that still tells
+/// correct and wrong characters apart.
+fn theme(args: &Args, config: &Config) -> Theme {
+    if args.no_color {
+        return Theme::new(ThemeName::HighContrast);
+    }
+
+    if args.theme != ThemeName::Auto {
+        return Theme::new(args.theme);
+    }
+
+    let light = std::env::var("COLORFGBG")
+        .ok()
+        .and_then(|colorfgbg| theme::is_light_background(&colorfgbg));
+
+    Theme::new(match light {
+        Some(true) => config.light_theme,
+        Some(false) | None => config.dark_theme,
+    })
+}
+
+fn start_state(args: &Args) -> State {
+    match args.memory {
+        Some(secs) => State::Preview {
+            until: Instant::now() + Duration::from_secs(secs),
+        },
+        None => ready_state(args),
+    }
+}
+
+/// Where a session opens: the history, achievements or leaderboard for
+/// their commands, offering to resume a test left part way, the tutorial on
+/// the first run, the menu or straight into a test.
+fn opening_state(args: &Args, saved: bool) -> State {
+    if args.command == Some(Subcommand::History { action: None }) {
+        State::History
+    } else if args.command == Some(Subcommand::Achievements) {
+        State::Achievements
+    } else if args.command == Some(Subcommand::Leaderboard) {
+        State::Leaderboard
+    } else if saved {
+        State::Resume
+    } else if args.menu && tutorial::is_first_run() {
+        State::Tutorial { page: 0 }
+    } else if args.menu {
+        State::Menu
+    } else {
+        start_state(args)
+    }
+}
+
+/// How a test starts once the text can be typed.
+fn ready_state(args: &Args) -> State {
+    /// How long `--start countdown` counts down for.
+    const COUNTDOWN: Duration = Duration::from_secs(3);
+
+    match args.start {
+        Start::Keypress => State::Playing,
+        Start::Enter | Start::AnyKey => State::Ready { until: None },
+        Start::Countdown => State::Ready {
+            until: Some(Instant::now() + COUNTDOWN),
+        },
+    }
+}
+
+fn source(args: &Args) -> io::Result<Source> {
+    let source = if args.clipboard {
+        let text = Clipboard::new()
+            .and_then(|mut clipboard| clipboard.get_text())
+            .map_err(io::Error::other)?;
+
+        Source::clipboard(&text)
+    } else if args.is_daily() {
+        Source::Text(daily::words())
+    } else if let Some(challenge) = challenge(args) {
+        Source::Text(challenge.generate())
+    } else if let Some(command) = &args.generator {
+        Source::Command(command.clone())
+    } else if let Some(path) = &args.file {
+        let text = fs::read_to_string(path)?;
+
+        if args.chunks {
+            Source::chunks(path.clone(), &text)
+        } else {
+            Source::text(&text)
+        }
+    } else if !args.text.is_empty() {
+        Source::text(&args.text.join(" "))
+    } else if let Some(path) = &args.vocab {
+        Source::Vocab(vocab::read(path)?)
+    } else {
+        match args.mode {
+            Mode::Words => Source::Random(generator(args)),
+            Mode::Numbers => Source::Numbers,
+            Mode::Symbols => Source::Symbols,
+        }
+    };
+
+    if source.is_empty() {
+        let reason = if args.clipboard {
+            "the clipboard holds no typeable text"
+        } else if args.file.is_some() {
+            "the file holds no typeable text"
+        } else if args.generator.is_some() {
+            "the generator printed no typeable words"
+        } else if !args.text.is_empty() {
+            "the words given hold no typeable text"
+        } else if args.vocab.is_some() {
+            "the vocabulary file holds no terms"
+        } else {
+            "no words match the given filters"
+        };
+
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, reason));
+    }
+
+    Ok(source)
+}
+
+/// ASCII on terminals and locales that look like they can't show more,
+/// going by the environment.
+fn detect_glyphs() -> Glyphs {
+    let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let locale = var("LC_ALL")
+        .or_else(|| var("LC_CTYPE"))
+        .or_else(|| var("LANG"));
+
+    if ascii::detect(var("TERM").as_deref(), locale.as_deref()) {
+        Glyphs::Ascii
+    } else {
+        Glyphs::Unicode
+    }
+}
+
+/// Turns raw mode off before a panic is reported, so neither the message
+/// nor the shell after it is left without line breaks.
+fn leave_raw_mode_on_panic() {
+    let hook = std::panic::take_hook();
+
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = terminal::disable_raw_mode();
+        hook(info);
+    }));
+}
+
+/// Times every test to the sprints of `--intervals`.
+fn set_up_intervals(args: &mut Args) {
+    if let Some(intervals) = args.intervals {
+        args.max_time = Some(intervals.sprint);
+    }
+}
+
+/// Times every test to the minutes of `--endurance`.
+fn set_up_endurance(args: &mut Args) {
+    if let Some(minutes) = args.endurance {
+        args.max_time = Some(minutes * 60);
+    }
+}
+
+/// Opens the menu on the options last started from it, for those not given
+/// on the command line.
+pub fn restore_picks(args: &mut Args, matches: &ArgMatches) {
+    if let Some(picks) = Picks::load().filter(|_| args.menu) {
+        picks.apply(args, matches);
+    }
+}
+
+/// Works out the options that depend on more than what was given.
+fn set_up(args: &mut Args) -> io::Result<()> {
+    if args.ladder.is_some() {
+        args.words = ladder::load().unwrap_or(args.words);
+    }
+
+    if args.glyphs == Glyphs::Auto {
+        args.glyphs = detect_glyphs();
+    }
+
+    strings::use_language(args.ui_lang);
+
+    set_up_challenge(args)?;
+    set_up_intervals(args);
+    set_up_endurance(args);
+    Ok(())
+}
+
+/// Picks a new challenge for `typers challenge` without a code, or checks
+/// the one given, and sets the test up for it.
+fn set_up_challenge(args: &mut Args) -> io::Result<()> {
+    let Some(Subcommand::Challenge { code }) = &mut args.command else {
+        return Ok(());
+    };
+
+    let challenge = if code.is_empty() {
+        Challenge::random(&mut rand::thread_rng())
+    } else {
+        let code = code.join(" ");
+        Challenge::parse(&code).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("{code} isn't a challenge code, three words such as river-stone-apple"),
+            )
+        })?
+    };
+
+    *code = vec![challenge.code.clone()];
+    args.mode = challenge.mode;
+    args.words = challenge.words;
+    args.menu = false;
+
+    Ok(())
+}
+
+/// The challenge taken with `typers challenge`, once it has a code.
+fn challenge(args: &Args) -> Option<Challenge> {
+    match &args.command {
+        Some(Subcommand::Challenge { code }) => Challenge::parse(&code.join(" ")),
+        _ => None,
+    }
+}
+
+/// The history to look through with `typers history`, which isn't read
+/// otherwise until it is opened from the menu.
+fn browser(args: &Args) -> io::Result<Browser> {
+    if args.command == Some(Subcommand::History { action: None }) {
+        Ok(Browser::new(
+            History::load_recent()?,
+            History::archived_months(),
+        ))
+    } else {
+        Ok(Browser::default())
+    }
+}
+
+/// The playlist picked with `typers playlist`, from the config.
+fn playlist(args: &Args, config: &Config) -> io::Result<Option<Playlist>> {
+    let Some(Subcommand::Playlist { name }) = &args.command else {
+        return Ok(None);
+    };
+
+    match config.playlists.get(name) {
+        Some(steps) if !steps.is_empty() => Ok(Some(Playlist::new(name.clone(), steps, args))),
+        _ => Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no playlist named {name} in the config"),
+        )),
+    }
+}
+
+/// Connects the two ends of `typers duel`, returning the words the host
+/// picked on the end that connected.
+fn duel(args: &mut Args) -> io::Result<(Option<Duel>, Option<Vec<String>>)> {
+    let (duel, words) = match &args.command {
+        Some(Subcommand::Duel {
+            listen,
+            connect,
+            name,
+        }) => {
+            let name = name
+                .clone()
+                .or_else(|| std::env::var("USER").ok())
+                .unwrap_or_default();
+
+            match (listen, connect) {
+                (Some(port), _) => {
+                    println!("waiting for an opponent on port {port}");
+                    (Some(Duel::listen(*port, &name)?), None)
+                }
+                (None, Some(address)) => {
+                    let (duel, words) = Duel::connect(address, &name)?;
+                    (Some(duel), Some(words))
+                }
+                (None, None) => (None, None),
+            }
+        }
+        _ => (None, None),
+    };
+
+    // Both ends count down from when the words reach them, so neither gets
+    // a head start.
+    if duel.is_some() {
+        args.start = Start::Countdown;
+        args.menu = false;
+        args.warmup = false;
+    }
+
+    Ok((duel, words))
+}
+
+/// The names in a `--roster` file, one per line, or none without one.
+fn roster(path: Option<&Path>) -> io::Result<Vec<String>> {
+    let Some(path) = path else {
+        return Ok(Vec::new());
+    };
+
+    let names: Vec<String> = fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    if names.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no names in roster {}", path.display()),
+        ));
+    }
+
+    Ok(names)
+}
+
+/// Writes to `path`, or to `name` in the data directory when none was given.
+fn write_file(path: Option<&Path>, name: &str, contents: &str) -> io::Result<()> {
+    if let Some(path) = path {
+        return fs::write(path, contents);
+    }
+
+    let data = Paths::new()?.data;
+    fs::create_dir_all(&data)?;
+    fs::write(data.join(name), contents)
+}
+
+fn warmup_words(args: &Args) -> Vec<String> {
+    const WARMUP_WORDS: usize = 10;
+
+    generator(args).generate(WARMUP_WORDS)
+}
+
+/// Keeps the history however `config` says, from here on.
+pub fn use_history_store(config: &Config) {
+    History::use_store(config.history_store);
+}
+
+/// What the commands that only look at or change the history print, or
+/// none for the rest.
+pub fn history_command(command: &Subcommand) -> Option<io::Result<String>> {
+    Some(match command {
+        Subcommand::Stats { filter } => history_stats(filter),
+        Subcommand::History {
+            action: Some(action),
+        } => manage_history(action),
+        Subcommand::Graph { days, filter } => history_graph(*days, filter),
+        Subcommand::Report { month, filter, .. } => history_report(*month, filter),
+        Subcommand::Compare { a, b } => compare_results(a, b),
+        Subcommand::Goal { action } => goal(action.as_ref()),
+        Subcommand::Import { from } => import_history(from),
+        Subcommand::Sync { remote } => sync_history(remote),
+        _ => return None,
+    })
+}
+
+/// The report for `typers stats`, on the tests in the history picked by
+/// `filter`.
+fn history_stats(filter: &HistoryFilter) -> io::Result<String> {
+    let history = History::load()?;
+    let mut report = history.filter(filter).report();
+
+    if let Some(goal) = Goal::load() {
+        report.insert_str(0, &(goal.progress(&history, history::now()) + "\n"));
+    }
+
+    Ok(report)
+}
+
+fn parse_date(text: &str) -> io::Result<Date> {
+    Date::parse(text).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid date {text}, expected one like 2026-01-31"),
+        )
+    })
+}
+
+/// Sets or clears the goal for `typers goal`, or else tells how it's coming
+/// along.
+fn goal(action: Option<&GoalAction>) -> io::Result<String> {
+    match action {
+        Some(GoalAction::Set { wpm, by }) => {
+            let by = parse_date(by)?;
+            Goal {
+                wpm: *wpm,
+                by: by.to_string(),
+            }
+            .save()?;
+            Ok(format!("goal set to {wpm:.0} wpm by {by}\n"))
+        }
+        Some(GoalAction::Clear) => {
+            Goal::clear()?;
+            Ok("goal cleared\n".to_string())
+        }
+        None => match Goal::load() {
+            Some(goal) => Ok(goal.progress(&History::load()?, history::now())),
+            None => Ok("no goal set yet, see typers goal set --help\n".to_string()),
+        },
+    }
+}
+
+/// Clears, deletes from or prunes the history for `typers history`, saying
+/// what was taken out.
+fn manage_history(action: &HistoryAction) -> io::Result<String> {
+    let mut history = History::load()?;
+
+    let report = match action {
+        HistoryAction::Clear => {
+            let cleared = history::tests(history.tests.len());
+            history.tests.clear();
+            format!("cleared {cleared}")
+        }
+        HistoryAction::Delete { id } => {
+            let id = history.find(Some(id))?.id.clone();
+            history.tests.retain(|test| test.id != id);
+            format!("deleted test {id}")
+        }
+        HistoryAction::Prune { before } => {
+            let date = parse_date(before)?;
+            let count = history.tests.len();
+            history.prune(date);
+            format!(
+                "pruned {} from before {date}",
+                history::tests(count - history.tests.len())
+            )
+        }
+    };
+
+    history.save()?;
+    Ok(report + "\n")
+}
+
+/// Merges the history with the one kept at `remote` for `typers sync`.
+fn sync_history(remote: &str) -> io::Result<String> {
+    let remote = sync::Remote::parse(remote)?;
+    let mut history = History::load()?;
+    let (pulled, pushed) = remote.sync(&mut history)?;
+
+    Ok(format!(
+        "synced, {} pulled and {} pushed\n",
+        history::tests(pulled),
+        history::tests(pushed)
+    ))
+}
+
+/// Adds the tests exported elsewhere to the history for `typers import`,
+/// leaving out any imported before.
+fn import_history(from: &ImportFrom) -> io::Result<String> {
+    let ImportFrom::Monkeytype { path } = from;
+    let records = monkeytype::parse(&fs::read_to_string(path)?)?;
+    let mut history = History::load()?;
+
+    let count = records.len();
+    let imported = history.merge(records);
+    history.save()?;
+
+    Ok(format!(
+        "imported {}, leaving out {} already in the history\n",
+        history::tests(imported),
+        count - imported
+    ))
+}
+
+/// The summary for `typers report` of the last month, or else week, of the
+/// tests in the history picked by `filter`.
+fn history_report(month: bool, filter: &HistoryFilter) -> io::Result<String> {
+    let days = if month { 30 } else { 7 };
+    Ok(report::render(
+        &History::load()?.filter(filter),
+        days,
+        Date::epoch_days(),
+    ))
+}
+
+/// The chart for `typers graph`, of the last `days` days of the tests in the
+/// history picked by `filter`.
+fn history_graph(days: u64, filter: &HistoryFilter) -> io::Result<String> {
+    let since = history::now().saturating_sub(days * 86_400);
+    Ok(graph::render(
+        &History::load_since(since)?.filter(filter),
+        days,
+        Date::epoch_days(),
+    ))
+}
+
+/// The side by side report for `typers compare`, on two tests in the
+/// history.
+fn compare_results(a: &str, b: &str) -> io::Result<String> {
+    let history = History::load()?;
+    Ok(compare::report(
+        history.find(Some(a))?,
+        history.find(Some(b))?,
+    ))
+}
+
+/// Writes the card of a test in the history picked by `filter` for `typers
+/// export-result`, returning where it went.
+pub fn export_result(
+    args: &Args,
+    config: &Config,
+    id: Option<&str>,
+    format: CardFormat,
+    output: Option<&Path>,
+    filter: &HistoryFilter,
+) -> io::Result<PathBuf> {
+    let history = History::load()?.filter(filter);
+    let record = history.find(id)?;
+    let card = export::render(record, &theme(args, config), format);
+
+    let path = if let Some(path) = output {
+        path.to_path_buf()
+    } else {
+        let data = Paths::new()?.data;
+        fs::create_dir_all(&data)?;
+        data.join(format!(
+            "result-{}.{}",
+            record.id,
+            export::extension(format)
+        ))
+    };
+
+    fs::write(&path, card)?;
+    Ok(path)
+}
+
+/// The report for `typers simulate`, on words picked the way a test with
+/// `args` picks them.
+pub fn simulate(
+    args: &Args,
+    wpm: f64,
+    accuracy: f64,
+    runs: NonZeroUsize,
+    seed: Option<u64>,
+) -> io::Result<String> {
+    if wpm <= 0.0 || !(0.0..=100.0).contains(&accuracy) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "--wpm must be above 0 and --accuracy from 0 to 100",
+        ));
+    }
+
+    let mut rng = seed.map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+
+    Ok(simulate::report(
+        &generator(args),
+        scoring(args),
+        args.words,
+        wpm,
+        accuracy,
+        runs.get(),
+        &mut rng,
+    ))
+}
+
+/// How `--grace` and `--wpm-formula` have tests scored.
+fn scoring(args: &Args) -> Scoring {
+    Scoring {
+        grace: args.grace,
+        formula: args.wpm_formula,
+    }
+}
+
+fn generator(args: &Args) -> Words {
+    let mut generator = Words::default();
+
+    if let Some(n) = args.min_word_len {
+        generator = generator.filter(Filter::MinLen(n));
+    }
+
+    if let Some(n) = args.max_word_len {
+        generator = generator.filter(Filter::MaxLen(n));
+    }
+
+    if let Some(regex) = args.word_filter.clone() {
+        generator = generator.filter(Filter::Regex(regex));
+    }
+
+    if let Some(hand) = args.hand {
+        generator = generator.filter(Filter::Hand(hand));
+    }
+
+    match args.charset.clone() {
+        Some(Charset::LettersOnly) => generator = generator.filter(Filter::Alphabetic),
+        Some(Charset::Lowercase) => generator = generator.transform(Transform::Lowercase),
+        Some(Charset::Allowed(set)) => generator = generator.filter(Filter::Charset(set)),
+        None => {}
+    }
+
+    for funbox in &args.funbox {
+        match funbox {
+            Funbox::RandomCase => generator = generator.modifier(Transform::RandomCase),
+        }
+    }
+
+    generator
+}
+
+fn center(area: Rect, horizontal: Constraint, vertical: Constraint) -> Rect {
+    let [area] = Layout::horizontal([horizontal])
+        .flex(Flex::Center)
+        .areas(area);
+
+    let [area] = Layout::vertical([vertical]).flex(Flex::Center).areas(area);
+
+    area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn finishes_multibyte_text() {
+        let args = Args::parse_from(["typers", "café", "日本"]);
+        let mut app = App::new(args, Config::default()).unwrap();
+        let start = Instant::now();
+        app.state = State::Playing;
+        app.timer.start_at(start);
+
+        for (i, c) in "café 日本".chars().enumerate() {
+            assert!(!app.is_finished());
+            app.typed.push(c);
+            app.times.push(Duration::from_secs(i as u64));
+        }
+
+        assert!(app.is_finished());
+        assert_eq!(app.alignment().errors(), 0);
+
+        app.timer.end_at(start + Duration::from_mins(1));
+        assert!((app.wpm() - 1.0).abs() < f64::EPSILON);
+    }
+
+    /// Whatever is typed, with any grace and formula, and whether it's
+    /// finished or stopped short, the library's engine scores a test the
+    /// same as the app.
+    #[test]
+    fn scores_the_same_as_the_engine() {
+        use rand::Rng;
+        use typers::TestEngine;
+
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..200 {
+            let words: Vec<String> = (0..rng.gen_range(1..6))
+                .map(|_| {
+                    (0..rng.gen_range(1..5))
+                        .map(|_| rng.gen_range('a'..='c'))
+                        .collect()
+                })
+                .collect();
+            let grace = ["word", "0", "3"][rng.gen_range(0..3)];
+            let formula = ["chars", "words"][rng.gen_range(0..2)];
+            let args = Args::parse_from(
+                ["typers", "--grace", grace, "--wpm-formula", formula]
+                    .into_iter()
+                    .map(str::to_string)
+                    .chain(words.iter().cloned()),
+            );
+
+            let mut engine = TestEngine::new(&words.join(" ")).scoring(scoring(&args));
+            let mut now = Duration::ZERO;
+            for _ in 0..rng.gen_range(1..30) {
+                now += Duration::from_millis(rng.gen_range(1..300));
+                if rng.gen_ratio(1, 5) {
+                    engine.backspace();
+                } else {
+                    engine.type_char(b"abcd "[rng.gen_range(0..5)].into(), now);
+                }
+            }
+
+            if engine.typed().is_empty() {
+                continue;
+            }
+
+            let mut app = App::new(args, Config::default()).unwrap();
+            let start = Instant::now();
+            app.timer.start_at(start);
+            app.typed = engine.typed().to_vec();
+            app.times = engine.times().to_vec();
+            app.incomplete = !engine.is_finished();
+            app.timer.end_at(start + *engine.times().last().unwrap());
+
+            let result = engine.result().unwrap_or_else(|| engine.stop());
+            assert!((app.wpm() - result.wpm).abs() < 1e-9, "{words:?}");
+            assert!((app.accuracy() - result.accuracy).abs() < 1e-9, "{words:?}");
+        }
+    }
+
+    #[test]
+    fn types_a_flagged_turn_again() {
+        let args = Args::parse_from(["typers", "--versus", "--skip-invalid", "ab", "cd"]);
+        let mut app = App::new(args, Config::default()).unwrap();
+        let start = Instant::now();
+        app.state = State::Playing;
+        app.timer.start_at(start);
+
+        // A pause of five seconds before the last word.
+        for (i, c) in "ab cd".chars().enumerate() {
+            app.typed.push(c);
+            app.times.push(Duration::from_secs(if i < 3 {
+                i as u64
+            } else {
+                5 + i as u64
+            }));
+        }
+        app.received = start + Duration::from_secs(9);
+        app.finish();
+
+        assert!(app.session.is_empty());
+        assert!(app.typed.is_empty());
+        assert_ne!(app.state, State::Finished);
+        assert_eq!(
+            app.notice,
+            Some("flagged, kept out of the results · type it again")
+        );
+    }
+
+    #[test]
+    fn ends_endurance_runs_at_what_was_typed() {
+        // Finishing writes the history, so it goes somewhere of its own.
+        let home = std::env::temp_dir().join("typers-endurance-test");
+        for var in ["HOME", "XDG_CONFIG_HOME", "XDG_DATA_HOME", "XDG_CACHE_HOME"] {
+            std::env::set_var(var, &home);
+        }
+
+        let args = Args::parse_from(["typers", "--endurance", "1", "--words", "20"]);
+        let mut app = App::new(args, Config::default()).unwrap();
+        let start = Instant::now();
+        app.state = State::Playing;
+        app.timer.start_at(start);
+
+        let typed: Vec<char> = app.words().chars().take(50).collect();
+        for (i, c) in typed.iter().enumerate() {
+            app.typed.push(*c);
+            app.times.push(Duration::from_millis(i as u64 * 100));
+        }
+        assert!(app.words().chars().count() > typed.len());
+
+        app.received = start + Duration::from_mins(1);
+        app.stop();
+        let _ = fs::remove_dir_all(&home);
+
+        assert_eq!(app.state, State::Finished);
+        assert!((app.wpm() - 10.0).abs() < f64::EPSILON);
+        assert!((app.accuracy() - 100.0).abs() < f64::EPSILON);
+    }
 }
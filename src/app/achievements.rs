@@ -0,0 +1,235 @@
+use super::{
+    date::Date,
+    history::History,
+    screen::{Context, Screen},
+    strings::{text, Text},
+};
+use crate::paths::Paths;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+    Frame,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::PathBuf,
+};
+
+/// Days in a row with a test for [`Achievement::Streak`].
+const STREAK: usize = 10;
+
+/// Tests finished for [`Achievement::Thousand`].
+const TESTS: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Achievement {
+    Fast,
+    Streak,
+    Flawless,
+    Thousand,
+}
+
+impl Achievement {
+    const ALL: [Self; 4] = [Self::Fast, Self::Streak, Self::Flawless, Self::Thousand];
+
+    /// The name it's kept under.
+    fn key(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::Streak => "streak",
+            Self::Flawless => "flawless",
+            Self::Thousand => "thousand",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fast => "first 100 wpm",
+            Self::Streak => "10-day streak",
+            Self::Flawless => "100% accuracy",
+            Self::Thousand => "1000 tests",
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Fast => "finish a test at 100 wpm or more",
+            Self::Streak => "finish a test on ten UTC days in a row",
+            Self::Flawless => "finish a test without a single mistake",
+            Self::Thousand => "finish a thousand tests",
+        }
+    }
+
+    /// The notice on the finish screen when it's unlocked.
+    pub fn toast(self) -> &'static str {
+        match self {
+            Self::Fast => "achievement unlocked: first 100 wpm",
+            Self::Streak => "achievement unlocked: 10-day streak",
+            Self::Flawless => "achievement unlocked: 100% accuracy",
+            Self::Thousand => "achievement unlocked: 1000 tests",
+        }
+    }
+
+    /// Whether `history` has earned it, with `archived` tests left out of
+    /// it in the archive.
+    fn is_earned(self, history: &History, archived: usize) -> bool {
+        match self {
+            Self::Fast => history.tests.iter().any(|test| test.wpm >= 100.0),
+            Self::Streak => longest_streak(history) >= STREAK,
+            Self::Flawless => history.tests.iter().any(|test| test.accuracy >= 100.0),
+            Self::Thousand => history.tests.len() + archived >= TESTS,
+        }
+    }
+}
+
+/// Most UTC days in a row with a finished test.
+fn longest_streak(history: &History) -> usize {
+    let days: BTreeSet<u64> = history.tests.iter().map(|test| test.at / 86_400).collect();
+    let (mut longest, mut streak, mut last) = (0, 0, None);
+
+    for day in days {
+        streak = if last.is_some_and(|last| last + 1 == day) {
+            streak + 1
+        } else {
+            1
+        };
+        longest = longest.max(streak);
+        last = Some(day);
+    }
+
+    longest
+}
+
+/// When each achievement was unlocked, in seconds since the Unix epoch,
+/// kept next to the history so clearing or pruning it takes none away.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Unlocked(BTreeMap<String, u64>);
+
+impl Unlocked {
+    fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.data.join("achievements.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(fs::read_to_string)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(self).map_err(io::Error::other)?)
+    }
+
+    /// Unlocks the achievements `history`, plus `archived` tests left out of
+    /// it, has earned as of `now`, returning those that weren't before.
+    pub fn unlock(&mut self, history: &History, archived: usize, now: u64) -> Vec<Achievement> {
+        let mut unlocked = Vec::new();
+
+        for achievement in Achievement::ALL {
+            if !self.0.contains_key(achievement.key()) && achievement.is_earned(history, archived) {
+                self.0.insert(achievement.key().to_string(), now);
+                unlocked.push(achievement);
+            }
+        }
+
+        unlocked
+    }
+}
+
+impl Screen for Unlocked {
+    type Choice = ();
+
+    const WIDTH: u16 = 60;
+    #[expect(clippy::cast_possible_truncation)]
+    const HEIGHT: u16 = Achievement::ALL.len() as u16 + 4;
+
+    /// Renders every achievement, with the day it was unlocked or what it
+    /// takes to.
+    fn render(&self, frame: &mut Frame, area: Rect, context: &Context) {
+        let theme = context.theme;
+        let count = Achievement::ALL
+            .iter()
+            .filter(|achievement| self.0.contains_key(achievement.key()))
+            .count();
+
+        let block = Block::bordered()
+            .title(Span::styled(
+                format!("achievements · {count} of {}", Achievement::ALL.len()),
+                theme.accent,
+            ))
+            .title_bottom(Span::styled(text(Text::EscBack), theme.muted))
+            .border_style(theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        let lines: Vec<Line> = Achievement::ALL
+            .iter()
+            .map(|achievement| match self.0.get(achievement.key()) {
+                Some(at) => Line::from(vec![
+                    Span::styled(format!("✓ {:<16}", achievement.label()), theme.correct),
+                    Span::styled(format!("unlocked {}", Date::at(*at)), theme.muted),
+                ]),
+                None => Line::from(vec![
+                    Span::styled(format!("· {:<16}", achievement.label()), theme.muted),
+                    Span::styled(achievement.description(), theme.muted),
+                ]),
+            })
+            .collect();
+
+        Paragraph::new(lines)
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::history::Record;
+
+    #[test]
+    fn unlocks_each_once() {
+        let day = |day: u64, wpm, accuracy| Record {
+            at: day * 86_400,
+            wpm,
+            accuracy,
+            ..Record::default()
+        };
+        let mut history = History {
+            tests: (0..9).map(|i| day(i, 80.0, 98.0)).collect(),
+        };
+        let mut unlocked = Unlocked::default();
+
+        assert!(unlocked.unlock(&history, 0, 0).is_empty());
+
+        history.tests.push(day(9, 104.0, 98.0));
+        history.tests.push(day(11, 70.0, 100.0));
+        let keys: Vec<_> = unlocked
+            .unlock(&history, 0, 1)
+            .iter()
+            .map(|a| a.key())
+            .collect();
+        assert_eq!(keys, ["fast", "streak", "flawless"]);
+
+        assert!(unlocked.unlock(&history, 0, 2).is_empty());
+        assert_eq!(unlocked.0["fast"], 1);
+
+        // Archived tests count towards a thousand.
+        assert!(unlocked.unlock(&history, 988, 3).is_empty());
+        assert_eq!(
+            unlocked.unlock(&history, 989, 3)[0].key(),
+            Achievement::Thousand.key()
+        );
+    }
+}
@@ -0,0 +1,30 @@
+/// What rings the terminal bell.
+const BELL: &str = "\x07";
+
+/// The bytes that ring the bell and pop up `notification` on the desktop,
+/// for whichever of them are asked for. Notifications go through the
+/// terminal with OSC 9, which terminals without it ignore.
+pub fn sequence(bell: bool, notification: Option<&str>) -> String {
+    let bell = if bell { BELL } else { "" };
+    let notification = notification.map_or_else(String::new, |text| {
+        let text: String = text.chars().filter(|c| !c.is_control()).collect();
+        format!("\x1b]9;{text}{BELL}")
+    });
+
+    format!("{bell}{notification}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_sequences() {
+        assert_eq!(sequence(false, None), "");
+        assert_eq!(sequence(true, None), "\x07");
+        assert_eq!(
+            sequence(true, Some("time's up\x07")),
+            "\x07\x1b]9;time's up\x07"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+use super::{
+    date::Date,
+    history::{History, Record},
+    store::{self, Store},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Months before the current one whose tests stay in the history file.
+const RECENT_MONTHS: i64 = 2;
+
+/// Counts of the tests in each segment, by month.
+const INDEX: &str = "archive.toml";
+
+/// The tests of past months, rolled out of the history file into a segment
+/// for each month next to it, like `history-2026-07.toml`, so the file read
+/// after every test stays short however long the history grows. Segments
+/// are read only when something asks for them.
+pub struct Archive {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Index {
+    tests: BTreeMap<String, usize>,
+}
+
+impl Archive {
+    /// The archive of the history kept in `dir`.
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    /// The months with a segment, oldest first.
+    pub fn months(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+
+        let mut months: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let (month, extension) = name.strip_prefix("history-")?.split_once('.')?;
+                let known = store::ALL
+                    .map(store::of)
+                    .iter()
+                    .any(|store| extension_of(*store) == extension);
+                (known && Date::parse(&format!("{month}-01")).is_some()).then(|| month.to_string())
+            })
+            .collect();
+
+        months.sort();
+        months.dedup();
+        months
+    }
+
+    /// How many tests the segments hold, without reading them.
+    pub fn count(&self) -> usize {
+        self.index().tests.values().sum()
+    }
+
+    /// The tests of `month`, written like 2026-07.
+    pub fn load(&self, month: &str) -> io::Result<Vec<Record>> {
+        match self.segment(month) {
+            Some((path, store)) => Ok(History::read(&path, store)?.tests),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The tests of `month` and every one after it, oldest first.
+    pub fn load_from(&self, month: &str) -> io::Result<Vec<Record>> {
+        let mut tests = Vec::new();
+
+        for archived in self
+            .months()
+            .iter()
+            .filter(|archived| archived.as_str() >= month)
+        {
+            tests.extend(self.load(archived)?);
+        }
+
+        Ok(tests)
+    }
+
+    /// Adds `tests` to the segments of the months they finished in.
+    pub fn add(&self, tests: Vec<Record>, store: &dyn Store) -> io::Result<()> {
+        let mut index = self.index();
+
+        for (month, tests) in by_month(tests) {
+            let mut segment = History {
+                tests: self.load(&month)?,
+            };
+            segment.merge(tests);
+            self.write(&month, &segment, store)?;
+            index.tests.insert(month, segment.tests.len());
+        }
+
+        self.save_index(&index)
+    }
+
+    /// Writes `tests` over the whole archive, taking out the segments of the
+    /// months left without any.
+    pub fn replace(&self, tests: Vec<Record>, store: &dyn Store) -> io::Result<()> {
+        let months = by_month(tests);
+        let mut index = Index::default();
+
+        for month in self.months() {
+            if !months.contains_key(&month) {
+                self.remove(&month, None)?;
+            }
+        }
+
+        for (month, tests) in months {
+            index.tests.insert(month.clone(), tests.len());
+            self.write(&month, &History { tests }, store)?;
+        }
+
+        self.save_index(&index)
+    }
+
+    /// The file of `month` in whichever store it's kept in.
+    fn segment(&self, month: &str) -> Option<(PathBuf, &'static dyn Store)> {
+        store::ALL
+            .map(store::of)
+            .into_iter()
+            .map(|store| (self.dir.join(file(month, store)), store))
+            .find(|(path, _)| path.exists())
+    }
+
+    /// Writes the segment of `month` in `store`, taking out any kept in
+    /// another.
+    fn write(&self, month: &str, segment: &History, store: &dyn Store) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.dir.join(file(month, store)),
+            store.write(segment).map_err(io::Error::other)?,
+        )?;
+        self.remove(month, Some(store))
+    }
+
+    /// Takes out the segment of `month`, leaving the one in `keep`.
+    fn remove(&self, month: &str, keep: Option<&dyn Store>) -> io::Result<()> {
+        for store in store::ALL.map(store::of) {
+            if keep.is_some_and(|keep| keep.file() == store.file()) {
+                continue;
+            }
+
+            match fs::remove_file(self.dir.join(file(month, store))) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn index(&self) -> Index {
+        fs::read_to_string(self.dir.join(INDEX))
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_index(&self, index: &Index) -> io::Result<()> {
+        let path = self.dir.join(INDEX);
+
+        if index.tests.is_empty() {
+            return match fs::remove_file(path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(path, toml::to_string(index).map_err(io::Error::other)?)
+    }
+}
+
+/// The month a test finished in at `at` seconds since the Unix epoch,
+/// written like 2026-07.
+pub fn month(at: u64) -> String {
+    let date = Date::at(at);
+    format!("{}-{:02}", date.year, date.month)
+}
+
+/// Splits `tests` into those to archive as of `now` and those to keep in
+/// the history file, finished in this month or the [`RECENT_MONTHS`]
+/// before.
+pub fn split(tests: Vec<Record>, now: u64) -> (Vec<Record>, Vec<Record>) {
+    let date = Date::at(now);
+    let months = date.year * 12 + i64::from(date.month) - 1 - RECENT_MONTHS;
+    let first = Date {
+        year: months.div_euclid(12),
+        month: u32::try_from(months.rem_euclid(12)).unwrap_or_default() + 1,
+        day: 1,
+    };
+    let start = u64::try_from(first.days() * 86_400).unwrap_or_default();
+
+    tests.into_iter().partition(|test| test.at < start)
+}
+
+fn by_month(tests: Vec<Record>) -> BTreeMap<String, Vec<Record>> {
+    let mut months: BTreeMap<String, Vec<Record>> = BTreeMap::new();
+
+    for test in tests {
+        months.entry(month(test.at)).or_default().push(test);
+    }
+
+    months
+}
+
+fn file(month: &str, store: &dyn Store) -> String {
+    format!("history-{month}.{}", extension_of(store))
+}
+
+fn extension_of(store: &dyn Store) -> &'static str {
+    store
+        .file()
+        .rsplit_once('.')
+        .map_or_else(|| store.file(), |(_, extension)| extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolls_old_months_into_segments() {
+        let test = |date: &str, id: &str| Record {
+            id: id.to_string(),
+            at: u64::try_from(Date::parse(date).unwrap().days() * 86_400).unwrap(),
+            ..Record::default()
+        };
+        let now = test("2026-03-14", "").at;
+        let tests = vec![
+            test("2025-11-30", "a"),
+            test("2025-12-31", "b"),
+            test("2026-01-01", "c"),
+            test("2026-03-14", "d"),
+        ];
+
+        let (old, recent) = split(tests, now);
+        let ids = |tests: &[Record]| tests.iter().map(|test| test.id.clone()).collect::<String>();
+        assert_eq!(
+            (ids(&old), ids(&recent)),
+            ("ab".to_string(), "cd".to_string())
+        );
+        assert_eq!(
+            by_month(old).into_keys().collect::<Vec<_>>(),
+            ["2025-11", "2025-12"]
+        );
+
+        let dir = std::env::temp_dir().join("typers-archive-test");
+        let _ = fs::remove_dir_all(&dir);
+        let archive = Archive::new(&dir);
+        let toml = store::of(crate::args::HistoryStore::Toml);
+
+        archive.add(vec![test("2025-11-02", "a")], toml).unwrap();
+        archive
+            .add(vec![test("2025-11-01", "b"), test("2025-12-01", "c")], toml)
+            .unwrap();
+        let (months, count, november) = (
+            archive.months(),
+            archive.count(),
+            archive.load("2025-11").unwrap(),
+        );
+        archive
+            .replace(vec![test("2025-12-01", "c")], toml)
+            .unwrap();
+        let left = (archive.months(), archive.load_from("").unwrap().len());
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(months, ["2025-11", "2025-12"]);
+        assert_eq!((count, ids(&november)), (3, "ba".to_string()));
+        assert_eq!(left, (vec!["2025-12".to_string()], 1));
+    }
+}
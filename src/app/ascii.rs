@@ -0,0 +1,123 @@
+use ratatui::{
+    buffer::Buffer,
+    style::{Color, Modifier},
+};
+
+/// Terminals that can't draw much beyond ASCII or the eight basic colors.
+const BASIC_TERMS: [&str; 5] = ["dumb", "linux", "vt100", "vt102", "vt220"];
+
+/// Whether to fall back to ASCII, going by `TERM` and the first of
+/// `LC_ALL`, `LC_CTYPE` and `LANG` that is set. With no locale set at all
+/// the terminal gets the benefit of the doubt.
+pub fn detect(term: Option<&str>, locale: Option<&str>) -> bool {
+    let basic = term.is_some_and(|term| BASIC_TERMS.contains(&term));
+    let utf8 = locale.is_none_or(|locale| {
+        let locale = locale.to_ascii_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+    });
+
+    basic || !utf8
+}
+
+/// Redraws everything in `buffer` with ASCII and the eight basic colors.
+/// Only the characters the UI draws itself are swapped, anything else is
+/// the text being typed and stays as it is.
+pub fn degrade(buffer: &mut Buffer) {
+    for cell in &mut buffer.content {
+        let mut chars = cell.symbol().chars();
+
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if let Some(ascii) = glyph(c) {
+                cell.set_char(ascii);
+            }
+        }
+
+        let (fg, dim) = basic(cell.fg);
+        cell.fg = fg;
+
+        if dim {
+            cell.modifier.insert(Modifier::DIM);
+        }
+
+        // Without a gray to shade the background with, swapping the colors
+        // still marks the cell out.
+        if matches!(cell.bg, Color::DarkGray | Color::Gray) {
+            cell.bg = Color::Reset;
+            cell.modifier.insert(Modifier::REVERSED);
+        } else {
+            cell.bg = basic(cell.bg).0;
+        }
+    }
+}
+
+/// What each drawn character falls back to, the bars of sparklines and
+/// big words included.
+const GLYPHS: [(&str, char); 12] = [
+    ("─━═–—▄", '-'),
+    ("│┃║", '|'),
+    ("╭╮╰╯┌┐└┘├┤┬┴┼▆", '+'),
+    ("·…▂▃░", '.'),
+    ("›→", '>'),
+    ("‹←", '<'),
+    ("↑", '^'),
+    ("↓", 'v'),
+    ("␣▁", '_'),
+    ("▅", '='),
+    ("▇█", '#'),
+    ("▀", '"'),
+];
+
+fn glyph(c: char) -> Option<char> {
+    GLYPHS
+        .iter()
+        .find(|(from, _)| from.contains(c))
+        .map(|(_, to)| *to)
+}
+
+/// The nearest of the eight basic colors to `color`, and whether it needs
+/// dimming to stand for a dark gray.
+fn basic(color: Color) -> (Color, bool) {
+    match color {
+        Color::DarkGray => (Color::White, true),
+        Color::Gray => (Color::White, false),
+        Color::LightRed => (Color::Red, false),
+        Color::LightGreen => (Color::Green, false),
+        Color::LightYellow => (Color::Yellow, false),
+        Color::LightBlue => (Color::Blue, false),
+        Color::LightMagenta => (Color::Magenta, false),
+        Color::LightCyan => (Color::Cyan, false),
+        Color::Rgb(..) | Color::Indexed(_) => (Color::Reset, false),
+        color => (color, false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{
+        layout::Rect,
+        style::{Style, Stylize},
+    };
+
+    #[test]
+    fn detects_basic_terminals() {
+        assert!(!detect(Some("xterm-256color"), Some("en_US.UTF-8")));
+        assert!(!detect(Some("xterm-256color"), None));
+        assert!(detect(Some("xterm-256color"), Some("C")));
+        assert!(detect(Some("linux"), Some("en_US.utf8")));
+    }
+
+    #[test]
+    fn degrades_glyphs_and_colors() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buffer.set_string(0, 0, "╭·日", Style::new().dark_gray().on_dark_gray());
+        degrade(&mut buffer);
+
+        let cell = &buffer.content[1];
+        assert_eq!(cell.symbol(), ".");
+        assert_eq!((cell.fg, cell.bg), (Color::White, Color::Reset));
+        assert!(cell.modifier.contains(Modifier::DIM | Modifier::REVERSED));
+        assert_eq!(buffer.content[0].symbol(), "+");
+        assert_eq!(buffer.content[2].symbol(), "日");
+    }
+}
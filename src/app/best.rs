@@ -0,0 +1,60 @@
+use crate::paths::Paths;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+/// The fastest finished test of each kind, kept in the data directory.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Bests(BTreeMap<String, f64>);
+
+impl Bests {
+    fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.data.join("best.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(fs::read_to_string)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(self).map_err(io::Error::other)?)
+    }
+
+    /// Records `wpm` for tests of `kind`, returning whether it beat an
+    /// earlier best. The first test of a kind sets the bar without beating
+    /// anything.
+    pub fn record(&mut self, kind: &str, wpm: f64) -> bool {
+        match self.0.get(kind) {
+            Some(best) if *best >= wpm => false,
+            earlier => {
+                let beaten = earlier.is_some();
+                self.0.insert(kind.to_string(), wpm);
+                beaten
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_bests() {
+        let mut bests = Bests::default();
+
+        assert!(!bests.record("words 24", 60.0));
+        assert!(!bests.record("words 24", 55.0));
+        assert!(bests.record("words 24", 65.0));
+        assert!(!bests.record("words 50", 80.0));
+    }
+}
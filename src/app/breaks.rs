@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+/// A gap between tests at least this long counts as a break.
+const PAUSE: Duration = Duration::from_mins(5);
+
+/// Time spent typing since the last break, with how many of the breaks
+/// suggested were taken.
+pub struct Breaks {
+    after: Duration,
+    typing: Duration,
+    last: Option<Instant>,
+    due: bool,
+    suggested: usize,
+    taken: usize,
+}
+
+impl Breaks {
+    pub fn new(after: Duration) -> Self {
+        Self {
+            after,
+            typing: Duration::ZERO,
+            last: None,
+            due: false,
+            suggested: 0,
+            taken: 0,
+        }
+    }
+
+    /// Adds a test that took `duration` and ended at `now`.
+    pub fn typed(&mut self, duration: Duration, now: Instant) {
+        self.typing += duration;
+        self.last = Some(now);
+
+        if !self.due && self.typing >= self.after {
+            self.due = true;
+            self.suggested += 1;
+        }
+    }
+
+    /// Starts a test at `now`, starting the count over if enough time went by
+    /// since the last one to have been a break.
+    pub fn start(&mut self, now: Instant) {
+        let rested = self
+            .last
+            .is_some_and(|last| now.saturating_duration_since(last) >= PAUSE);
+
+        if rested {
+            self.taken += usize::from(self.due);
+            self.typing = Duration::ZERO;
+            self.due = false;
+        }
+    }
+
+    /// The time typed since the last break, once it is time for another.
+    pub fn due(&self) -> Option<Duration> {
+        self.due.then_some(self.typing)
+    }
+
+    /// How many of the breaks suggested so far were taken, if any were.
+    pub fn adherence(&self) -> Option<(usize, usize)> {
+        (self.suggested > 0).then_some((self.taken, self.suggested))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_mins(1);
+
+    #[test]
+    fn suggests_a_break_after_typing_long_enough() {
+        let start = Instant::now();
+        let mut breaks = Breaks::new(10 * MINUTE);
+
+        breaks.typed(6 * MINUTE, start);
+        assert_eq!(breaks.due(), None);
+
+        breaks.start(start + MINUTE);
+        breaks.typed(6 * MINUTE, start + 7 * MINUTE);
+        assert_eq!(breaks.due(), Some(12 * MINUTE));
+        assert_eq!(breaks.adherence(), Some((0, 1)));
+    }
+
+    #[test]
+    fn counts_breaks_taken() {
+        let start = Instant::now();
+        let mut breaks = Breaks::new(MINUTE);
+
+        breaks.typed(MINUTE, start);
+        breaks.start(start + MINUTE);
+        assert!(breaks.due().is_some());
+
+        breaks.typed(MINUTE, start + 2 * MINUTE);
+        breaks.start(start + 2 * MINUTE + PAUSE);
+        assert_eq!(breaks.due(), None);
+        assert_eq!(breaks.adherence(), Some((1, 1)));
+    }
+}
@@ -0,0 +1,429 @@
+use super::{
+    center, compare, export,
+    history::{self, History, Record},
+    keymap::Action,
+    screen::{Context, Screen},
+    theme::Theme,
+    BARS,
+};
+use crate::Args;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+    Frame,
+};
+use std::{cmp::Reverse, io};
+
+/// Lines under the list for the test picked in it.
+const DETAIL: u16 = 6;
+
+/// Widest the mode gets in the list before it's cut short.
+const MODE_WIDTH: usize = 24;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Sort {
+    Date,
+    Mode,
+    Wpm,
+    Accuracy,
+}
+
+impl Sort {
+    const ALL: [Self; 4] = [Self::Date, Self::Mode, Self::Wpm, Self::Accuracy];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Mode => "mode",
+            Self::Wpm => "wpm",
+            Self::Accuracy => "accuracy",
+        }
+    }
+
+    /// Newest, fastest and most accurate first, and modes in order.
+    fn sort(self, tests: &mut [Record]) {
+        match self {
+            Self::Date => tests.sort_by_key(|test| Reverse(test.at)),
+            Self::Mode => tests.sort_by(|a, b| a.mode.cmp(&b.mode).then(b.at.cmp(&a.at))),
+            Self::Wpm => tests.sort_by(|a, b| b.wpm.total_cmp(&a.wpm)),
+            Self::Accuracy => tests.sort_by(|a, b| b.accuracy.total_cmp(&a.accuracy)),
+        }
+    }
+}
+
+pub enum Choice {
+    /// Type the words of the picked test again, with where they came from.
+    Replay(Vec<String>, String),
+    Notice(&'static str),
+}
+
+/// The finished tests in the history, to look through, sort, delete and
+/// type again.
+pub struct Browser {
+    tests: Vec<Record>,
+    sort: Sort,
+    selected: usize,
+    /// Whether delete was pressed once on the picked test, to be pressed
+    /// again to go through with it.
+    deleting: bool,
+    /// The id of the test marked to compare the picked one with.
+    marked: Option<String>,
+    /// The archived months not read yet, oldest first, read one at a time
+    /// as the list runs out.
+    older: Vec<String>,
+}
+
+impl Default for Browser {
+    fn default() -> Self {
+        Self::new(History::default(), Vec::new())
+    }
+}
+
+impl Browser {
+    /// Looks through the recent tests of `history`, with the `older` months
+    /// of the archive read as they're scrolled to.
+    pub fn new(history: History, older: Vec<String>) -> Self {
+        let mut tests = history.tests;
+        Sort::Date.sort(&mut tests);
+
+        Self {
+            tests,
+            sort: Sort::Date,
+            selected: 0,
+            deleting: false,
+            marked: None,
+            older,
+        }
+    }
+
+    /// Whether `action` is for the browser, rather than the rest of the app.
+    pub fn handles(action: Action) -> bool {
+        matches!(
+            action,
+            Action::Up
+                | Action::Down
+                | Action::Sort
+                | Action::Compare
+                | Action::Delete
+                | Action::Retry
+        )
+    }
+
+    /// Sorts by the next column, staying on the same test.
+    fn resort(&mut self) {
+        let at = Sort::ALL.iter().position(|sort| *sort == self.sort);
+        self.sort = Sort::ALL[at.map_or(0, |at| (at + 1) % Sort::ALL.len())];
+        self.sort_keeping_pick();
+    }
+
+    /// Adds the tests of the newest archived month not read yet, skipping
+    /// any without tests.
+    fn read_older(&mut self) -> io::Result<()> {
+        while let Some(month) = self.older.pop() {
+            let tests = History::load_month(&month)?;
+
+            if !tests.is_empty() {
+                self.tests.extend(tests);
+                self.sort_keeping_pick();
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn sort_keeping_pick(&mut self) {
+        let id = self.tests.get(self.selected).map(|test| test.id.clone());
+        self.sort.sort(&mut self.tests);
+        self.selected = self
+            .tests
+            .iter()
+            .position(|test| Some(&test.id) == id.as_ref())
+            .unwrap_or_default();
+    }
+
+    /// Marks the picked test to compare others with, or unmarks it.
+    fn mark(&mut self) -> &'static str {
+        let Some(test) = self.tests.get(self.selected) else {
+            return "no test to compare";
+        };
+
+        if self.marked.as_ref() == Some(&test.id) {
+            self.marked = None;
+            "unmarked"
+        } else {
+            self.marked = Some(test.id.clone());
+            "marked, pick another test to compare"
+        }
+    }
+
+    fn delete(&mut self) -> &'static str {
+        let Some(test) = self.tests.get(self.selected) else {
+            return "no test to delete";
+        };
+
+        let deleted = History::load().and_then(|mut history| {
+            history.tests.retain(|kept| kept.id != test.id);
+            history.save()
+        });
+
+        if deleted.is_err() {
+            return "couldn't delete the test";
+        }
+
+        if self.marked.as_ref() == Some(&test.id) {
+            self.marked = None;
+        }
+
+        self.tests.remove(self.selected);
+        self.selected = self.selected.min(self.tests.len().saturating_sub(1));
+        "deleted"
+    }
+}
+
+impl Screen for Browser {
+    type Choice = Choice;
+
+    const WIDTH: u16 = 64;
+    const HEIGHT: u16 = DETAIL + 10;
+
+    /// Taller than the least it needs where there's room, to list more.
+    fn area(&self, area: Rect) -> Rect {
+        center(
+            area,
+            Constraint::Length(Self::WIDTH),
+            Constraint::Max(2 * Self::HEIGHT),
+        )
+    }
+
+    fn handle(&mut self, action: Action, _args: &mut Args) -> Option<Choice> {
+        let deleting = std::mem::take(&mut self.deleting);
+
+        match action {
+            Action::Up => self.selected = self.selected.saturating_sub(1),
+            Action::Down => {
+                if self.selected + 1 >= self.tests.len() && self.read_older().is_err() {
+                    return Some(Choice::Notice("couldn't read the archived tests"));
+                }
+
+                self.selected = (self.selected + 1).min(self.tests.len().saturating_sub(1));
+            }
+            Action::Sort => self.resort(),
+            Action::Compare => return Some(Choice::Notice(self.mark())),
+            Action::Retry => {
+                let test = self.tests.get(self.selected)?;
+                let words = test.text.split_whitespace().map(str::to_string).collect();
+                let from = test
+                    .source
+                    .clone()
+                    .unwrap_or_else(|| format!("test {}", test.id));
+                return Some(Choice::Replay(words, from));
+            }
+            Action::Delete if deleting => return Some(Choice::Notice(self.delete())),
+            Action::Delete if !self.tests.is_empty() => {
+                self.deleting = true;
+                return Some(Choice::Notice("press d again to delete the test"));
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, context: &Context) {
+        let (theme, notice) = (context.theme, context.notice);
+        let block = Block::bordered()
+            .title(Span::styled(
+                format!(
+                    "history · {}{} · by {}",
+                    history::tests(self.tests.len()),
+                    if self.older.is_empty() {
+                        ""
+                    } else {
+                        ", more on scrolling down"
+                    },
+                    self.sort.label()
+                ),
+                theme.accent,
+            ))
+            .title_bottom(Span::styled(
+                notice.unwrap_or("r replay · s sort · c compare · d delete · esc back"),
+                theme.muted,
+            ))
+            .border_style(theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1));
+
+        let inner = block.inner(area);
+        block.render(area, frame.buffer_mut());
+
+        let [list, _, detail] = Layout::vertical([
+            Constraint::Min(1),
+            Constraint::Length(1),
+            Constraint::Length(DETAIL),
+        ])
+        .areas(inner);
+
+        if self.tests.is_empty() {
+            Paragraph::new(Span::styled("no tests finished yet", theme.muted))
+                .render(list, frame.buffer_mut());
+            return;
+        }
+
+        let rows = usize::from(list.height);
+        let first = self.selected.saturating_sub(rows.saturating_sub(1));
+
+        let lines: Vec<Line> = self
+            .tests
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(rows)
+            .map(|(i, test)| {
+                let mode: String = test.mode.chars().take(MODE_WIDTH).collect();
+                let line = Line::raw(format!(
+                    "{} {}  {mode:<MODE_WIDTH$} {:>4.0} wpm {:>4.0}%",
+                    if i == self.selected { "›" } else { " " },
+                    test.when(),
+                    test.wpm,
+                    test.accuracy,
+                ));
+
+                if i == self.selected {
+                    line.style(theme.selected)
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        Paragraph::new(lines).render(list, frame.buffer_mut());
+
+        let marked = self
+            .marked
+            .as_ref()
+            .and_then(|id| self.tests.iter().find(|test| &test.id == id));
+
+        if let Some(test) = self.tests.get(self.selected) {
+            let width = usize::from(detail.width.saturating_sub(10));
+            let lines = match marked {
+                Some(marked) if marked.id != test.id => comparison(marked, test, theme, width),
+                _ => details(test, theme, width),
+            };
+            Paragraph::new(lines).render(detail, frame.buffer_mut());
+        }
+    }
+}
+
+/// What the list leaves out about `test`, with the speed of its words as a
+/// graph `width` wide at most.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn details<'a>(test: &Record, theme: &Theme, width: usize) -> Vec<Line<'a>> {
+    let speeds: Vec<f64> = test.speeds.iter().map(|(_, wpm)| *wpm).collect();
+    let speeds = export::average_down(&speeds, width);
+    let most = speeds.iter().copied().fold(0.0, f64::max);
+
+    let bars: String = speeds
+        .iter()
+        .map(|wpm| BARS[(wpm / most * (BARS.len() - 1) as f64).round() as usize])
+        .collect();
+
+    let mut about = vec![test.id.clone(), test.mode.clone()];
+    if !test.tags.is_empty() {
+        about.push(format!("tagged {}", test.tags.join(", ")));
+    }
+    if !test.flags.is_empty() {
+        about.push(format!("flagged {}", test.flags.join(", ")));
+    }
+    if let Some(session) = &test.session {
+        about.push(format!("session {session}"));
+    }
+    if let Some(source) = &test.source {
+        about.push(format!("from {source}"));
+    }
+    if let Some(held) = test.endurance {
+        about.push(format!("endurance {held:.0}% of the opening pace"));
+    }
+    if let Some(note) = &test.note {
+        about.push(format!("“{note}”"));
+    }
+
+    vec![
+        Line::styled(about.join(" · "), theme.muted),
+        Line::raw(format!(
+            "{:.0} wpm · {:.0}% acc · {:.0}% consistency · {:.1}s",
+            test.wpm, test.accuracy, test.consistency, test.time
+        )),
+        Line::from(vec![
+            Span::styled(bars, theme.chart),
+            Span::styled(format!(" {most:.0} wpm"), theme.axis),
+        ]),
+        Line::styled(
+            if test.missed.is_empty() {
+                "no words typed wrong".to_string()
+            } else {
+                format!("typed wrong: {}", test.missed.join(" "))
+            },
+            theme.muted,
+        ),
+    ]
+}
+
+/// How `test` did against the `marked` one, with both curves `width` wide
+/// at most.
+fn comparison<'a>(marked: &Record, test: &Record, theme: &Theme, width: usize) -> Vec<Line<'a>> {
+    let (curve_marked, curve_test, most) = compare::curves(marked, test, width);
+
+    let mut lines = vec![Line::styled(
+        format!("marked {} against {}", marked.id, test.id),
+        theme.muted,
+    )];
+    lines.extend(compare::changes(marked, test).into_iter().map(Line::raw));
+    lines.extend([
+        Line::from(vec![
+            Span::styled(curve_marked, theme.muted),
+            Span::styled(format!(" {most:.0} wpm"), theme.axis),
+        ]),
+        Line::styled(curve_test, theme.chart),
+    ]);
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[test]
+    fn sorts_and_keeps_the_pick() {
+        let test = |id: &str, at, wpm| Record {
+            id: id.to_string(),
+            at,
+            wpm,
+            ..Record::default()
+        };
+        let mut browser = Browser::new(
+            History {
+                tests: vec![test("a", 1, 90.0), test("b", 2, 50.0), test("c", 3, 70.0)],
+            },
+            Vec::new(),
+        );
+
+        let ids = |browser: &Browser| -> Vec<String> {
+            browser.tests.iter().map(|test| test.id.clone()).collect()
+        };
+        assert_eq!(ids(&browser), ["c", "b", "a"]);
+
+        let mut args = Args::parse_from(["typers"]);
+        browser.handle(Action::Down, &mut args);
+        browser.handle(Action::Sort, &mut args);
+        browser.handle(Action::Sort, &mut args);
+        assert_eq!(ids(&browser), ["a", "c", "b"]);
+        assert_eq!(browser.tests[browser.selected].id, "b");
+    }
+}
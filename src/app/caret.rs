@@ -0,0 +1,91 @@
+use ratatui::layout::Position;
+use std::time::{Duration, Instant};
+
+/// How long the caret takes to glide to a new cell.
+const GLIDE: Duration = Duration::from_millis(80);
+
+/// A caret gliding between cells, kept between frames at a fractional
+/// position so a move that starts mid-glide carries on from where it is.
+#[derive(Clone, Copy)]
+pub struct Caret {
+    from: (f64, f64),
+    to: Position,
+    since: Instant,
+}
+
+impl Caret {
+    pub fn new(at: Position, now: Instant) -> Self {
+        Self {
+            from: (f64::from(at.x), f64::from(at.y)),
+            to: at,
+            since: now,
+        }
+    }
+
+    pub fn move_to(&mut self, to: Position, now: Instant) {
+        if to != self.to {
+            self.from = self.at(now);
+            self.to = to;
+            self.since = now;
+        }
+    }
+
+    /// Where the caret is at `now`, easing out towards its target.
+    fn at(&self, now: Instant) -> (f64, f64) {
+        let t = (now.saturating_duration_since(self.since).as_secs_f64() / GLIDE.as_secs_f64())
+            .min(1.0);
+        let eased = 1.0 - (1.0 - t).powi(2);
+        let (x, y) = (f64::from(self.to.x), f64::from(self.to.y));
+
+        (
+            self.from.0 + (x - self.from.0) * eased,
+            self.from.1 + (y - self.from.1) * eased,
+        )
+    }
+
+    /// The cell nearest to where the caret is at `now`.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn position(&self, now: Instant) -> Position {
+        let (x, y) = self.at(now);
+        Position::new(x.round() as u16, y.round() as u16)
+    }
+
+    pub fn is_moving(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.since) < GLIDE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glides_to_the_target() {
+        let start = Instant::now();
+        let mut caret = Caret::new(Position::new(0, 0), start);
+        caret.move_to(Position::new(10, 2), start);
+
+        assert_eq!(caret.position(start), Position::new(0, 0));
+        assert!(caret.is_moving(start + GLIDE / 2));
+
+        let halfway = caret.position(start + GLIDE / 2);
+        assert!(halfway.x > 0 && halfway.x < 10);
+
+        assert_eq!(caret.position(start + GLIDE), Position::new(10, 2));
+        assert!(!caret.is_moving(start + GLIDE));
+    }
+
+    #[test]
+    fn turns_mid_glide() {
+        let start = Instant::now();
+        let mut caret = Caret::new(Position::new(0, 0), start);
+        caret.move_to(Position::new(10, 0), start);
+
+        let turn = start + GLIDE / 2;
+        let at = caret.position(turn);
+        caret.move_to(Position::new(0, 0), turn);
+
+        assert_eq!(caret.position(turn), at);
+        assert_eq!(caret.position(turn + GLIDE), Position::new(0, 0));
+    }
+}
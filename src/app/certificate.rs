@@ -0,0 +1,68 @@
+use super::session::Run;
+use crate::args::Goal;
+use std::fmt::Write;
+
+/// A text certificate for `runs` reaching `goal`, ending in a stamp over
+/// everything above it so edits to the numbers show.
+pub fn render(goal: Goal, runs: &[Run], date: &str) -> String {
+    let tests = match runs.len() {
+        1 => "1 test".to_string(),
+        n => format!("{n} tests in a row"),
+    };
+    let mut text = format!(
+        "typers certificate\n\n{tests} at {} wpm and {}% accuracy or better\n\n",
+        goal.wpm, goal.accuracy
+    );
+
+    for (i, run) in runs.iter().enumerate() {
+        let _ = writeln!(
+            text,
+            "  {}. {:.0} wpm · {:.0}% acc",
+            i + 1,
+            run.wpm,
+            run.accuracy
+        );
+    }
+
+    let _ = writeln!(text, "\ndate {date}\n");
+    let stamp = stamp(&text);
+    let _ = writeln!(text, "stamp {stamp:016x}");
+
+    text
+}
+
+/// FNV-1a, which stays the same across builds unlike the std hasher.
+fn stamp(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    #[test]
+    fn stamps_the_text() {
+        assert_eq!(stamp(""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(stamp("a"), 0xaf63_dc4c_8601_ec8c);
+
+        let goal = Goal {
+            wpm: 60.0,
+            accuracy: 95.0,
+            tests: NonZeroUsize::MIN,
+        };
+        let runs = [Run {
+            wpm: 72.4,
+            accuracy: 98.0,
+        }];
+        let text = render(goal, &runs, "2026-10-14");
+
+        assert!(text.contains("1 test at 60 wpm and 95% accuracy"));
+        assert!(text.contains("  1. 72 wpm · 98% acc\n"));
+
+        let (body, stamp_line) = text.rsplit_once("stamp ").unwrap();
+        assert_eq!(stamp_line, format!("{:016x}\n", stamp(body)));
+    }
+}
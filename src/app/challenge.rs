@@ -0,0 +1,100 @@
+use super::{numbers, symbols, words};
+use crate::args::Mode;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const MODES: [Mode; 3] = [Mode::Words, Mode::Numbers, Mode::Symbols];
+const LENGTHS: [usize; 4] = [10, 25, 50, 100];
+
+/// Words that read out clearly, for codes.
+fn vocabulary() -> Vec<&'static str> {
+    words::all()
+        .filter(|word| word.chars().all(|c| c.is_ascii_lowercase()))
+        .collect()
+}
+
+/// A test anyone can take again from a code of three words, such as
+/// `river-stone-apple`. The code numbers the test, which decides its kind,
+/// its length and the seed its words are picked with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub code: String,
+    pub mode: Mode,
+    pub words: usize,
+    seed: u64,
+}
+
+impl Challenge {
+    /// The challenge for `code`, in any case and split by dashes or
+    /// spaces, if all three words are ones codes use.
+    pub fn parse(code: &str) -> Option<Self> {
+        let vocabulary = vocabulary();
+        let indices: Vec<usize> = code
+            .split(|c: char| c == '-' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                vocabulary
+                    .iter()
+                    .position(|known| word.eq_ignore_ascii_case(known))
+            })
+            .collect::<Option<_>>()?;
+
+        let indices: [usize; 3] = indices.try_into().ok()?;
+        Some(Self::new(indices, &vocabulary))
+    }
+
+    /// A new challenge to share.
+    pub fn random(rng: &mut impl Rng) -> Self {
+        let vocabulary = vocabulary();
+        let indices = [(); 3].map(|()| rng.gen_range(0..vocabulary.len()));
+        Self::new(indices, &vocabulary)
+    }
+
+    fn new(indices: [usize; 3], vocabulary: &[&str]) -> Self {
+        let number = indices
+            .iter()
+            .fold(0, |number, i| number * vocabulary.len() + i);
+
+        Self {
+            code: indices.map(|i| vocabulary[i]).join("-"),
+            mode: MODES[number % MODES.len()],
+            words: LENGTHS[number / MODES.len() % LENGTHS.len()],
+            seed: number as u64,
+        }
+    }
+
+    /// The words of the challenge, the same every time.
+    pub fn generate(&self) -> Vec<String> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+
+        match self.mode {
+            Mode::Words => words::Words::default().generate_with(self.words, &mut rng),
+            Mode::Numbers => numbers::generate(self.words, &mut rng),
+            Mode::Symbols => symbols::generate(self.words, &mut rng),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_codes() {
+        let challenge = Challenge::random(&mut StdRng::seed_from_u64(1));
+        let code = challenge.code.to_uppercase().replace('-', " ");
+
+        assert_eq!(Challenge::parse(&code), Some(challenge.clone()));
+        assert_eq!(
+            Challenge::parse(&challenge.code).unwrap().generate(),
+            challenge.generate()
+        );
+        assert_eq!(challenge.generate().len(), challenge.words);
+    }
+
+    #[test]
+    fn rejects_other_codes() {
+        assert_eq!(Challenge::parse("the-the"), None);
+        assert_eq!(Challenge::parse("the-the-the-the"), None);
+        assert_eq!(Challenge::parse("the-the-xyzzy"), None);
+    }
+}
@@ -0,0 +1,139 @@
+use super::session::Run;
+use crate::paths::Paths;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// How far `--chunks` has got through a file, and how the tests typed
+/// through it went.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct Progress {
+    /// Words of the file typed so far, where the next chunk starts.
+    pub words: usize,
+    pub tests: usize,
+    /// Sums over every test, for the averages.
+    pub wpm: f64,
+    pub accuracy: f64,
+}
+
+/// Progress through every file, by its full path, in the data directory.
+type Files = BTreeMap<String, Progress>;
+
+fn path() -> io::Result<PathBuf> {
+    Ok(Paths::new()?.data.join("chunks.toml"))
+}
+
+fn key(file: &Path) -> String {
+    fs::canonicalize(file)
+        .unwrap_or_else(|_| file.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn load_all() -> Files {
+    path()
+        .and_then(fs::read_to_string)
+        .ok()
+        .and_then(|text| toml::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+impl Progress {
+    pub fn load(file: &Path) -> Self {
+        load_all().get(&key(file)).copied().unwrap_or_default()
+    }
+
+    pub fn save(self, file: &Path) -> io::Result<()> {
+        let path = path()?;
+        let mut files = load_all();
+        files.insert(key(file), self);
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(&files).map_err(io::Error::other)?)
+    }
+
+    /// The next `n` words of `text`.
+    pub fn chunk(&self, text: &[String], n: usize) -> Vec<String> {
+        text.iter()
+            .skip(self.words)
+            .take(n.max(1))
+            .cloned()
+            .collect()
+    }
+
+    /// Moves past `typed` when it is the chunk due next, so retrying an
+    /// earlier one doesn't skip ahead, and back to the start once the whole
+    /// text is done. Returns whether it moved.
+    pub fn advance(&mut self, text: &[String], typed: &[String], run: &Run) -> bool {
+        if self.chunk(text, typed.len()) != typed {
+            return false;
+        }
+
+        self.words += typed.len();
+        self.tests += 1;
+        self.wpm += run.wpm;
+        self.accuracy += run.accuracy;
+
+        if self.words >= text.len() {
+            self.words = 0;
+        }
+
+        true
+    }
+
+    /// How much of `text` is typed, in percent.
+    #[expect(clippy::cast_precision_loss)]
+    pub fn percent(&self, text: &[String]) -> f64 {
+        self.words as f64 / text.len().max(1) as f64 * 100.0
+    }
+
+    /// Averages over the tests typed through the text.
+    #[expect(clippy::cast_precision_loss)]
+    pub fn averages(&self) -> Option<(f64, f64)> {
+        let tests = self.tests as f64;
+        (self.tests > 0).then(|| (self.wpm / tests, self.accuracy / tests))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(words: &str) -> Vec<String> {
+        words.split(' ').map(ToString::to_string).collect()
+    }
+
+    fn run(wpm: f64, accuracy: f64) -> Run {
+        Run { wpm, accuracy }
+    }
+
+    #[test]
+    fn types_through_the_text() {
+        let text = words("a b c d e");
+        let mut progress = Progress::default();
+
+        let first = progress.chunk(&text, 2);
+        assert_eq!(first, words("a b"));
+        assert!(progress.advance(&text, &first, &run(60.0, 100.0)));
+        assert_eq!(progress.chunk(&text, 2), words("c d"));
+
+        // Retrying the first chunk doesn't count again.
+        assert!(!progress.advance(&text, &first, &run(60.0, 100.0)));
+        assert!((progress.percent(&text) - 40.0).abs() < 1e-9);
+
+        assert!(progress.advance(&text, &words("c d"), &run(40.0, 90.0)));
+        assert_eq!(progress.chunk(&text, 2), words("e"));
+        assert!(progress.advance(&text, &words("e"), &run(50.0, 95.0)));
+
+        assert_eq!(progress.words, 0);
+        let (wpm, accuracy) = progress.averages().unwrap();
+        assert!((wpm - 50.0).abs() < 1e-9 && (accuracy - 95.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::args::ThemeName;
+use clap::ValueEnum;
+
+/// What can be run from the command line opened with `:` or Ctrl+P.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    New,
+    Retry,
+    Menu,
+    Words(usize),
+    Theme(ThemeName),
+    Tape,
+    LiveStats,
+    RandomCase,
+    /// A note on the last result, taken off when empty.
+    Note(String),
+}
+
+impl Command {
+    pub fn parse(input: &str) -> Option<Self> {
+        // A note is the rest of the line, spaces and all.
+        if let Some(note) = input.trim().strip_prefix("note") {
+            if note.is_empty() || note.starts_with(' ') {
+                return Some(Self::Note(note.trim().to_string()));
+            }
+        }
+
+        let mut parts = input.split_whitespace();
+        let command = parts.next()?;
+        let argument = parts.next();
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        match (command, argument) {
+            ("q" | "quit", None) => Some(Self::Quit),
+            ("new", None) => Some(Self::New),
+            ("retry", None) => Some(Self::Retry),
+            ("menu", None) => Some(Self::Menu),
+            ("words", Some(n)) => n.parse().ok().filter(|n| *n > 0).map(Self::Words),
+            ("theme", Some(name)) => ThemeName::from_str(name, true).ok().map(Self::Theme),
+            ("tape", None) => Some(Self::Tape),
+            ("live-stats", None) => Some(Self::LiveStats),
+            ("random-case", None) => Some(Self::RandomCase),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(Command::parse("q"), Some(Command::Quit));
+        assert_eq!(Command::parse(" retry "), Some(Command::Retry));
+        assert_eq!(Command::parse("nope"), None);
+    }
+
+    #[test]
+    fn parses_arguments() {
+        assert_eq!(Command::parse("words 50"), Some(Command::Words(50)));
+        assert_eq!(
+            Command::parse("theme high-contrast"),
+            Some(Command::Theme(ThemeName::HighContrast))
+        );
+        assert_eq!(Command::parse("words"), None);
+        assert_eq!(Command::parse("words 0"), None);
+        assert_eq!(Command::parse("quit now"), None);
+        assert_eq!(
+            Command::parse("note  new keyboard "),
+            Some(Command::Note("new keyboard".to_string()))
+        );
+        assert_eq!(Command::parse("note"), Some(Command::Note(String::new())));
+        assert_eq!(Command::parse("notes"), None);
+    }
+}
@@ -0,0 +1,128 @@
+use super::{export, history::Record, BARS};
+use std::fmt::Write as _;
+
+/// Most bars in each speed curve.
+const CURVE_BARS: usize = 40;
+
+/// Slowest words listed for each test.
+const SLOWEST: usize = 5;
+
+/// Two tests side by side for `typers compare`: how much faster, more
+/// accurate and steadier `b` was than `a`, their speed curves on one scale
+/// and their slowest words.
+pub fn report(a: &Record, b: &Record) -> String {
+    let mut report = String::new();
+
+    for (name, test) in [("a", a), ("b", b)] {
+        let _ = writeln!(
+            report,
+            "{name} {} · {} · {}",
+            test.id,
+            test.mode,
+            test.when()
+        );
+    }
+
+    report.push('\n');
+    for line in changes(a, b) {
+        let _ = writeln!(report, "{line}");
+    }
+
+    let (curve_a, curve_b, most) = curves(a, b, CURVE_BARS);
+    let _ = writeln!(
+        report,
+        "\na {curve_a}\nb {curve_b}  at most {most:.0} wpm\n"
+    );
+
+    for (name, test) in [("a", a), ("b", b)] {
+        let _ = writeln!(report, "slowest in {name}: {}", slowest(test));
+    }
+
+    report
+}
+
+/// What changed from `a` to `b`, a line for each stat.
+pub fn changes(a: &Record, b: &Record) -> Vec<String> {
+    [
+        ("wpm", a.wpm, b.wpm, ""),
+        ("accuracy", a.accuracy, b.accuracy, "%"),
+        ("consistency", a.consistency, b.consistency, "%"),
+    ]
+    .into_iter()
+    // Rounded first, so the change adds up with what's shown.
+    .map(|(stat, a, b, unit)| (stat, a.round(), b.round(), unit))
+    .map(|(stat, a, b, unit)| format!("{stat:<12} {a:>4} → {b}{unit}  {:+}", b - a))
+    .collect()
+}
+
+/// The speeds of the words of `a` and `b` as bars at most `width` long, on
+/// the same scale, with the top of it.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn curves(a: &Record, b: &Record, width: usize) -> (String, String, f64) {
+    let speeds = |test: &Record| {
+        let speeds: Vec<f64> = test.speeds.iter().map(|(_, wpm)| *wpm).collect();
+        export::average_down(&speeds, width)
+    };
+    let (a, b) = (speeds(a), speeds(b));
+    let most = a.iter().chain(&b).copied().fold(0.0, f64::max);
+
+    let bars = |speeds: &[f64]| -> String {
+        speeds
+            .iter()
+            .map(|wpm| BARS[(wpm / most * (BARS.len() - 1) as f64).round() as usize])
+            .collect()
+    };
+
+    if most > 0.0 {
+        (bars(&a), bars(&b), most)
+    } else {
+        (String::new(), String::new(), most)
+    }
+}
+
+fn slowest(test: &Record) -> String {
+    let mut speeds = test.speeds.clone();
+    speeds.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    if speeds.is_empty() {
+        return "none timed".to_string();
+    }
+
+    speeds
+        .iter()
+        .take(SLOWEST)
+        .map(|(word, wpm)| format!("{word} {wpm:.0}"))
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_on_one_scale() {
+        let test = |wpm, speeds: &[f64]| Record {
+            wpm,
+            accuracy: 95.0,
+            speeds: speeds.iter().map(|wpm| ("w".to_string(), *wpm)).collect(),
+            ..Record::default()
+        };
+        let (a, b) = (test(80.0, &[40.0, 80.0]), test(92.4, &[80.0]));
+
+        let (curve_a, curve_b, most) = curves(&a, &b, 10);
+        assert_eq!(
+            (curve_a.as_str(), curve_b.as_str(), most),
+            ("▅█", "█", 80.0)
+        );
+
+        let changes = changes(&a, &b);
+        assert_eq!(changes[0], "wpm            80 → 92  +12");
+        assert_eq!(changes[1], "accuracy       95 → 95%  +0");
+        assert!(report(&a, &b).contains("slowest in a: w 40 · w 80"));
+    }
+}
@@ -0,0 +1,110 @@
+use rand::Rng;
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use std::time::{Duration, Instant};
+
+/// How long the confetti takes to fall.
+const DURATION: Duration = Duration::from_secs(2);
+
+/// How many pieces fall, spread across whatever is drawn over.
+const COUNT: usize = 48;
+
+const PIECES: [char; 4] = ['*', '+', 'o', '.'];
+
+struct Piece {
+    /// Where it starts across the area, from 0 to 1.
+    x: f64,
+    /// Rows of the area per second.
+    speed: f64,
+    /// Columns per second, either way.
+    drift: f64,
+    delay: Duration,
+    glyph: char,
+    /// Index into the styles it is drawn with.
+    style: usize,
+}
+
+/// Pieces falling over the results after a new personal best.
+pub struct Confetti {
+    start: Instant,
+    pieces: Vec<Piece>,
+}
+
+impl Confetti {
+    pub fn new(start: Instant, rng: &mut impl Rng) -> Self {
+        let pieces = (0..COUNT)
+            .map(|_| Piece {
+                x: rng.gen(),
+                speed: rng.gen_range(0.6..1.2),
+                drift: rng.gen_range(-4.0..4.0),
+                delay: rng.gen_range(Duration::ZERO..DURATION / 3),
+                glyph: PIECES[rng.gen_range(0..PIECES.len())],
+                style: rng.gen(),
+            })
+            .collect();
+
+        Self { start, pieces }
+    }
+
+    pub fn is_over(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= DURATION
+    }
+
+    /// Draws the pieces where they have fallen to by `now`, each in one of
+    /// `styles`.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn render(&self, buffer: &mut Buffer, area: Rect, now: Instant, styles: &[Style]) {
+        // Every piece has fallen the whole height by the end, whatever its
+        // speed, so the slowest still leave in time.
+        let fall = DURATION.as_secs_f64() * 2.0 / 3.0;
+
+        for piece in &self.pieces {
+            let t = now
+                .saturating_duration_since(self.start + piece.delay)
+                .as_secs_f64();
+
+            if t == 0.0 {
+                continue;
+            }
+
+            let y = t / fall * piece.speed * f64::from(area.height);
+            let x = piece.x * f64::from(area.width) + piece.drift * t;
+
+            if y < f64::from(area.height) && (0.0..f64::from(area.width)).contains(&x) {
+                let style = styles.get(piece.style % styles.len().max(1));
+                let cell = &mut buffer[(area.x + x as u16, area.y + y as u16)];
+                cell.set_char(piece.glyph);
+
+                if let Some(style) = style {
+                    cell.set_style(*style);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn falls_within_the_area() {
+        let start = Instant::now();
+        let confetti = Confetti::new(start, &mut StdRng::seed_from_u64(1));
+        let area = Rect::new(0, 0, 40, 10);
+
+        let pieces = |at: Duration| {
+            let mut buffer = Buffer::empty(area);
+            confetti.render(&mut buffer, area, start + at, &[Style::new()]);
+            buffer
+                .content
+                .iter()
+                .filter(|cell| cell.symbol() != " ")
+                .count()
+        };
+
+        assert!(pieces(DURATION / 2) > 0);
+        assert_eq!(pieces(DURATION * 2), 0);
+        assert!(confetti.is_over(start + DURATION));
+    }
+}
@@ -0,0 +1,35 @@
+use super::{date::Date, words::Words};
+use crate::paths::Paths;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{fs, io, path::PathBuf};
+
+const WORDS: usize = 50;
+
+/// Today's words, picked with the UTC date as the seed so everyone gets the
+/// same ones.
+#[expect(clippy::cast_sign_loss)]
+pub fn words() -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(Date::today().number() as u64);
+    Words::default().generate_with(WORDS, &mut rng)
+}
+
+/// Holds the date of the last finished challenge.
+fn path() -> io::Result<PathBuf> {
+    Ok(Paths::new()?.data.join("daily"))
+}
+
+pub fn is_done() -> bool {
+    path()
+        .and_then(fs::read_to_string)
+        .is_ok_and(|date| date.trim() == Date::today().to_string())
+}
+
+pub fn mark_done() -> io::Result<()> {
+    let path = path()?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, Date::today().to_string())
+}
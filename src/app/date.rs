@@ -0,0 +1,111 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A calendar date in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        Self::from_days(Self::epoch_days())
+    }
+
+    /// The date `secs` seconds after the Unix epoch.
+    pub fn at(secs: u64) -> Self {
+        Self::from_days(i64::try_from(secs / 86_400).unwrap_or_default())
+    }
+
+    /// Days since the Unix epoch today, for counting days between dates.
+    pub fn epoch_days() -> i64 {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+
+        i64::try_from(secs / 86_400).unwrap_or_default()
+    }
+
+    /// Days from the Unix epoch to the date, after Howard Hinnant's
+    /// `days_from_civil`.
+    pub fn days(self) -> i64 {
+        let year = self.year - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let yoe = year.rem_euclid(400);
+        let mp = i64::from((self.month + 9) % 12);
+        let doy = (153 * mp + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+
+        era * 146_097 + doe - 719_468
+    }
+
+    /// Reads a date written like 2026-10-14.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '-');
+        let date = Self {
+            year: parts.next()?.parse().ok()?,
+            month: parts.next()?.parse().ok()?,
+            day: parts.next()?.parse().ok()?,
+        };
+
+        // Days past the end of the month come out as another date.
+        (date.month >= 1 && Self::from_days(date.days()) == date).then_some(date)
+    }
+
+    /// The date read as a number, like 20261014.
+    pub fn number(self) -> i64 {
+        self.year * 10_000 + i64::from(self.month) * 100 + i64::from(self.day)
+    }
+
+    /// Converts days since the Unix epoch, after Howard Hinnant's
+    /// `civil_from_days`.
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_days(days: i64) -> Self {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let doe = days.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_days() {
+        assert_eq!(Date::from_days(0).to_string(), "1970-01-01");
+        assert_eq!(Date::from_days(11_016).to_string(), "2000-02-29");
+        assert_eq!(Date::from_days(20_740).to_string(), "2026-10-14");
+    }
+
+    #[test]
+    fn parses_dates() {
+        for days in [0, 11_016, 20_740, -1] {
+            let date = Date::from_days(days);
+            assert_eq!(Date::parse(&date.to_string()), Some(date));
+            assert_eq!(date.days(), days);
+        }
+
+        assert_eq!(Date::parse("2026-02-30"), None);
+        assert_eq!(Date::parse("2026-13-01"), None);
+        assert_eq!(Date::parse("yesterday"), None);
+    }
+}
@@ -0,0 +1,86 @@
+use super::{keyboard, words};
+use std::collections::HashMap;
+
+/// Share of same finger bigrams that counts as fully awkward. English text
+/// has about one in twenty.
+const AWKWARD: f64 = 0.2;
+
+/// Share of symbols, digits and capitals that counts as fully dense.
+const DENSE: f64 = 0.25;
+
+/// How hard `text` is to type, from 0 to 100: the average of how rare its
+/// words are, how many bigrams need the same finger twice, and how dense it
+/// is in keys other than lowercase letters.
+#[expect(clippy::cast_precision_loss)]
+pub fn rate(text: &str) -> f64 {
+    let ranks: HashMap<String, usize> = words::all()
+        .enumerate()
+        .map(|(rank, word)| (word.to_lowercase(), rank))
+        .collect();
+    let known = ranks.len().max(1) as f64;
+
+    // Words off the list are as rare as the last one on it.
+    let rarities: Vec<f64> = text
+        .split_whitespace()
+        .map(|word| {
+            let word = word
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase();
+            ranks.get(&word).map_or(1.0, |rank| *rank as f64 / known)
+        })
+        .collect();
+    let rarity = mean(rarities.iter().sum(), rarities.len());
+
+    let chars: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let bigrams: Vec<(char, char)> = text
+        .split_whitespace()
+        .flat_map(|word| {
+            let chars: Vec<char> = word.chars().collect();
+            chars
+                .windows(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let awkward = bigrams
+        .iter()
+        .filter(|(a, b)| {
+            !a.eq_ignore_ascii_case(b)
+                && keyboard::finger(*a).is_some()
+                && keyboard::finger(*a) == keyboard::finger(*b)
+        })
+        .count();
+    let awkwardness = (mean(awkward as f64, bigrams.len()) / AWKWARD).min(1.0);
+
+    let symbols = chars.iter().filter(|c| !c.is_ascii_lowercase()).count();
+    let density = (mean(symbols as f64, chars.len()) / DENSE).min(1.0);
+
+    (rarity + awkwardness + density) / 3.0 * 100.0
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn mean(sum: f64, n: usize) -> f64 {
+    if n == 0 {
+        0.0
+    } else {
+        sum / n as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_harder_text_higher() {
+        let common = rate("the of to and a in is it");
+        let rare = rate("unusual phrases remain puzzling");
+        let symbols = rate("fn main() { let x = [1, 2]; }");
+
+        assert!(common < 20.0, "{common}");
+        assert!(rare > common, "{rare}");
+        assert!(symbols > rare, "{symbols}");
+        assert!((0.0..=100.0).contains(&symbols));
+        assert!(rate("").abs() < f64::EPSILON);
+    }
+}
@@ -0,0 +1,252 @@
+use super::session::Run;
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::mpsc::{self, Receiver},
+    thread,
+    time::Duration,
+};
+
+/// Longest name an opponent is shown by.
+const NAME_LEN: usize = 16;
+
+/// What the two ends of a duel tell each other, one per line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    /// What the end that sends it is called, sent first by both.
+    Hello(String),
+    /// The words of the next race, always sent by the host.
+    Words(Vec<String>),
+    /// How many characters have been typed so far.
+    At(usize),
+    /// The result of a finished race.
+    Done(Run, Duration),
+    /// Asks for another race.
+    Rematch,
+    Bye,
+}
+
+impl Message {
+    fn encode(&self) -> String {
+        match self {
+            Self::Hello(name) => format!("hello {name}\n"),
+            Self::Words(words) => format!("words {}\n", words.join(" ")),
+            Self::At(at) => format!("at {at}\n"),
+            Self::Done(run, time) => {
+                format!("done {} {} {}\n", run.wpm, run.accuracy, time.as_millis())
+            }
+            Self::Rematch => "rematch\n".to_string(),
+            Self::Bye => "bye\n".to_string(),
+        }
+    }
+
+    fn decode(line: &str) -> Option<Self> {
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+
+        Some(match kind {
+            "hello" => Self::Hello(name(rest)),
+            "words" => Self::Words(rest.split_whitespace().map(String::from).collect()),
+            "at" => Self::At(rest.parse().ok()?),
+            "done" => {
+                let mut fields = rest.split(' ');
+                let mut next = || fields.next()?.parse::<f64>().ok();
+                let (wpm, accuracy, ms) = (next()?, next()?, next()?);
+
+                #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Self::Done(Run { wpm, accuracy }, Duration::from_millis(ms as u64))
+            }
+            "rematch" => Self::Rematch,
+            "bye" => Self::Bye,
+            _ => return None,
+        })
+    }
+}
+
+/// A race against someone else's typers, connected directly over TCP.
+#[expect(clippy::struct_excessive_bools)]
+pub struct Duel {
+    stream: TcpStream,
+    incoming: Receiver<Message>,
+    /// Whether this end picks the words.
+    pub host: bool,
+    /// What the opponent is called, or `opponent` until they say.
+    pub opponent: String,
+    /// Where the opponent is up to in the current race.
+    pub opponent_at: usize,
+    pub opponent_done: Option<(Run, Duration)>,
+    pub opponent_rematch: bool,
+    pub rematch: bool,
+    /// The progress last sent, so it is only sent again once it changes.
+    sent: usize,
+    /// Whether the opponent has left or the connection dropped.
+    pub gone: bool,
+}
+
+impl Duel {
+    /// Waits for an opponent to connect on `port`, going by `name` to them.
+    pub fn listen(port: u16, name: &str) -> io::Result<Self> {
+        let (stream, _) = TcpListener::bind(("0.0.0.0", port))?.accept()?;
+        Self::new(stream, true, name)
+    }
+
+    /// Connects to an opponent listening at `address`, going by `name` to
+    /// them, and waits for the words of the first race.
+    pub fn connect(address: &str, name: &str) -> io::Result<(Self, Vec<String>)> {
+        let mut duel = Self::new(TcpStream::connect(address)?, false, name)?;
+
+        loop {
+            match duel.incoming.recv() {
+                Ok(Message::Hello(name)) => duel.opponent = name,
+                Ok(Message::Words(words)) if !words.is_empty() => return Ok((duel, words)),
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{address} didn't send words to race"),
+                    ))
+                }
+            }
+        }
+    }
+
+    fn new(stream: TcpStream, host: bool, name: &str) -> io::Result<Self> {
+        stream.set_nodelay(true)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        let (sender, incoming) = mpsc::channel();
+
+        // Lines are read on their own thread so the UI never waits on the
+        // network. It ends when the connection does.
+        thread::spawn(move || {
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+
+                if let Some(message) = Message::decode(&line) {
+                    if sender.send(message).is_err() {
+                        break;
+                    }
+                }
+            }
+
+            let _ = sender.send(Message::Bye);
+        });
+
+        let mut duel = Self {
+            stream,
+            incoming,
+            host,
+            opponent: "opponent".to_string(),
+            opponent_at: 0,
+            opponent_done: None,
+            opponent_rematch: false,
+            rematch: false,
+            sent: 0,
+            gone: false,
+        };
+
+        duel.send(&Message::Hello(self::name(name)));
+        Ok(duel)
+    }
+
+    pub fn send(&mut self, message: &Message) {
+        if self.stream.write_all(message.encode().as_bytes()).is_err() {
+            self.gone = true;
+        }
+    }
+
+    /// Tells the opponent `at` characters have been typed, if that's news.
+    pub fn progress(&mut self, at: usize) {
+        if at != self.sent {
+            self.sent = at;
+            self.send(&Message::At(at));
+        }
+    }
+
+    /// Messages that have come in since the last call.
+    pub fn receive(&mut self) -> Vec<Message> {
+        let messages: Vec<Message> = self.incoming.try_iter().collect();
+
+        for message in &messages {
+            match message {
+                Message::At(at) => self.opponent_at = *at,
+                Message::Done(run, time) => self.opponent_done = Some((*run, *time)),
+                Message::Rematch => self.opponent_rematch = true,
+                Message::Bye => self.gone = true,
+                Message::Hello(name) => self.opponent.clone_from(name),
+                Message::Words(_) => {}
+            }
+        }
+
+        messages
+    }
+
+    /// Clears everything about the race just run, for the next one.
+    pub fn next_race(&mut self) {
+        self.opponent_at = 0;
+        self.opponent_done = None;
+        self.opponent_rematch = false;
+        self.rematch = false;
+        self.sent = 0;
+    }
+}
+
+/// `text` as a name to race under: one line of at most [`NAME_LEN`]
+/// characters, or `opponent` if that leaves nothing.
+fn name(text: &str) -> String {
+    let name: String = text
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(NAME_LEN)
+        .collect();
+
+    if name.is_empty() {
+        "opponent".to_string()
+    } else {
+        name
+    }
+}
+
+impl Drop for Duel {
+    fn drop(&mut self) {
+        if !self.gone {
+            self.send(&Message::Bye);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_messages() {
+        let messages = [
+            Message::Hello("ada".to_string()),
+            Message::Words(vec!["one".to_string(), "two".to_string()]),
+            Message::At(12),
+            Message::Done(
+                Run {
+                    wpm: 71.5,
+                    accuracy: 98.0,
+                },
+                Duration::from_millis(12_345),
+            ),
+            Message::Rematch,
+            Message::Bye,
+        ];
+
+        for message in messages {
+            let line = message.encode();
+            assert_eq!(Message::decode(line.trim_end()), Some(message));
+        }
+
+        assert_eq!(Message::decode("at many"), None);
+        assert_eq!(
+            Message::decode("hello  a very long name indeed "),
+            Some(Message::Hello("a very long name".to_string()))
+        );
+        assert_eq!(
+            Message::decode("hello"),
+            Some(Message::Hello("opponent".to_string()))
+        );
+    }
+}
@@ -0,0 +1,352 @@
+use super::{big, date::Date, history::Record, png, theme::Theme, BARS};
+use crate::args::CardFormat;
+use crossterm::style::{Attribute, ContentStyle};
+use ratatui::style::{Color, Modifier, Style};
+use std::fmt::Write as _;
+
+/// Most bars in the speed graph, with longer tests averaged down to it.
+const GRAPH_BARS: usize = 40;
+
+/// Colors for styles that leave them to the terminal.
+const DARK: [u8; 3] = [24, 24, 24];
+const LIGHT: [u8; 3] = [240, 240, 240];
+
+/// Size in pixels of a character in SVG cards, and of a glyph pixel in PNG.
+const FONT_SIZE: usize = 16;
+const LINE_HEIGHT: usize = 24;
+const PIXEL: usize = 3;
+const PADDING: usize = 20;
+const GRAPH_HEIGHT: usize = 60;
+
+pub fn extension(format: CardFormat) -> &'static str {
+    match format {
+        CardFormat::Png => "png",
+        CardFormat::Svg => "svg",
+        CardFormat::Ansi => "ans",
+    }
+}
+
+/// The results card of `record` with the speed of each word as a graph, in
+/// the colors of `theme`.
+pub fn render(record: &Record, theme: &Theme, format: CardFormat) -> Vec<u8> {
+    let card = Card::new(record, theme);
+
+    match format {
+        CardFormat::Png => card.png(),
+        CardFormat::Svg => card.svg().into_bytes(),
+        CardFormat::Ansi => card.ansi().into_bytes(),
+    }
+}
+
+struct Card {
+    lines: Vec<(String, Style)>,
+    speeds: Vec<f64>,
+    chart: Style,
+    axis: Style,
+    /// The text color when a style has none, which the background is picked
+    /// to go with.
+    foreground: [u8; 3],
+}
+
+impl Card {
+    fn new(record: &Record, theme: &Theme) -> Self {
+        let speeds: Vec<f64> = record.speeds.iter().map(|(_, wpm)| *wpm).collect();
+        let foreground = rgb(theme.correct.fg, LIGHT);
+
+        Self {
+            lines: vec![
+                (format!("typers · {}", record.mode), theme.accent),
+                (
+                    format!(
+                        "{:.0} wpm · {:.0}% acc · {:.0}% consistency",
+                        record.wpm, record.accuracy, record.consistency
+                    ),
+                    theme.correct,
+                ),
+                (Date::at(record.at).to_string(), theme.muted),
+            ],
+            speeds: average_down(&speeds, GRAPH_BARS),
+            chart: theme.chart,
+            axis: theme.axis,
+            foreground,
+        }
+    }
+
+    fn background(&self) -> [u8; 3] {
+        let [r, g, b] = self.foreground.map(u32::from);
+
+        if r * 299 + g * 587 + b * 114 > 128_000 {
+            DARK
+        } else {
+            LIGHT
+        }
+    }
+
+    fn most(&self) -> f64 {
+        self.speeds.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// The card as text with escape codes, to `cat` in a terminal.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn ansi(&self) -> String {
+        let mut text = String::new();
+
+        for (line, style) in &self.lines {
+            let _ = writeln!(text, "{}", ansi_style(*style).apply(line));
+        }
+
+        let most = self.most();
+        if most > 0.0 {
+            let bars: String = self
+                .speeds
+                .iter()
+                .map(|wpm| BARS[(wpm / most * (BARS.len() - 1) as f64).round() as usize])
+                .collect();
+
+            let _ = writeln!(
+                text,
+                "{}{}",
+                ansi_style(self.chart).apply(bars),
+                ansi_style(self.axis).apply(format!(" {most:.0} wpm"))
+            );
+        }
+
+        text
+    }
+
+    #[expect(clippy::cast_precision_loss)]
+    fn svg(&self) -> String {
+        let columns = self.lines.iter().map(|(line, _)| line.chars().count());
+        let width = columns.max().unwrap_or_default() * FONT_SIZE * 3 / 5 + 2 * PADDING;
+        let graph_top = PADDING + self.lines.len() * LINE_HEIGHT;
+        let height = graph_top + GRAPH_HEIGHT + 2 * PADDING;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             font-family=\"monospace\" font-size=\"{FONT_SIZE}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            hex(self.background())
+        );
+
+        for (i, (line, style)) in self.lines.iter().enumerate() {
+            let weight = if style.add_modifier.contains(Modifier::BOLD) {
+                " font-weight=\"bold\""
+            } else {
+                ""
+            };
+
+            let _ = writeln!(
+                svg,
+                "<text x=\"{PADDING}\" y=\"{}\" fill=\"{}\"{weight}>{}</text>",
+                PADDING + (i + 1) * LINE_HEIGHT - LINE_HEIGHT / 4,
+                hex(rgb(style.fg, self.foreground)),
+                escape(line)
+            );
+        }
+
+        let most = self.most();
+        if most > 0.0 {
+            let step = (width - 2 * PADDING) as f64 / (self.speeds.len().max(2) - 1) as f64;
+            let points: Vec<String> = self
+                .speeds
+                .iter()
+                .enumerate()
+                .map(|(i, wpm)| {
+                    let x = PADDING as f64 + i as f64 * step;
+                    let y = (graph_top + GRAPH_HEIGHT) as f64 - wpm / most * GRAPH_HEIGHT as f64;
+                    format!("{x:.1},{y:.1}")
+                })
+                .collect();
+
+            let _ = writeln!(
+                svg,
+                "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n\
+                 <text x=\"{PADDING}\" y=\"{}\" fill=\"{}\">{most:.0} wpm at best</text>",
+                points.join(" "),
+                hex(rgb(self.chart.fg, self.foreground)),
+                graph_top + GRAPH_HEIGHT + PADDING,
+                hex(rgb(self.axis.fg, self.foreground)),
+            );
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The card drawn in the letters of [`big`], [`PIXEL`] pixels to a dot.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn png(&self) -> Vec<u8> {
+        // Glyphs are three dots wide with one between them, and five high.
+        let advance = 4 * PIXEL;
+        let line_height = 8 * PIXEL;
+
+        let columns = self.lines.iter().map(|(line, _)| line.chars().count());
+        let width = columns.max().unwrap_or_default() * advance + 2 * PADDING;
+        let graph_top = PADDING + self.lines.len() * line_height;
+        let height = graph_top + GRAPH_HEIGHT + PADDING;
+
+        let mut canvas = Canvas {
+            width,
+            pixels: vec![self.background(); width * height],
+        };
+
+        for (i, (line, style)) in self.lines.iter().enumerate() {
+            let color = rgb(style.fg, self.foreground);
+
+            for (j, c) in line.chars().enumerate() {
+                let Some(glyph) = big::glyph(c) else {
+                    continue;
+                };
+
+                let x = PADDING + j * advance;
+                let y = PADDING + i * line_height;
+
+                for (row, bits) in glyph.iter().enumerate() {
+                    for (column, bit) in [0b100, 0b010, 0b001].into_iter().enumerate() {
+                        if bits & bit != 0 {
+                            canvas.fill(x + column * PIXEL, y + row * PIXEL, PIXEL, PIXEL, color);
+                        }
+                    }
+                }
+            }
+        }
+
+        let most = self.most();
+        if most > 0.0 {
+            let color = rgb(self.chart.fg, self.foreground);
+            let bar = (width - 2 * PADDING) / self.speeds.len();
+
+            for (i, wpm) in self.speeds.iter().enumerate() {
+                let tall = ((wpm / most) * GRAPH_HEIGHT as f64).round() as usize;
+                let x = PADDING + i * bar;
+                let y = graph_top + GRAPH_HEIGHT - tall;
+                canvas.fill(x, y, bar.saturating_sub(1).max(1), tall, color);
+            }
+        }
+
+        png::encode(width, &canvas.pixels)
+    }
+}
+
+struct Canvas {
+    width: usize,
+    pixels: Vec<[u8; 3]>,
+}
+
+impl Canvas {
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: [u8; 3]) {
+        for row in y..y + height {
+            for column in x..(x + width).min(self.width) {
+                if let Some(pixel) = self.pixels.get_mut(row * self.width + column) {
+                    *pixel = color;
+                }
+            }
+        }
+    }
+}
+
+/// `speeds` averaged into at most `n` groups in order.
+#[expect(clippy::cast_precision_loss)]
+pub fn average_down(speeds: &[f64], n: usize) -> Vec<f64> {
+    let size = speeds.len().div_ceil(n).max(1);
+
+    speeds
+        .chunks(size)
+        .map(|chunk| chunk.iter().sum::<f64>() / chunk.len() as f64)
+        .collect()
+}
+
+fn ansi_style(style: Style) -> ContentStyle {
+    let mut content = ContentStyle::new();
+    content.foreground_color = style.fg.map(Into::into);
+    content.background_color = style.bg.map(Into::into);
+
+    for (modifier, attribute) in [
+        (Modifier::BOLD, Attribute::Bold),
+        (Modifier::DIM, Attribute::Dim),
+        (Modifier::ITALIC, Attribute::Italic),
+        (Modifier::UNDERLINED, Attribute::Underlined),
+        (Modifier::REVERSED, Attribute::Reverse),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            content.attributes.set(attribute);
+        }
+    }
+
+    content
+}
+
+/// The usual look of the terminal colors the themes are made of.
+fn rgb(color: Option<Color>, fallback: [u8; 3]) -> [u8; 3] {
+    match color {
+        Some(Color::Rgb(r, g, b)) => [r, g, b],
+        Some(Color::Black) => [0, 0, 0],
+        Some(Color::Red) => [205, 49, 49],
+        Some(Color::Green) => [13, 188, 121],
+        Some(Color::Yellow) => [229, 229, 16],
+        Some(Color::Blue) => [36, 114, 200],
+        Some(Color::Magenta) => [188, 63, 188],
+        Some(Color::Cyan) => [17, 168, 205],
+        Some(Color::Gray) => [204, 204, 204],
+        Some(Color::DarkGray) => [118, 118, 118],
+        Some(Color::LightRed) => [241, 76, 76],
+        Some(Color::LightGreen) => [35, 209, 139],
+        Some(Color::LightYellow) => [245, 245, 67],
+        Some(Color::LightBlue) => [59, 142, 234],
+        Some(Color::LightMagenta) => [214, 112, 214],
+        Some(Color::LightCyan) => [41, 184, 219],
+        Some(Color::White) => [255, 255, 255],
+        Some(Color::Indexed(_) | Color::Reset) | None => fallback,
+    }
+}
+
+fn hex([r, g, b]: [u8; 3]) -> String {
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::ThemeName;
+
+    #[test]
+    fn renders_cards() {
+        let record = Record {
+            mode: "words 3 · <english>".to_string(),
+            wpm: 72.0,
+            speeds: vec![("a".to_string(), 60.0), ("b".to_string(), 80.0)],
+            ..Record::default()
+        };
+        let theme = Theme::new(ThemeName::Default);
+
+        let svg = String::from_utf8(render(&record, &theme, CardFormat::Svg)).unwrap();
+        assert!(svg.contains("words 3 · &lt;english&gt;"));
+        assert!(svg.contains("<polyline"));
+
+        let ansi = String::from_utf8(render(&record, &theme, CardFormat::Ansi)).unwrap();
+        assert!(ansi.contains("72 wpm"));
+        assert!(ansi.contains('▆') && ansi.contains('█'));
+
+        assert!(render(&record, &theme, CardFormat::Png).starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn averages_long_tests_down() {
+        assert_eq!(average_down(&[1.0, 3.0, 5.0, 7.0, 9.0], 2), [3.0, 8.0]);
+        assert_eq!(average_down(&[1.0, 2.0], 5), [1.0, 2.0]);
+    }
+}
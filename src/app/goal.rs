@@ -0,0 +1,168 @@
+use super::{date::Date, history::History};
+use crate::paths::Paths;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fmt::Write as _, fs, io, path::PathBuf};
+
+/// Days back the trend is worked out over.
+const TREND_DAYS: u64 = 30;
+
+/// Days with a test among those needed for a trend, as one day's tests
+/// alone would make a steep one out of a good or bad hour.
+const MIN_DAYS: usize = 3;
+
+/// The speed to reach by a day, set with `typers goal set`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Goal {
+    pub wpm: f64,
+    /// The UTC day to reach it by, like 2026-12-31.
+    pub by: String,
+}
+
+impl Goal {
+    fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.data.join("goal.toml"))
+    }
+
+    /// The goal set, if any.
+    pub fn load() -> Option<Self> {
+        Self::path()
+            .and_then(fs::read_to_string)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(self).map_err(io::Error::other)?)
+    }
+
+    pub fn clear() -> io::Result<()> {
+        match fs::remove_file(Self::path()?) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// The speed `history` is at `now`, in seconds since the Unix epoch, and
+    /// on pace for by the goal's day, from the trend over the last
+    /// [`TREND_DAYS`] days.
+    #[expect(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn pace(&self, history: &History, now: u64) -> Option<(f64, f64)> {
+        let since = now.saturating_sub(TREND_DAYS * 86_400);
+        let points: Vec<(f64, f64)> = history
+            .tests
+            .iter()
+            .filter(|test| test.at >= since)
+            .map(|test| ((test.at - since) as f64 / 86_400.0, test.wpm))
+            .collect();
+
+        let days: BTreeSet<u64> = points.iter().map(|(day, _)| day.floor() as u64).collect();
+        if days.len() < MIN_DAYS {
+            return None;
+        }
+
+        let (slope, intercept) = fit(&points);
+        let by = Date::parse(&self.by)?.days() as f64 - since as f64 / 86_400.0;
+        let at = |day: f64| (intercept + slope * day).max(0.0);
+        Some((at(TREND_DAYS as f64), at(by)))
+    }
+
+    /// The line about the goal on the finish screen, as of `now`.
+    pub fn summary(&self, history: &History, now: u64) -> Option<String> {
+        let (current, pace) = self.pace(history, now)?;
+
+        Some(if current >= self.wpm {
+            format!("Goal: {:.0} wpm reached", self.wpm)
+        } else {
+            format!("Goal: on pace for {pace:.0} of {:.0}", self.wpm)
+        })
+    }
+
+    /// How far along the goal is, as of `now`, for `typers stats`.
+    pub fn progress(&self, history: &History, now: u64) -> String {
+        let mut progress = format!("goal {:.0} wpm by {}", self.wpm, self.by);
+
+        match self.pace(history, now) {
+            Some((current, _)) if current >= self.wpm => {
+                let _ = write!(progress, " · at {current:.0} now, reached");
+            }
+            Some((current, pace)) => {
+                let on = if pace >= self.wpm { "on" } else { "behind" };
+                let _ = write!(progress, " · at {current:.0} now · {on} pace for {pace:.0}");
+            }
+            None => progress.push_str(" · too few days of recent tests for a trend"),
+        }
+
+        progress.push('\n');
+        progress
+    }
+}
+
+/// Slope and intercept of the least squares line through `points`.
+#[expect(clippy::cast_precision_loss)]
+fn fit(points: &[(f64, f64)]) -> (f64, f64) {
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+
+    let spread: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let slope = if spread > 0.0 {
+        points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum::<f64>()
+            / spread
+    } else {
+        0.0
+    };
+
+    (slope, mean_y - slope * mean_x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::history::Record;
+
+    #[test]
+    fn projects_the_trend() {
+        let day = 86_400;
+        let now = 100 * day;
+        // A word a minute faster every day for the last ten.
+        let history = History {
+            tests: (0..10)
+                .map(|i: u32| Record {
+                    at: now - u64::from(9 - i) * day,
+                    wpm: 60.0 + f64::from(i),
+                    ..Record::default()
+                })
+                .collect(),
+        };
+        let goal = Goal {
+            wpm: 80.0,
+            by: Date::at(now + 10 * day).to_string(),
+        };
+
+        let (current, pace) = goal.pace(&history, now).unwrap();
+        assert!((current - 69.0).abs() < 1e-6, "{current}");
+        assert!((pace - 79.0).abs() < 1e-6, "{pace}");
+        assert!(goal
+            .progress(&history, now)
+            .contains("at 69 now · behind pace for 79"));
+
+        // Lots of tests, but on two days.
+        let few = History {
+            tests: [&history.tests[..2]; 3].concat(),
+        };
+        assert_eq!(goal.pace(&few, now), None);
+    }
+}
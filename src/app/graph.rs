@@ -0,0 +1,111 @@
+use super::{
+    date::Date,
+    history::{self, History},
+    BARS,
+};
+use std::{collections::BTreeMap, fmt::Write as _};
+
+/// Rows of bars, each a full block high.
+const HEIGHT: usize = 8;
+
+/// Columns the speeds are labelled in, left of the bars.
+const LABEL: usize = 5;
+
+/// The average speed of each of the last `days` UTC days up to `today`,
+/// counted from the Unix epoch, as a bar chart for `typers graph`.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn render(history: &History, days: u64, today: i64) -> String {
+    let first = today - days as i64 + 1;
+    let mut speeds: BTreeMap<i64, Vec<f64>> = BTreeMap::new();
+
+    for test in &history.tests {
+        let day = (test.at / 86_400) as i64;
+
+        if (first..=today).contains(&day) {
+            speeds.entry(day).or_default().push(test.wpm);
+        }
+    }
+
+    let from = Date::from_days(first).to_string();
+    let to = Date::from_days(today).to_string();
+
+    if speeds.is_empty() {
+        return format!("no tests since {from}\n");
+    }
+
+    let averages: Vec<Option<f64>> = (first..=today)
+        .map(|day| {
+            let speeds = speeds.get(&day)?;
+            Some(speeds.iter().sum::<f64>() / speeds.len() as f64)
+        })
+        .collect();
+    let most = averages.iter().flatten().copied().fold(0.0, f64::max);
+
+    // How many eighths of a row each day's bar is high.
+    let heights: Vec<usize> = averages
+        .iter()
+        .map(|wpm| wpm.map_or(0, |wpm| (wpm / most * (HEIGHT * 8) as f64).round() as usize))
+        .collect();
+
+    let mut graph = format!(
+        "wpm by day since {from}, over {}\n\n",
+        history::tests(speeds.values().map(Vec::len).sum())
+    );
+
+    for row in (0..HEIGHT).rev() {
+        let label = match row {
+            _ if row == HEIGHT - 1 => format!("{most:.0}"),
+            0 => "0".to_string(),
+            _ => String::new(),
+        };
+
+        let bars: String = heights
+            .iter()
+            .map(|height| match height.saturating_sub(row * 8) {
+                0 => ' ',
+                eighths => BARS[eighths.min(8) - 1],
+            })
+            .collect();
+
+        let _ = writeln!(graph, "{label:>LABEL$} ┤{}", bars.trim_end());
+    }
+
+    let gap = (days as usize).saturating_sub(from.len() + to.len()).max(1);
+
+    let _ = writeln!(graph, "{:>LABEL$} └{}", "", "─".repeat(days as usize));
+    let _ = writeln!(graph, "{:>LABEL$}  {from}{:gap$}{to}", "", "");
+    graph
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::history::Record;
+
+    #[test]
+    fn draws_a_bar_for_each_day() {
+        let test = |day: u64, wpm| Record {
+            at: day * 86_400 + 60,
+            wpm,
+            ..Record::default()
+        };
+        let history = History {
+            tests: vec![test(7, 40.0), test(7, 60.0), test(9, 100.0), test(1, 500.0)],
+        };
+
+        let graph = render(&history, 3, 9);
+        let lines: Vec<&str> = graph.lines().collect();
+
+        // Half as fast on the first day, and nothing on the second.
+        assert_eq!(lines[2], "  100 ┤  █");
+        assert_eq!(lines[6], "      ┤█ █");
+        assert_eq!(lines[9], "    0 ┤█ █");
+        assert!(lines[11].ends_with("1970-01-10"));
+        assert_eq!(render(&history, 1, 20), "no tests since 1970-01-21\n");
+    }
+}
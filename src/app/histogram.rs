@@ -0,0 +1,80 @@
+use std::fmt::Write as _;
+
+/// Most buckets shown, with wider ones for a wider spread of speeds.
+const MAX_BUCKETS: usize = 12;
+
+/// Columns the longest bar takes.
+const BAR_WIDTH: usize = 30;
+
+/// How many of `wpms` fall in each band of speeds, as bars for `typers
+/// stats`, to see the spread and the slow and fast ends rather than just
+/// the average.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn render(wpms: &[f64]) -> String {
+    if wpms.is_empty() {
+        return String::new();
+    }
+
+    let low = wpms.iter().copied().fold(f64::MAX, f64::min);
+    let high = wpms.iter().copied().fold(0.0, f64::max);
+
+    // Bands a multiple of 10 wpm wide, starting on one.
+    let first = (low / 10.0).floor() as usize * 10;
+    let span = high as usize + 1 - first;
+    let size = span.div_ceil(10 * MAX_BUCKETS) * 10;
+    let mut counts = vec![0_usize; span.div_ceil(size)];
+
+    for wpm in wpms {
+        counts[(*wpm as usize).saturating_sub(first) / size] += 1;
+    }
+
+    let most = counts.iter().copied().max().unwrap_or(1);
+    let mut histogram = "\nspread of speeds\n".to_string();
+
+    for (i, count) in counts.iter().enumerate() {
+        let from = first + i * size;
+        let length = (*count as f64 / most as f64 * BAR_WIDTH as f64).round() as usize;
+        let bar = if *count > 0 {
+            "█".repeat(length.max(1))
+        } else {
+            String::new()
+        };
+
+        let _ = writeln!(
+            histogram,
+            "  {from:>4}–{:<4} {bar:<BAR_WIDTH$} {count}",
+            from + size - 1
+        );
+    }
+
+    histogram
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_the_speeds() {
+        let histogram = render(&[42.0, 48.9, 51.0, 79.5]);
+        let lines: Vec<&str> = histogram.lines().collect();
+
+        assert_eq!(lines[2], format!("    40–49   {} 2", "█".repeat(30)));
+        assert_eq!(
+            lines[3],
+            format!("    50–59   {}{} 1", "█".repeat(15), " ".repeat(15))
+        );
+        assert_eq!(lines[4], format!("    60–69   {} 0", " ".repeat(30)));
+        assert_eq!(lines.len(), 6);
+        assert_eq!(render(&[]), "");
+
+        // Wider bands once there'd be too many.
+        let wide = render(&[0.0, 300.0]);
+        assert!(wide.contains("     0–29 "), "{wide}");
+        assert_eq!(wide.lines().count(), 13);
+    }
+}
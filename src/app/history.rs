@@ -0,0 +1,773 @@
+use super::{
+    archive::{self, Archive},
+    date::Date,
+    histogram,
+    store::{self, Store},
+};
+use crate::{
+    args::{HistoryFilter, HistoryStore},
+    paths::Paths,
+};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    sync::RwLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Times a word has to have been typed for its speed to count as slow.
+const MIN_SEEN: usize = 2;
+
+/// Other tests of a mode needed to say how a new one compares.
+const MIN_COMPARED: usize = 3;
+
+/// How the history is kept, from the `history-store` in the config.
+static STORE: RwLock<HistoryStore> = RwLock::new(HistoryStore::Toml);
+
+/// How many words `typers stats` lists as slowest and most missed.
+const LISTED: usize = 10;
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+/// One finished test, as kept in the history.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Record {
+    /// Picked with [`new_id`].
+    pub id: String,
+    /// When the test finished, in seconds since the Unix epoch.
+    pub at: u64,
+    pub mode: String,
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub consistency: f64,
+    /// Seconds the test took.
+    pub time: f64,
+    /// The words as given, for typing the same test again.
+    pub text: String,
+    /// Burst speed of each fully typed word.
+    pub speeds: Vec<(String, f64)>,
+    /// The words typed wrong at least once.
+    pub missed: Vec<String>,
+    /// Labels given with `--tag`.
+    pub tags: Vec<String>,
+    /// The UTC day of the challenge, for the first try at `typers daily`.
+    pub daily: Option<String>,
+    /// Shared by the tests of one `--tests` session.
+    pub session: Option<String>,
+    /// What made the test look off, such as pauses or pasting.
+    pub flags: Vec<String>,
+    /// The share of its opening speed an `--endurance` run held to the end.
+    pub endurance: Option<f64>,
+    /// Written on the results screen with `n`, such as how it went or what
+    /// was new, like "tired" or "new keyboard".
+    pub note: Option<String>,
+    /// Where the words came from, such as the file or command, to trace the
+    /// test back to its material. Words made up by typers have none.
+    pub source: Option<String>,
+}
+
+impl Record {
+    /// The hour of the day the test finished in, in UTC.
+    pub fn hour(&self) -> usize {
+        usize::try_from(self.at % 86_400 / 3600).unwrap_or_default()
+    }
+
+    /// The day of the week the test finished on, from Monday.
+    pub fn weekday(&self) -> usize {
+        // The Unix epoch was a Thursday.
+        usize::try_from((self.at / 86_400 + 3) % 7).unwrap_or_default()
+    }
+
+    /// The date and UTC time the test finished.
+    pub fn when(&self) -> String {
+        format!(
+            "{} {:02}:{:02}",
+            Date::at(self.at),
+            self.hour(),
+            self.at % 3600 / 60
+        )
+    }
+
+    /// A record with a new id, finished now.
+    pub fn new() -> Self {
+        Self {
+            id: new_id(),
+            at: now(),
+            ..Self::default()
+        }
+    }
+}
+
+/// Seconds since the Unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+/// An id picked at random, so ones from different machines don't clash.
+pub fn new_id() -> String {
+    format!("{:012x}", rand::thread_rng().gen::<u64>() >> 16)
+}
+
+/// Every finished test, oldest first, kept in the data directory. Tests are
+/// appended to the file as they finish rather than writing it out again,
+/// and those of past months are rolled into the [`Archive`].
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct History {
+    pub tests: Vec<Record>,
+}
+
+impl History {
+    /// Keeps the history in `store` from here on.
+    pub fn use_store(store: HistoryStore) {
+        if let Ok(mut current) = STORE.write() {
+            *current = store;
+        }
+    }
+
+    /// The file the current profile's history is kept in, and how. A history
+    /// kept in another store is moved over to this one first, so it isn't
+    /// split between the two.
+    fn path() -> io::Result<(PathBuf, &'static dyn Store)> {
+        let dir = Paths::new()?.data;
+        let picked = STORE.read().map_or(HistoryStore::Toml, |store| *store);
+        let (path, store) = find(&dir, picked);
+        let wanted = store::of(picked);
+
+        if path.exists() && store.file() != wanted.file() {
+            let history = Self::read(&path, store)?;
+            let moved = dir.join(wanted.file());
+            fs::write(&moved, wanted.write(&history).map_err(io::Error::other)?)?;
+            fs::remove_file(path)?;
+            return Ok((moved, wanted));
+        }
+
+        Ok((path, store))
+    }
+
+    /// Reads the whole history, archive and all, which is empty until a
+    /// test has finished.
+    pub fn load() -> io::Result<Self> {
+        Self::load_from("")
+    }
+
+    /// Reads the tests of this month and the few before it, kept in the
+    /// history file, rolling any older ones into the archive first.
+    pub fn load_recent() -> io::Result<Self> {
+        let (path, store) = Self::path()?;
+        let mut history = Self::read(&path, store)?;
+        let (old, recent) = archive::split(history.tests, now());
+        history.tests = recent;
+
+        if !old.is_empty() {
+            Archive::new(&Paths::new()?.data).add(old, store)?;
+            fs::write(path, store.write(&history).map_err(io::Error::other)?)?;
+        }
+
+        Ok(history)
+    }
+
+    /// Reads the tests finished since `at`, in seconds since the Unix epoch,
+    /// along with the rest of the month and the recent ones.
+    pub fn load_since(at: u64) -> io::Result<Self> {
+        Self::load_from(&archive::month(at))
+    }
+
+    /// Reads the recent tests and the archived ones from `month` on.
+    fn load_from(month: &str) -> io::Result<Self> {
+        let mut history = Self::load_recent()?;
+        history.merge(Archive::new(&Paths::new()?.data).load_from(month)?);
+        Ok(history)
+    }
+
+    /// Reads the tests archived in `month`, written like 2026-07.
+    pub fn load_month(month: &str) -> io::Result<Vec<Record>> {
+        Archive::new(&Paths::new()?.data).load(month)
+    }
+
+    /// The months with archived tests, oldest first.
+    pub fn archived_months() -> Vec<String> {
+        Paths::new().map_or_else(|_| Vec::new(), |paths| Archive::new(&paths.data).months())
+    }
+
+    /// How many tests are archived, without reading them.
+    pub fn archived() -> usize {
+        Paths::new().map_or(0, |paths| Archive::new(&paths.data).count())
+    }
+
+    /// Reads the whole history of `profile`, whichever is current, from
+    /// whichever store it's kept in.
+    pub fn load_profile(profile: Option<&str>) -> io::Result<Self> {
+        let picked = STORE.read().map_or(HistoryStore::Toml, |store| *store);
+        let dir = Paths::of(profile)?.data;
+        let (path, store) = find(&dir, picked);
+        let mut history = Self::read(&path, store)?;
+        history.merge(Archive::new(&dir).load_from("")?);
+        Ok(history)
+    }
+
+    /// Reads the history file at `path`, kept in `store`.
+    pub fn read(path: &Path, store: &dyn Store) -> io::Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+
+        store.parse(&text).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid history {}: {err}", path.display()),
+            )
+        })
+    }
+
+    /// Adds `record` to the end of the file.
+    pub fn append(record: &Record) -> io::Result<()> {
+        let (path, store) = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let mut text = store.entry(record).map_err(io::Error::other)?;
+
+        // A new file starts with the version its tests are kept in.
+        if fs::metadata(&path).map_or(true, |file| file.len() == 0) {
+            text.insert_str(0, &store.header());
+        }
+
+        let mut file = File::options().create(true).append(true).open(path)?;
+        file.write_all(text.as_bytes())
+    }
+
+    /// Writes the whole history out again, as read with [`History::load`],
+    /// after tests were taken out or added. Tests of past months go to the
+    /// archive.
+    pub fn save(&self) -> io::Result<()> {
+        let (path, store) = Self::path()?;
+        let (old, tests) = archive::split(self.tests.clone(), now());
+        Archive::new(&Paths::new()?.data).replace(old, store)?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(
+            path,
+            store.write(&Self { tests }).map_err(io::Error::other)?,
+        )
+    }
+
+    /// Adds the tests of `records` not in the history yet, going by their
+    /// ids, keeping it ordered by when the tests finished. Returns how many
+    /// were added.
+    pub fn merge(&mut self, records: impl IntoIterator<Item = Record>) -> usize {
+        let seen: HashSet<String> = self.tests.iter().map(|test| test.id.clone()).collect();
+        let count = self.tests.len();
+
+        self.tests.extend(
+            records
+                .into_iter()
+                .filter(|record| !seen.contains(&record.id)),
+        );
+        self.tests.sort_by_key(|test| test.at);
+        self.tests.len() - count
+    }
+
+    /// Takes out the tests finished before the start of `date`.
+    pub fn prune(&mut self, date: Date) {
+        let start = u64::try_from(date.days() * 86_400).unwrap_or_default();
+        self.tests.retain(|test| test.at >= start);
+    }
+
+    /// The share of the other tests of the same mode slower than `record`,
+    /// and how many there are, once there are at least [`MIN_COMPARED`].
+    #[expect(clippy::cast_precision_loss)]
+    pub fn standing(&self, record: &Record) -> Option<(f64, usize)> {
+        let others: Vec<f64> = self
+            .tests
+            .iter()
+            .filter(|test| test.mode == record.mode && test.id != record.id)
+            .map(|test| test.wpm)
+            .collect();
+
+        if others.len() < MIN_COMPARED {
+            return None;
+        }
+
+        let slower = others.iter().filter(|wpm| **wpm < record.wpm).count();
+        Some((slower as f64 / others.len() as f64 * 100.0, others.len()))
+    }
+
+    /// The mean speed of the last `n` tests of `mode`, unless there are none.
+    #[expect(clippy::cast_precision_loss)]
+    pub fn recent_speed(&self, mode: &str, n: usize) -> Option<f64> {
+        let speeds: Vec<f64> = self
+            .tests
+            .iter()
+            .rev()
+            .filter(|test| test.mode == mode)
+            .take(n)
+            .map(|test| test.wpm)
+            .collect();
+
+        (!speeds.is_empty()).then(|| speeds.iter().sum::<f64>() / speeds.len() as f64)
+    }
+
+    /// The tests `filter` picks out, in the same order.
+    pub fn filter(&self, filter: &HistoryFilter) -> Self {
+        let tests = self
+            .tests
+            .iter()
+            .filter(|test| filter.tag.iter().all(|tag| test.tags.contains(tag)))
+            .filter(|test| !filter.daily || test.daily.is_some())
+            .filter(|test| !filter.valid || test.flags.is_empty())
+            .filter(|test| {
+                filter
+                    .mode
+                    .as_ref()
+                    .is_none_or(|mode| test.mode.contains(mode))
+            })
+            .filter(|test| {
+                filter.note.as_ref().is_none_or(|note| {
+                    test.note
+                        .as_ref()
+                        .is_some_and(|text| text.to_lowercase().contains(&note.to_lowercase()))
+                })
+            })
+            .cloned()
+            .collect();
+
+        Self { tests }
+    }
+
+    /// Writes `note` on the test with `id`, or takes its note off when empty.
+    pub fn write_note(&mut self, id: &str, note: &str) -> io::Result<()> {
+        let test = self
+            .tests
+            .iter_mut()
+            .find(|test| test.id == id)
+            .ok_or_else(|| not_found(format!("no test {id} in the history")))?;
+
+        test.note = Some(note.to_string()).filter(|note| !note.is_empty());
+        Ok(())
+    }
+
+    /// The test with an id starting with `id`, or the last one without.
+    pub fn find(&self, id: Option<&str>) -> io::Result<&Record> {
+        let Some(id) = id else {
+            return self
+                .tests
+                .last()
+                .ok_or_else(|| not_found("no tests in the history yet".to_string()));
+        };
+
+        let mut found = self.tests.iter().filter(|test| test.id.starts_with(id));
+
+        match (found.next(), found.next()) {
+            (Some(test), None) => Ok(test),
+            (Some(_), Some(_)) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("more than one test has an id starting with {id}"),
+            )),
+            (None, _) => Err(not_found(format!("no test {id} in the history"))),
+        }
+    }
+
+    /// Average speed of each word typed at least [`MIN_SEEN`] times, with
+    /// how often it was, slowest first.
+    pub fn slowest(&self, n: usize) -> Vec<(String, f64, usize)> {
+        let mut speeds: HashMap<&str, Vec<f64>> = HashMap::new();
+
+        for (word, wpm) in self.tests.iter().flat_map(|test| &test.speeds) {
+            speeds.entry(word).or_default().push(*wpm);
+        }
+
+        let mut slowest: Vec<_> = speeds
+            .into_iter()
+            .filter(|(_, speeds)| speeds.len() >= MIN_SEEN)
+            .map(|(word, speeds)| (word.to_string(), mean(&speeds), speeds.len()))
+            .collect();
+
+        slowest.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        slowest.truncate(n);
+        slowest
+    }
+
+    /// How many tests each word was typed wrong in, out of how many it was
+    /// in, most missed first.
+    pub fn most_missed(&self, n: usize) -> Vec<(String, usize, usize)> {
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+
+        for test in &self.tests {
+            for word in test.text.split_whitespace() {
+                counts.entry(word).or_default().1 += 1;
+            }
+
+            for word in &test.missed {
+                counts.entry(word).or_default().0 += 1;
+            }
+        }
+
+        let mut missed: Vec<_> = counts
+            .into_iter()
+            .filter(|(_, (missed, _))| *missed > 0)
+            .map(|(word, (missed, seen))| (word.to_string(), missed, seen.max(missed)))
+            .collect();
+
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        missed.truncate(n);
+        missed
+    }
+
+    /// The slowest and most missed words together, for a test of them.
+    pub fn weak_words(&self) -> Vec<String> {
+        let mut words: Vec<String> = self
+            .slowest(LISTED)
+            .into_iter()
+            .map(|(word, ..)| word)
+            .collect();
+
+        for (word, ..) in self.most_missed(LISTED) {
+            if !words.contains(&word) {
+                words.push(word);
+            }
+        }
+
+        words
+    }
+
+    /// The report for `typers stats`.
+    pub fn report(&self) -> String {
+        if self.tests.is_empty() {
+            return "no tests finished yet\n".to_string();
+        }
+
+        let wpms: Vec<f64> = self.tests.iter().map(|test| test.wpm).collect();
+        let accuracies: Vec<f64> = self.tests.iter().map(|test| test.accuracy).collect();
+        let best = wpms.iter().copied().fold(0.0, f64::max);
+
+        let mut report = format!(
+            "{} · {:.0} wpm on average · {best:.0} at best · {:.0}% accuracy\n",
+            tests(self.tests.len()),
+            mean(&wpms),
+            mean(&accuracies),
+        );
+        report.push_str(&histogram::render(&wpms));
+
+        report.push_str("\nby hour of the day, in UTC\n");
+        for (hour, wpm, accuracy, n) in self.breakdown(Record::hour) {
+            let _ = writeln!(
+                report,
+                "  {hour:02}:00  {wpm:>4.0} wpm  {accuracy:>3.0}%  over {}",
+                tests(n)
+            );
+        }
+
+        report.push_str("\nby day of the week\n");
+        for (day, wpm, accuracy, n) in self.breakdown(Record::weekday) {
+            let _ = writeln!(
+                report,
+                "  {}    {wpm:>4.0} wpm  {accuracy:>3.0}%  over {}",
+                WEEKDAYS[day],
+                tests(n)
+            );
+        }
+
+        report.push_str(&self.words_report());
+        report
+    }
+
+    /// Average speed and accuracy of the tests grouped by `key`, with how
+    /// many there were, in order of the key.
+    fn breakdown(&self, key: impl Fn(&Record) -> usize) -> Vec<(usize, f64, f64, usize)> {
+        let mut groups: BTreeMap<usize, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+
+        for test in &self.tests {
+            let (wpms, accuracies) = groups.entry(key(test)).or_default();
+            wpms.push(test.wpm);
+            accuracies.push(test.accuracy);
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, (wpms, accuracies))| (key, mean(&wpms), mean(&accuracies), wpms.len()))
+            .collect()
+    }
+
+    fn words_report(&self) -> String {
+        let mut report = String::new();
+
+        let slowest = self.slowest(LISTED);
+        if !slowest.is_empty() {
+            report.push_str("\nslowest words\n");
+        }
+        for (word, wpm, seen) in &slowest {
+            let _ = writeln!(report, "  {word:<16} {wpm:>4.0} wpm  over {seen} times");
+        }
+
+        let missed = self.most_missed(LISTED);
+        if !missed.is_empty() {
+            report.push_str("\nmost missed words\n");
+        }
+        for (word, missed, seen) in &missed {
+            let _ = writeln!(report, "  {word:<16} {missed:>4} of {seen} times");
+        }
+
+        if !slowest.is_empty() || !missed.is_empty() {
+            report.push_str("\npress s on the results of a test to practice these\n");
+        }
+
+        report
+    }
+}
+
+/// `n` tests, in words.
+pub fn tests(n: usize) -> String {
+    if n == 1 {
+        "1 test".to_string()
+    } else {
+        format!("{n} tests")
+    }
+}
+
+fn not_found(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, message)
+}
+
+/// The history file in `dir`, in `picked` unless there's only one kept in
+/// another store.
+fn find(dir: &Path, picked: HistoryStore) -> (PathBuf, &'static dyn Store) {
+    let picked = store::of(picked);
+
+    std::iter::once(picked)
+        .chain(store::ALL.map(store::of))
+        .map(|store| (dir.join(store.file()), store))
+        .find(|(path, _)| path.exists())
+        .unwrap_or_else(|| (dir.join(picked.file()), picked))
+}
+
+#[expect(clippy::cast_precision_loss)]
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(text: &str, speeds: &[(&str, f64)], missed: &[&str]) -> Record {
+        Record {
+            text: text.to_string(),
+            speeds: speeds
+                .iter()
+                .map(|(word, wpm)| ((*word).to_string(), *wpm))
+                .collect(),
+            missed: missed.iter().map(|word| (*word).to_string()).collect(),
+            ..Record::new()
+        }
+    }
+
+    #[test]
+    fn finds_weak_words() {
+        let history = History {
+            tests: vec![
+                record(
+                    "the quick fox",
+                    &[("the", 90.0), ("quick", 40.0), ("fox", 60.0)],
+                    &["quick"],
+                ),
+                record(
+                    "the quick dog",
+                    &[("the", 100.0), ("quick", 50.0), ("dog", 10.0)],
+                    &["quick", "dog"],
+                ),
+            ],
+        };
+
+        assert_eq!(
+            history.slowest(5),
+            [("quick".to_string(), 45.0, 2), ("the".to_string(), 95.0, 2)]
+        );
+        assert_eq!(
+            history.most_missed(5),
+            [("quick".to_string(), 2, 2), ("dog".to_string(), 1, 1)]
+        );
+        assert_eq!(history.weak_words(), ["quick", "the", "dog"]);
+    }
+
+    #[test]
+    fn filters_by_tag() {
+        let tagged = |tags: &[&str]| Record {
+            tags: tags.iter().map(|tag| (*tag).to_string()).collect(),
+            ..Record::new()
+        };
+        let history = History {
+            tests: vec![tagged(&[]), tagged(&["new"]), tagged(&["new", "evening"])],
+        };
+
+        let filter = |tags: &[&str]| HistoryFilter {
+            tag: tags.iter().map(|tag| (*tag).to_string()).collect(),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(history.filter(&filter(&[])).tests.len(), 3);
+        assert_eq!(history.filter(&filter(&["new"])).tests.len(), 2);
+        assert_eq!(history.filter(&filter(&["new", "evening"])).tests.len(), 1);
+    }
+
+    #[test]
+    fn filters_daily_challenges() {
+        let history = History {
+            tests: vec![
+                Record::new(),
+                Record {
+                    daily: Some("2026-10-14".to_string()),
+                    ..Record::new()
+                },
+            ],
+        };
+
+        let filter = HistoryFilter {
+            daily: true,
+            ..HistoryFilter::default()
+        };
+        assert_eq!(history.filter(&filter).tests, history.tests[1..]);
+
+        let mut flagged = history.tests.clone();
+        flagged[1].flags = vec!["pasted".to_string()];
+        let filter = HistoryFilter {
+            valid: true,
+            ..HistoryFilter::default()
+        };
+        let history = History { tests: flagged };
+        assert_eq!(history.filter(&filter).tests, history.tests[..1]);
+    }
+
+    #[test]
+    fn filters_by_note() {
+        let mut history = History {
+            tests: vec![Record::new(), Record::new()],
+        };
+        let id = history.tests[1].id.clone();
+        history.write_note(&id, "Tired, late").unwrap();
+
+        let filter = HistoryFilter {
+            note: Some("tired".to_string()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(history.filter(&filter).tests, history.tests[1..]);
+
+        history.write_note(&id, "").unwrap();
+        assert_eq!(history.tests[1].note, None);
+        assert!(history.write_note("nope", "x").is_err());
+    }
+
+    #[test]
+    fn breaks_down_by_time() {
+        let at = |at, wpm| Record {
+            at,
+            wpm,
+            accuracy: 100.0,
+            ..Record::default()
+        };
+        // Thursday 1 January 1970, twice at one in the morning, then Monday
+        // 5 January at noon.
+        let history = History {
+            tests: vec![
+                at(3600, 50.0),
+                at(5400, 70.0),
+                at(4 * 86_400 + 43_200, 90.0),
+            ],
+        };
+
+        assert_eq!(
+            history.breakdown(Record::hour),
+            [(1, 60.0, 100.0, 2), (12, 90.0, 100.0, 1)]
+        );
+        assert_eq!(
+            history.breakdown(Record::weekday),
+            [(0, 90.0, 100.0, 1), (3, 60.0, 100.0, 2)]
+        );
+    }
+
+    #[test]
+    fn ranks_against_the_same_mode() {
+        let test = |mode: &str, wpm| Record {
+            mode: mode.to_string(),
+            wpm,
+            ..Record::new()
+        };
+        let mut history = History {
+            tests: vec![test("a", 50.0), test("a", 60.0), test("b", 10.0)],
+        };
+        let last = test("a", 70.0);
+        history.tests.push(last.clone());
+        assert_eq!(history.standing(&last), None);
+
+        history.tests.push(test("a", 40.0));
+        assert_eq!(history.standing(&last), Some((100.0, 3)));
+    }
+
+    #[test]
+    fn averages_the_recent_tests_of_the_mode() {
+        let test = |mode: &str, wpm| Record {
+            mode: mode.to_string(),
+            wpm,
+            ..Record::default()
+        };
+        let history = History {
+            tests: vec![
+                test("words", 10.0),
+                test("words", 50.0),
+                test("quote", 90.0),
+                test("words", 70.0),
+            ],
+        };
+
+        assert_eq!(history.recent_speed("words", 2), Some(60.0));
+        assert_eq!(history.recent_speed("time", 2), None);
+    }
+
+    #[test]
+    fn prunes_before_the_day() {
+        let at = |at| Record {
+            at,
+            ..Record::default()
+        };
+        let mut history = History {
+            tests: vec![at(86_399), at(86_400), at(200_000)],
+        };
+
+        history.prune(Date::parse("1970-01-02").unwrap());
+        assert_eq!(history.tests, [at(86_400), at(200_000)]);
+    }
+
+    #[test]
+    fn merges_by_id() {
+        let test = |id: &str, at| Record {
+            id: id.to_string(),
+            at,
+            ..Record::default()
+        };
+        let mut history = History {
+            tests: vec![test("a", 10), test("c", 30)],
+        };
+
+        assert_eq!(history.merge([test("b", 20), test("c", 30)]), 1);
+        assert_eq!(history.tests, [test("a", 10), test("b", 20), test("c", 30)]);
+        assert_eq!(history.merge([test("a", 10)]), 0);
+    }
+}
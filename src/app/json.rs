@@ -0,0 +1,225 @@
+use std::{fmt::Write as _, iter::Peekable, str::Chars};
+use toml::{Table, Value};
+
+/// Writes `value` as JSON. Nothing typers writes as JSON holds what TOML
+/// can't, so it goes through TOML's values rather than a serializer of its
+/// own.
+pub fn write(json: &mut String, value: &Value) {
+    match value {
+        Value::String(text) => write_string(json, text),
+        Value::Integer(n) => {
+            let _ = write!(json, "{n}");
+        }
+        // Debug keeps the point, so whole numbers read back as floats.
+        Value::Float(n) if n.is_finite() => {
+            let _ = write!(json, "{n:?}");
+        }
+        Value::Float(_) => json.push_str("null"),
+        Value::Boolean(b) => {
+            let _ = write!(json, "{b}");
+        }
+        Value::Datetime(datetime) => write_string(json, &datetime.to_string()),
+        Value::Array(values) => {
+            json.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write(json, value);
+            }
+            json.push(']');
+        }
+        Value::Table(table) => {
+            json.push('{');
+            for (i, (key, value)) in table.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_string(json, key);
+                json.push(':');
+                write(json, value);
+            }
+            json.push('}');
+        }
+    }
+}
+
+/// Writes `text` as a JSON string, escaping what has to be.
+pub fn write_string(json: &mut String, text: &str) {
+    json.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", u32::from(c));
+            }
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+}
+
+/// Reads a line of JSON into the TOML value it stands for, leaving out
+/// nulls as TOML has none.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut chars = text.chars().peekable();
+    let value = parse_value(&mut chars)?;
+
+    skip_space(&mut chars);
+    match (value, chars.next()) {
+        (Some(value), None) => Ok(value),
+        (None, None) => Err("null where a test was expected".to_string()),
+        (_, Some(c)) => Err(format!("unexpected {c:?} after the value")),
+    }
+}
+
+fn parse_value(chars: &mut Peekable<Chars>) -> Result<Option<Value>, String> {
+    skip_space(chars);
+
+    Ok(Some(match chars.peek() {
+        Some('{') => {
+            chars.next();
+            let mut table = Table::new();
+
+            if !take(chars, '}') {
+                loop {
+                    skip_space(chars);
+                    expect(chars, '"')?;
+                    let key = parse_string(chars)?;
+                    skip_space(chars);
+                    expect(chars, ':')?;
+
+                    if let Some(value) = parse_value(chars)? {
+                        table.insert(key, value);
+                    }
+
+                    if !next_item(chars, '}')? {
+                        break;
+                    }
+                }
+            }
+
+            Value::Table(table)
+        }
+        Some('[') => {
+            chars.next();
+            let mut values = Vec::new();
+
+            if !take(chars, ']') {
+                loop {
+                    values.push(parse_value(chars)?.ok_or("null in an array")?);
+
+                    if !next_item(chars, ']')? {
+                        break;
+                    }
+                }
+            }
+
+            Value::Array(values)
+        }
+        Some('"') => {
+            chars.next();
+            Value::String(parse_string(chars)?)
+        }
+        Some('t' | 'f' | 'n') => {
+            let word: String =
+                std::iter::from_fn(|| chars.next_if(char::is_ascii_alphabetic)).collect();
+
+            match word.as_str() {
+                "true" => Value::Boolean(true),
+                "false" => Value::Boolean(false),
+                "null" => return Ok(None),
+                _ => return Err(format!("unexpected {word:?}")),
+            }
+        }
+        Some(_) => {
+            let number: String = std::iter::from_fn(|| {
+                chars.next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+            })
+            .collect();
+            let invalid = || format!("invalid number {number:?}");
+
+            if number.contains(['.', 'e', 'E']) {
+                Value::Float(number.parse().map_err(|_| invalid())?)
+            } else {
+                Value::Integer(number.parse().map_err(|_| invalid())?)
+            }
+        }
+        None => return Err("unexpected end of the line".to_string()),
+    }))
+}
+
+/// Reads the rest of a string, after its opening quote.
+fn parse_string(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    let mut text = String::new();
+
+    loop {
+        match chars.next().ok_or("unfinished string")? {
+            '"' => return Ok(text),
+            '\\' => text.push(match chars.next().ok_or("unfinished string")? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'u' => parse_escape(chars)?,
+                c => c,
+            }),
+            c => text.push(c),
+        }
+    }
+}
+
+/// Reads the character of a `\u` escape, joining a surrogate pair.
+fn parse_escape(chars: &mut Peekable<Chars>) -> Result<char, String> {
+    let hex = |chars: &mut Peekable<Chars>| {
+        let digits: String = chars.take(4).collect();
+        u32::from_str_radix(&digits, 16).map_err(|_| format!("invalid escape \\u{digits}"))
+    };
+
+    let high = hex(chars)?;
+    let code = if (0xd800..0xdc00).contains(&high) && take(chars, '\\') && take(chars, 'u') {
+        let low = hex(chars)?;
+        0x10000 + ((high - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff)
+    } else {
+        high
+    };
+
+    char::from_u32(code).ok_or_else(|| format!("invalid character {code:#x}"))
+}
+
+fn skip_space(chars: &mut Peekable<Chars>) {
+    while chars.next_if(char::is_ascii_whitespace).is_some() {}
+}
+
+/// Takes `c` if it's next, after any space.
+fn take(chars: &mut Peekable<Chars>, c: char) -> bool {
+    skip_space(chars);
+    chars.next_if_eq(&c).is_some()
+}
+
+fn expect(chars: &mut Peekable<Chars>, c: char) -> Result<(), String> {
+    if take(chars, c) {
+        Ok(())
+    } else {
+        Err(format!("expected {c:?}"))
+    }
+}
+
+/// Whether another item follows in an object or array, or it ends with
+/// `end`.
+fn next_item(chars: &mut Peekable<Chars>, end: char) -> Result<bool, String> {
+    if take(chars, ',') {
+        Ok(true)
+    } else if take(chars, end) {
+        Ok(false)
+    } else {
+        Err(format!("expected ',' or {end:?}"))
+    }
+}
@@ -0,0 +1,71 @@
+use crate::args::Hand;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Finger {
+    LeftPinky,
+    LeftRing,
+    LeftMiddle,
+    LeftIndex,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+}
+
+/// The keys each finger presses on a QWERTY keyboard, as touch typing
+/// teaches it. Shifted characters are typed with the same finger.
+const FINGERS: [(&str, Finger); 8] = [
+    ("`1qaz~!QAZ", Finger::LeftPinky),
+    ("2wsx@WSX", Finger::LeftRing),
+    ("3edc#EDC", Finger::LeftMiddle),
+    ("45rfvtgb$%RFVTGB", Finger::LeftIndex),
+    ("67yhnujm^&YHNUJM", Finger::RightIndex),
+    ("8ik,*IK<", Finger::RightMiddle),
+    ("9ol.(OL>", Finger::RightRing),
+    ("0p;/-[']=)P:?_{\"}+\\|", Finger::RightPinky),
+];
+
+impl Finger {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::LeftPinky => "left pinky",
+            Self::LeftRing => "left ring",
+            Self::LeftMiddle => "left middle",
+            Self::LeftIndex => "left index",
+            Self::RightIndex => "right index",
+            Self::RightMiddle => "right middle",
+            Self::RightRing => "right ring",
+            Self::RightPinky => "right pinky",
+        }
+    }
+
+    pub fn hand(self) -> Hand {
+        if self <= Self::LeftIndex {
+            Hand::Left
+        } else {
+            Hand::Right
+        }
+    }
+}
+
+/// The finger that types `c`, if it is on the keyboard at all.
+pub fn finger(c: char) -> Option<Finger> {
+    FINGERS
+        .iter()
+        .find(|(keys, _)| keys.contains(c))
+        .map(|(_, finger)| *finger)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_the_keyboard() {
+        assert_eq!(finger('f'), Some(Finger::LeftIndex));
+        assert_eq!(finger('J'), Some(Finger::RightIndex));
+        assert_eq!(finger('t').map(Finger::hand), Some(Hand::Left));
+        assert_eq!(finger('y').map(Finger::hand), Some(Hand::Right));
+        assert_eq!(finger(' '), None);
+    }
+}
@@ -0,0 +1,377 @@
+use crate::args::KeymapPreset;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// The screens keys are bound on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Menu,
+    Settings,
+    Preview,
+    Playing,
+    Finished,
+    Summary,
+    Resume,
+    Tutorial,
+    History,
+    Achievements,
+    Leaderboard,
+}
+
+impl Screen {
+    const ALL: [Self; 11] = [
+        Self::Menu,
+        Self::Settings,
+        Self::Preview,
+        Self::Playing,
+        Self::Finished,
+        Self::Summary,
+        Self::Resume,
+        Self::Tutorial,
+        Self::History,
+        Self::Achievements,
+        Self::Leaderboard,
+    ];
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Save,
+    Back,
+    Leave,
+    SkipPreview,
+    NewTest,
+    Backspace,
+    Retry,
+    MoreWords,
+    FewerWords,
+    Copy,
+    SaveWords,
+    ExportReplay,
+    ExportGhost,
+    ExportKeystrokes,
+    DrillFinger,
+    DrillMistakes,
+    PracticeWeakWords,
+    NextPreset,
+    Menu,
+    Help,
+    Command,
+    Resume,
+    Discard,
+    NextPage,
+    PreviousPage,
+    SkipTutorial,
+    Sort,
+    Compare,
+    Delete,
+    Note,
+    Quit,
+}
+
+impl Action {
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Up => "move up",
+            Self::Down => "move down",
+            Self::Left => "previous option",
+            Self::Right => "next option",
+            Self::Select => "select",
+            Self::Save => "save",
+            Self::Back => "back",
+            Self::Leave => "leave the test",
+            Self::SkipPreview => "start typing now",
+            Self::NewTest => "new test",
+            Self::Backspace => "delete the last character",
+            Self::Retry => "retry the same words",
+            Self::MoreWords => "new test with more words",
+            Self::FewerWords => "new test with fewer words",
+            Self::Copy => "copy results",
+            Self::SaveWords => "save the words to a file",
+            Self::ExportReplay => "export a replay page",
+            Self::ExportGhost => "save a ghost to race",
+            Self::ExportKeystrokes => "export every keystroke as CSV",
+            Self::DrillFinger => "drill the weakest finger",
+            Self::DrillMistakes => "drill the words typed wrong",
+            Self::PracticeWeakWords => "practice the slowest and most missed words",
+            Self::NextPreset => "new test at the next preset length",
+            Self::Menu => "back to menu",
+            Self::Help => "show keybindings",
+            Self::Command => "open the command line",
+            Self::Resume => "resume the test",
+            Self::Discard => "start fresh",
+            Self::NextPage => "next page",
+            Self::PreviousPage => "previous page",
+            Self::SkipTutorial => "skip to the menu",
+            Self::Sort => "sort by the next column",
+            Self::Compare => "compare with the marked test",
+            Self::Delete => "delete the test",
+            Self::Note => "write a note on the result",
+            Self::Quit => "quit",
+        }
+    }
+}
+
+/// A key as bound, with Ctrl being the only modifier that matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+}
+
+impl From<KeyCode> for Key {
+    fn from(code: KeyCode) -> Self {
+        Self { code, ctrl: false }
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self {
+            code: event.code,
+            ctrl: event.modifiers.contains(KeyModifiers::CONTROL),
+        }
+    }
+}
+
+/// Every key binding, looked up both when handling keys and when listing
+/// them, so the help can't disagree with what keys do.
+pub struct Keymap {
+    bindings: Vec<(Screen, Key, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(KeymapPreset::Default)
+    }
+}
+
+impl Keymap {
+    pub fn new(preset: KeymapPreset) -> Self {
+        use Action as A;
+        use KeyCode as K;
+        use Screen as S;
+
+        let mut bindings = Vec::new();
+
+        for screen in [S::Menu, S::Settings, S::History] {
+            bindings.extend([(screen, K::Up, A::Up), (screen, K::Down, A::Down)]);
+        }
+
+        for screen in [S::Menu, S::Settings] {
+            bindings.extend([(screen, K::Left, A::Left), (screen, K::Right, A::Right)]);
+        }
+
+        bindings.extend([
+            (S::Menu, K::Enter, A::Select),
+            (S::Menu, K::Esc, A::Quit),
+            (S::Settings, K::Enter, A::Save),
+            (S::Settings, K::Char('s'), A::Save),
+            (S::Settings, K::Esc, A::Back),
+            (S::Preview, K::Enter, A::SkipPreview),
+            (S::Preview, K::Tab, A::NewTest),
+            (S::Preview, K::Esc, A::Leave),
+            (S::Playing, K::Tab, A::NewTest),
+            (S::Playing, K::Backspace, A::Backspace),
+            (S::Playing, K::Esc, A::Leave),
+            (S::Finished, K::Char('r'), A::Retry),
+            (S::Finished, K::Tab, A::NewTest),
+            (S::Finished, K::Char('+'), A::MoreWords),
+            (S::Finished, K::Char('='), A::MoreWords),
+            (S::Finished, K::Char('-'), A::FewerWords),
+            (S::Finished, K::Char('c'), A::Copy),
+            (S::Finished, K::Char('w'), A::SaveWords),
+            (S::Finished, K::Char('e'), A::ExportReplay),
+            (S::Finished, K::Char('g'), A::ExportGhost),
+            (S::Finished, K::Char('k'), A::ExportKeystrokes),
+            (S::Finished, K::Char('f'), A::DrillFinger),
+            (S::Finished, K::Char('d'), A::DrillMistakes),
+            (S::Finished, K::Char('s'), A::PracticeWeakWords),
+            (S::Finished, K::Char('p'), A::NextPreset),
+            (S::Finished, K::Char('n'), A::Note),
+            (S::Finished, K::Char('m'), A::Menu),
+            (S::Finished, K::Char('q'), A::Quit),
+            (S::Finished, K::Enter, A::Quit),
+            (S::Summary, K::Char('m'), A::Menu),
+            (S::Summary, K::Char('q'), A::Quit),
+            (S::Summary, K::Enter, A::Quit),
+            (S::Summary, K::Esc, A::Quit),
+            (S::Resume, K::Char('y'), A::Resume),
+            (S::Resume, K::Enter, A::Resume),
+            (S::Resume, K::Char('n'), A::Discard),
+            (S::Resume, K::Esc, A::Discard),
+            (S::Tutorial, K::Enter, A::NextPage),
+            (S::Tutorial, K::Right, A::NextPage),
+            (S::Tutorial, K::Left, A::PreviousPage),
+            (S::Tutorial, K::Esc, A::SkipTutorial),
+            (S::History, K::Char('r'), A::Retry),
+            (S::History, K::Enter, A::Retry),
+            (S::History, K::Char('s'), A::Sort),
+            (S::History, K::Char('c'), A::Compare),
+            (S::History, K::Char('d'), A::Delete),
+            (S::History, K::Delete, A::Delete),
+            (S::History, K::Esc, A::Back),
+            (S::History, K::Char('q'), A::Quit),
+            (S::Achievements, K::Esc, A::Back),
+            (S::Achievements, K::Char('q'), A::Quit),
+            (S::Leaderboard, K::Esc, A::Back),
+            (S::Leaderboard, K::Char('q'), A::Quit),
+        ]);
+
+        // `?` and `:` would be typed during a test, so only F1 and Ctrl+P
+        // work there.
+        for screen in Screen::ALL {
+            bindings.push((screen, K::F(1), A::Help));
+
+            if screen != S::Playing {
+                bindings.push((screen, K::Char('?'), A::Help));
+                bindings.push((screen, K::Char(':'), A::Command));
+            }
+        }
+
+        if preset == KeymapPreset::Vim {
+            for screen in [S::Menu, S::Settings, S::History] {
+                bindings.extend([
+                    (screen, K::Char('k'), A::Up),
+                    (screen, K::Char('j'), A::Down),
+                ]);
+            }
+
+            for screen in [S::Menu, S::Settings] {
+                bindings.extend([
+                    (screen, K::Char('h'), A::Left),
+                    (screen, K::Char('l'), A::Right),
+                ]);
+            }
+        }
+
+        let mut bindings: Vec<_> = bindings
+            .into_iter()
+            .map(|(screen, code, action)| (screen, Key::from(code), action))
+            .collect();
+
+        // Raw mode turns Ctrl+C into a key press instead of an interrupt.
+        let ctrl = |c| Key {
+            code: K::Char(c),
+            ctrl: true,
+        };
+
+        for screen in Screen::ALL {
+            bindings.push((screen, ctrl('p'), A::Command));
+            bindings.push((screen, ctrl('c'), A::Quit));
+        }
+
+        Self { bindings }
+    }
+
+    pub fn action(&self, screen: Screen, key: Key) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(on, bound, _)| *on == screen && *bound == key)
+            .map(|(_, _, action)| *action)
+    }
+
+    /// The keys bound on `screen`, grouped by action in binding order.
+    pub fn bindings(&self, screen: Screen) -> Vec<(Vec<Key>, Action)> {
+        let mut grouped: Vec<(Vec<Key>, Action)> = Vec::new();
+
+        for (_, key, action) in self.bindings.iter().filter(|(on, ..)| *on == screen) {
+            match grouped.iter_mut().find(|(_, grouped)| grouped == action) {
+                Some((keys, _)) => keys.push(*key),
+                None => grouped.push((vec![*key], *action)),
+            }
+        }
+
+        grouped
+    }
+}
+
+pub fn key_name(key: Key) -> String {
+    let name = match key.code {
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::Char(' ') => "space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::F(n) => format!("F{n}"),
+        key => key.to_string().to_lowercase(),
+    };
+
+    if key.ctrl {
+        format!("ctrl+{name}")
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn question_mark_is_typeable() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action(Screen::Playing, KeyCode::Char('?').into()),
+            None
+        );
+        assert_eq!(
+            keymap.action(Screen::Finished, KeyCode::Char('?').into()),
+            Some(Action::Help)
+        );
+    }
+
+    #[test]
+    fn groups_keys_by_action() {
+        let bindings = Keymap::default().bindings(Screen::Finished);
+        let quit = bindings
+            .iter()
+            .find(|(_, action)| *action == Action::Quit)
+            .unwrap();
+
+        let ctrl_c = Key {
+            code: KeyCode::Char('c'),
+            ctrl: true,
+        };
+
+        assert_eq!(
+            quit.0,
+            [KeyCode::Char('q').into(), KeyCode::Enter.into(), ctrl_c]
+        );
+    }
+
+    #[test]
+    fn vim_adds_to_the_defaults() {
+        let keymap = Keymap::new(KeymapPreset::Vim);
+        let j = KeyCode::Char('j').into();
+        assert_eq!(keymap.action(Screen::Menu, j), Some(Action::Down));
+        assert_eq!(
+            keymap.action(Screen::Menu, KeyCode::Down.into()),
+            Some(Action::Down)
+        );
+        assert_eq!(Keymap::default().action(Screen::Menu, j), None);
+    }
+
+    #[test]
+    fn ctrl_is_part_of_the_key() {
+        let keymap = Keymap::default();
+        let ctrl_p = Key {
+            code: KeyCode::Char('p'),
+            ctrl: true,
+        };
+
+        assert_eq!(
+            keymap.action(Screen::Playing, ctrl_p),
+            Some(Action::Command)
+        );
+        assert_eq!(
+            keymap.action(Screen::Playing, KeyCode::Char('p').into()),
+            None
+        );
+    }
+}
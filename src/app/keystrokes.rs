@@ -0,0 +1,55 @@
+use std::{fmt::Write, time::Duration};
+
+/// One row per keystroke kept in the test: what was typed against what the
+/// text had there, when, and how long after the keystroke before it.
+pub fn csv(text: &str, typed: &[char], times: &[Duration]) -> String {
+    let mut csv = "index,char,expected,correct,timestamp_ms,latency_ms\n".to_string();
+    let mut expected = text.chars();
+    let mut last = Duration::ZERO;
+
+    for (i, (&c, &time)) in typed.iter().zip(times).enumerate() {
+        let want = expected.next();
+        let _ = writeln!(
+            csv,
+            "{i},{},{},{},{},{}",
+            field(Some(c)),
+            field(want),
+            want == Some(c),
+            time.as_millis(),
+            time.saturating_sub(last).as_millis()
+        );
+        last = time;
+    }
+
+    csv
+}
+
+/// A character as a CSV field, quoted when it would otherwise split the
+/// row.
+fn field(c: Option<char>) -> String {
+    match c {
+        Some(c @ (',' | '"')) => format!("\"{}\"", c.to_string().replace('"', "\"\"")),
+        Some(c) => c.to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_a_row_per_keystroke() {
+        let times = [300, 450, 700, 800].map(Duration::from_millis);
+        let csv = csv("a,\"", &['a', ',', 'x', 'b'], &times);
+
+        assert_eq!(
+            csv,
+            "index,char,expected,correct,timestamp_ms,latency_ms\n\
+             0,a,a,true,300,300\n\
+             1,\",\",\",\",true,450,150\n\
+             2,x,\"\"\"\",false,700,250\n\
+             3,b,,false,800,100\n"
+        );
+    }
+}
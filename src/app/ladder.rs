@@ -0,0 +1,43 @@
+use super::menu::step_words;
+use crate::paths::Paths;
+use std::{fs, io, path::PathBuf};
+
+/// Holds the word count the ladder has climbed to.
+fn path() -> io::Result<PathBuf> {
+    Ok(Paths::new()?.data.join("ladder"))
+}
+
+pub fn load() -> Option<usize> {
+    path()
+        .and_then(fs::read_to_string)
+        .ok()
+        .and_then(|words| words.trim().parse().ok())
+}
+
+pub fn save(words: usize) -> io::Result<()> {
+    let path = path()?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, words.to_string())
+}
+
+/// One rung up from `words` after a test at least `threshold` percent
+/// accurate, one down otherwise.
+pub fn next(words: usize, accuracy: f64, threshold: u8) -> usize {
+    step_words(words, accuracy >= f64::from(threshold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn climbs_on_accuracy() {
+        assert_eq!(next(24, 97.0, 95), 50);
+        assert_eq!(next(24, 90.0, 95), 10);
+        assert_eq!(next(200, 100.0, 95), 200);
+    }
+}
@@ -0,0 +1,197 @@
+use super::{
+    history::History,
+    screen::{Context, Screen},
+    strings::{text, Text},
+};
+use crate::paths::Paths;
+use ratatui::{
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+    Frame,
+};
+use std::collections::HashMap;
+
+/// Modes shown, the ones with the most tests across the profiles.
+const MODES: usize = 4;
+
+/// Profiles ranked in each mode.
+const PLACES: usize = 3;
+
+/// The best speed of each profile on this machine in the modes they've
+/// played most, to see who's fastest at what.
+#[derive(Default)]
+pub struct Leaderboard {
+    /// Each mode with the profiles in it, fastest first.
+    modes: Vec<(String, Vec<(String, f64)>)>,
+    /// The profile in use, to pick out among the others.
+    current: String,
+}
+
+impl Leaderboard {
+    /// Reads the history of every profile, leaving out any that can't be.
+    pub fn load(current: Option<&str>) -> Self {
+        let profiles: Vec<Option<String>> = [None]
+            .into_iter()
+            .chain(Paths::profiles().into_iter().map(Some))
+            .collect();
+
+        let histories: Vec<(String, History)> = profiles
+            .iter()
+            .filter_map(|profile| {
+                let history = History::load_profile(profile.as_deref()).ok()?;
+                Some((name(profile.as_deref()), history))
+            })
+            .collect();
+
+        Self::new(&histories, &name(current))
+    }
+
+    /// Ranks the valid tests of each of `histories`, by profile name, as
+    /// seen from the `current` one. Daily challenges all count as one mode,
+    /// though the words change each day.
+    pub fn new(histories: &[(String, History)], current: &str) -> Self {
+        // How many tests each mode has, and the best of each profile in it.
+        let mut modes: HashMap<String, (usize, HashMap<&str, f64>)> = HashMap::new();
+
+        for (profile, history) in histories {
+            for test in history.tests.iter().filter(|test| test.flags.is_empty()) {
+                let mode = if test.daily.is_some() {
+                    "daily challenge"
+                } else {
+                    &test.mode
+                };
+                let (count, bests) = modes.entry(mode.to_string()).or_default();
+                let best = bests.entry(profile).or_default();
+
+                *count += 1;
+                *best = best.max(test.wpm);
+            }
+        }
+
+        let mut modes: Vec<_> = modes.into_iter().collect();
+        modes.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then_with(|| a.0.cmp(&b.0)));
+
+        let modes = modes
+            .into_iter()
+            .take(MODES)
+            .map(|(mode, (_, bests))| {
+                let mut bests: Vec<(String, f64)> = bests
+                    .into_iter()
+                    .map(|(profile, wpm)| (profile.to_string(), wpm))
+                    .collect();
+                bests.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+                bests.truncate(PLACES);
+                (mode, bests)
+            })
+            .collect();
+
+        Self {
+            modes,
+            current: current.to_string(),
+        }
+    }
+}
+
+impl Screen for Leaderboard {
+    type Choice = ();
+
+    const WIDTH: u16 = 50;
+    #[expect(clippy::cast_possible_truncation)]
+    const HEIGHT: u16 = (MODES * (PLACES + 2)) as u16 + 3;
+
+    fn render(&self, frame: &mut Frame, area: Rect, context: &Context) {
+        let theme = context.theme;
+        let block = Block::bordered()
+            .title(Span::styled("leaderboard · best wpm", theme.accent))
+            .title_bottom(Span::styled(text(Text::EscBack), theme.muted))
+            .border_style(theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::horizontal(1));
+
+        let mut lines = Vec::new();
+
+        for (mode, bests) in &self.modes {
+            lines.push(Line::default());
+            lines.push(Line::styled(mode.as_str(), theme.accent));
+
+            for (place, (profile, wpm)) in bests.iter().enumerate() {
+                let style = if *profile == self.current {
+                    theme.selected
+                } else {
+                    theme.correct
+                };
+
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}. {profile:<24}", place + 1), style),
+                    Span::styled(format!("{wpm:>5.0}"), theme.muted),
+                ]));
+            }
+        }
+
+        if self.modes.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::styled("no tests in any profile yet", theme.muted));
+        }
+
+        Paragraph::new(lines)
+            .block(block)
+            .render(area, frame.buffer_mut());
+    }
+}
+
+fn name(profile: Option<&str>) -> String {
+    profile.unwrap_or("default").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::history::Record;
+
+    #[test]
+    fn ranks_profiles_in_each_mode() {
+        let test = |mode: &str, wpm| Record {
+            mode: mode.to_string(),
+            wpm,
+            ..Record::default()
+        };
+        let daily = Record {
+            daily: Some("2026-10-14".to_string()),
+            ..test("daily 2026-10-14", 90.0)
+        };
+        let pasted = Record {
+            flags: vec!["pasted".to_string()],
+            ..test("words 24 · english", 300.0)
+        };
+        let histories = [
+            (
+                "ada".to_string(),
+                History {
+                    tests: vec![
+                        test("words 24 · english", 70.0),
+                        test("words 24 · english", 80.0),
+                        pasted,
+                    ],
+                },
+            ),
+            (
+                "bo".to_string(),
+                History {
+                    tests: vec![test("words 24 · english", 75.0), daily],
+                },
+            ),
+        ];
+
+        let board = Leaderboard::new(&histories, "bo");
+        assert_eq!(
+            board.modes[0],
+            (
+                "words 24 · english".to_string(),
+                vec![("ada".to_string(), 80.0), ("bo".to_string(), 75.0)]
+            )
+        );
+        assert_eq!(board.modes[1].0, "daily challenge");
+        assert_eq!(board.modes.len(), 2);
+    }
+}
@@ -0,0 +1,48 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// How often buffered lines reach the file.
+const FLUSH_EVERY: Duration = Duration::from_secs(1);
+
+/// Timestamped `key=value` lines of what happened, for `--log-file`. Lines
+/// are buffered and flushed at most once per [`FLUSH_EVERY`], so logging
+/// stays off the path between a keystroke and its frame.
+pub struct Log {
+    file: BufWriter<File>,
+    start: Instant,
+    flushed: Instant,
+}
+
+impl Log {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let now = Instant::now();
+
+        Ok(Self {
+            file: BufWriter::new(File::create(path)?),
+            start: now,
+            flushed: now,
+        })
+    }
+
+    /// Logging is best effort, a full disk shouldn't end the test.
+    pub fn write(&mut self, kind: &str, message: fmt::Arguments) {
+        let ms = self.start.elapsed().as_millis();
+        let _ = writeln!(self.file, "{ms:>8} {kind:<6} {message}");
+
+        if self.flushed.elapsed() >= FLUSH_EVERY {
+            let _ = self.file.flush();
+            self.flushed = Instant::now();
+        }
+    }
+}
+
+impl Drop for Log {
+    fn drop(&mut self) {
+        let _ = self.file.flush();
+    }
+}
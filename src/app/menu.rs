@@ -0,0 +1,228 @@
+use super::{
+    keymap::Action,
+    screen::{Context, Screen},
+    strings::{text, Text},
+};
+use crate::{args::Funbox, paths::Paths, Args};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+    Frame,
+};
+
+pub const WORDS: [usize; 5] = [10, 24, 50, 100, 200];
+const MEMORY: [Option<u64>; 4] = [None, Some(3), Some(5), Some(10)];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Item {
+    Words,
+    Display,
+    Memory,
+    RandomCase,
+    LiveStats,
+    Profile,
+    Start,
+    History,
+    Achievements,
+    Leaderboard,
+    Settings,
+}
+
+impl Item {
+    const ALL: [Self; 11] = [
+        Self::Words,
+        Self::Display,
+        Self::Memory,
+        Self::RandomCase,
+        Self::LiveStats,
+        Self::Profile,
+        Self::Start,
+        Self::History,
+        Self::Achievements,
+        Self::Leaderboard,
+        Self::Settings,
+    ];
+
+    /// Items after the options, set apart as actions.
+    const ACTIONS: usize = 5;
+
+    fn label(self) -> &'static str {
+        text(match self {
+            Self::Words => Text::Words,
+            Self::Display => Text::Display,
+            Self::Memory => Text::Memory,
+            Self::RandomCase => Text::RandomCase,
+            Self::LiveStats => Text::LiveStats,
+            Self::Profile => Text::Profile,
+            Self::Start => Text::Start,
+            Self::History => Text::History,
+            Self::Achievements => Text::Achievements,
+            Self::Leaderboard => Text::Leaderboard,
+            Self::Settings => Text::Settings,
+        })
+    }
+
+    fn value(self, args: &Args) -> Option<String> {
+        let toggle = |on: bool| if on { "on" } else { "off" }.to_string();
+
+        match self {
+            Self::Words => Some(args.words.to_string()),
+            Self::Display => Some(if args.tape { "tape" } else { "paragraph" }.to_string()),
+            Self::Memory => Some(
+                args.memory
+                    .map_or("off".to_string(), |secs| format!("{secs}s")),
+            ),
+            Self::RandomCase => Some(toggle(args.funbox.contains(&Funbox::RandomCase))),
+            Self::LiveStats => Some(toggle(args.live_stats)),
+            Self::Profile => Some(args.profile.clone().unwrap_or("default".to_string())),
+            Self::Start
+            | Self::History
+            | Self::Achievements
+            | Self::Leaderboard
+            | Self::Settings => None,
+        }
+    }
+
+    /// Moves the item's option one step forwards or backwards, wrapping
+    /// around at either end.
+    fn step(self, args: &mut Args, forward: bool) {
+        match self {
+            Self::Words => args.words = cycle(&WORDS, &args.words, forward),
+            Self::Display => args.tape = !args.tape,
+            Self::Memory => args.memory = cycle(&MEMORY, &args.memory, forward),
+            Self::RandomCase => args.toggle_funbox(Funbox::RandomCase),
+            Self::LiveStats => args.live_stats = !args.live_stats,
+            Self::Profile => {
+                let mut profiles = vec![None];
+                profiles.extend(Paths::profiles().into_iter().map(Some));
+                args.profile = cycle(&profiles, &args.profile, forward);
+            }
+            Self::Start
+            | Self::History
+            | Self::Achievements
+            | Self::Leaderboard
+            | Self::Settings => {}
+        }
+    }
+}
+
+/// The option after (or before) `current`, or the first one when `current`
+/// isn't among them.
+pub fn cycle<T: Clone + PartialEq>(options: &[T], current: &T, forward: bool) -> T {
+    let Some(at) = options.iter().position(|option| option == current) else {
+        return options[0].clone();
+    };
+
+    let next = if forward {
+        (at + 1) % options.len()
+    } else {
+        (at + options.len() - 1) % options.len()
+    };
+
+    options[next].clone()
+}
+
+/// The next word count preset above (or below) `current`, staying put at
+/// either end.
+pub fn step_words(current: usize, bigger: bool) -> usize {
+    let next = if bigger {
+        WORDS.iter().find(|n| **n > current)
+    } else {
+        WORDS.iter().rev().find(|n| **n < current)
+    };
+
+    next.copied().unwrap_or(current)
+}
+
+pub enum Choice {
+    Start,
+    History,
+    Achievements,
+    Leaderboard,
+    Settings,
+    /// Another profile was picked, to load its settings.
+    Profile,
+}
+
+#[derive(Default)]
+pub struct Menu {
+    selected: usize,
+}
+
+impl Screen for Menu {
+    type Choice = Choice;
+
+    const WIDTH: u16 = 40;
+    #[expect(clippy::cast_possible_truncation)]
+    const HEIGHT: u16 = Item::ALL.len() as u16 + 5;
+
+    /// Applies `action` to the options in `args`, returning what to do next
+    /// if something was picked.
+    fn handle(&mut self, action: Action, args: &mut Args) -> Option<Choice> {
+        let item = Item::ALL[self.selected];
+
+        match action {
+            Action::Up => self.selected = self.selected.saturating_sub(1),
+            Action::Down => self.selected = (self.selected + 1).min(Item::ALL.len() - 1),
+            Action::Left | Action::Right if item == Item::Profile => {
+                item.step(args, action == Action::Right);
+                return Some(Choice::Profile);
+            }
+            Action::Left => item.step(args, false),
+            Action::Right => item.step(args, true),
+            Action::Select if item == Item::History => return Some(Choice::History),
+            Action::Select if item == Item::Achievements => return Some(Choice::Achievements),
+            Action::Select if item == Item::Leaderboard => return Some(Choice::Leaderboard),
+            Action::Select if item == Item::Settings => return Some(Choice::Settings),
+            Action::Select => return Some(Choice::Start),
+            _ => {}
+        }
+
+        None
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    fn render(&self, frame: &mut Frame, area: Rect, context: &Context) {
+        let (args, theme) = (context.args, context.theme);
+        let block = Block::bordered()
+            .title(Span::styled("typers", theme.accent))
+            .border_style(theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        let inner = block.inner(area);
+        block.render(area, frame.buffer_mut());
+
+        let [options, _, actions] = Layout::vertical([
+            Constraint::Length((Item::ALL.len() - Item::ACTIONS) as u16),
+            Constraint::Length(1),
+            Constraint::Length(Item::ACTIONS as u16),
+        ])
+        .areas(inner);
+
+        let lines: Vec<Line> = Item::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let selected = i == self.selected;
+                let marker = if selected { "› " } else { "  " };
+
+                let line = match item.value(args) {
+                    Some(value) => Line::raw(format!("{marker}{:<14}‹ {value} ›", item.label())),
+                    None => Line::raw(format!("{marker}{}", item.label())),
+                };
+
+                if selected {
+                    line.style(theme.selected)
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        let (lines, action_lines) = lines.split_at(Item::ALL.len() - Item::ACTIONS);
+        Paragraph::new(lines.to_vec()).render(options, frame.buffer_mut());
+        Paragraph::new(action_lines.to_vec()).render(actions, frame.buffer_mut());
+    }
+}
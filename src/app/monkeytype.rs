@@ -0,0 +1,131 @@
+use super::history::Record;
+use std::{collections::HashMap, io};
+
+/// The tests in a results export from Monkeytype's account page, as records
+/// for the history. Columns are found by their names in the header, so ones
+/// added or moved around in later exports don't matter.
+pub fn parse(text: &str) -> io::Result<Vec<Record>> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().map(fields).unwrap_or_default();
+    let column: HashMap<&str, usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.trim(), i))
+        .collect();
+
+    for name in ["wpm", "acc", "timestamp"] {
+        if !column.contains_key(name) {
+            return Err(invalid(format!("no {name} column in the export")));
+        }
+    }
+
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let row = fields(line);
+            let field = |name| {
+                column
+                    .get(name)
+                    .and_then(|&at| row.get(at))
+                    .map(String::as_str)
+            };
+            let number = |name| field(name).and_then(|value| value.trim().parse::<f64>().ok());
+            let bad = || invalid(format!("invalid result on line {} of the export", i + 2));
+
+            let at = field("timestamp")
+                .and_then(|ms| ms.trim().parse::<u64>().ok())
+                .ok_or_else(bad)?;
+            let mode = [field("mode"), field("mode2")]
+                .into_iter()
+                .flatten()
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            Ok(Record {
+                // Kept, so importing the same export again adds nothing.
+                id: field("_id")
+                    .filter(|id| !id.is_empty())
+                    .map_or_else(|| Record::new().id, str::to_string),
+                at: at / 1000,
+                mode: format!("monkeytype · {mode}"),
+                wpm: number("wpm").ok_or_else(bad)?,
+                accuracy: number("acc").ok_or_else(bad)?,
+                consistency: number("consistency").unwrap_or_default(),
+                time: number("testDuration").unwrap_or_default(),
+                tags: field("tags")
+                    .unwrap_or_default()
+                    .split(';')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                ..Record::default()
+            })
+        })
+        .collect()
+}
+
+/// The comma separated fields of `line`, which are quoted when they hold a
+/// comma, with quotes in them doubled.
+fn fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    fields.push(field);
+    fields
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_results_by_column_name() {
+        let export = "_id,isPb,wpm,acc,rawWpm,consistency,mode,mode2,testDuration,tags,timestamp\n\
+                      6571,true,88.4,97.5,90,81.2,time,30,30,\"a;b\",1700000000000\n\
+                      6572,false,70,95,72,79,words,25,21.5,,1700000100000\n";
+
+        let records = parse(export).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, "6571");
+        assert_eq!(records[0].at, 1_700_000_000);
+        assert_eq!(records[0].mode, "monkeytype · time 30");
+        assert_eq!((records[0].wpm, records[0].accuracy), (88.4, 97.5));
+        assert_eq!(records[0].tags, ["a", "b"]);
+        assert!(records[1].tags.is_empty());
+        assert_eq!(
+            (records[1].mode.as_str(), records[1].time),
+            ("monkeytype · words 25", 21.5)
+        );
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(parse("name,score\nada,3\n").is_err());
+        assert!(parse("wpm,acc,timestamp\nfast,97,1\n").is_err());
+    }
+
+    #[test]
+    fn splits_quoted_fields() {
+        assert_eq!(fields(r#"a,"b,c","d""e",,"#), ["a", "b,c", "d\"e", "", ""]);
+    }
+}
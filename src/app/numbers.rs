@@ -0,0 +1,35 @@
+use rand::Rng;
+
+/// Picks `n` groups for ten-key practice: plain digit runs, amounts with
+/// cents and phone-number-like pairs.
+pub fn generate(n: usize, rng: &mut impl Rng) -> Vec<String> {
+    (0..n)
+        .map(|_| match rng.gen_range(0..3) {
+            0 => digits(rng.gen_range(2..=6), rng),
+            1 => format!("{}.{}", digits(rng.gen_range(1..=4), rng), digits(2, rng)),
+            _ => format!("{}-{}", digits(3, rng), digits(4, rng)),
+        })
+        .collect()
+}
+
+fn digits(len: usize, rng: &mut impl Rng) -> String {
+    (0..len)
+        .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn only_numbers() {
+        let groups = generate(100, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(groups.len(), 100);
+        assert!(groups.iter().all(|group| group
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == '-')));
+    }
+}
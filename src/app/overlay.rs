@@ -0,0 +1,55 @@
+use super::template;
+use std::{ffi::OsString, fs, io, path::Path, time::Duration};
+
+/// How often the file is brought up to date during a test.
+pub const STEP: Duration = Duration::from_millis(500);
+
+/// The `--overlay` file's contents from `values`: JSON for a path ending in
+/// `.json`, or else `text` as a line for tools that show files as they are.
+pub fn contents(path: &Path, values: &[(&str, String)], text: &str) -> String {
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        format!("{}\n", template::json(values))
+    } else {
+        format!("{text}\n")
+    }
+}
+
+/// Replaces `path` with `contents` through a file beside it, so whatever
+/// reads it never sees half a write.
+pub fn write(path: &Path, contents: &str) -> io::Result<()> {
+    let mut temporary = OsString::from(path.as_os_str());
+    temporary.push(".tmp");
+
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_json_or_text() {
+        let values = [("state", "typing".to_string()), ("wpm", "72".to_string())];
+        assert_eq!(
+            contents(Path::new("stats.json"), &values, "72 wpm"),
+            "{\"state\":\"typing\",\"wpm\":72}\n"
+        );
+        assert_eq!(
+            contents(Path::new("stats.txt"), &values, "72 wpm"),
+            "72 wpm\n"
+        );
+
+        let path = std::env::temp_dir().join("typers-overlay-test.txt");
+        write(&path, "72 wpm\n").unwrap();
+        write(&path, "80 wpm\n").unwrap();
+        let written = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(written.unwrap(), "80 wpm\n");
+        assert!(!path.with_extension("txt.tmp").exists());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{args::Funbox, paths::Paths, Args};
+use clap::{parser::ValueSource, ArgMatches};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+/// The options last picked in the menu, kept in the data directory so the
+/// menu opens on them next time.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Picks {
+    words: usize,
+    tape: bool,
+    memory: Option<u64>,
+    random_case: bool,
+    live_stats: bool,
+}
+
+impl Picks {
+    pub fn new(args: &Args) -> Self {
+        Self {
+            words: args.words,
+            tape: args.tape,
+            memory: args.memory,
+            random_case: args.funbox.contains(&Funbox::RandomCase),
+            live_stats: args.live_stats,
+        }
+    }
+
+    fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.data.join("picks.toml"))
+    }
+
+    pub fn load() -> Option<Self> {
+        let text = fs::read_to_string(Self::path().ok()?).ok()?;
+        toml::from_str(&text).ok()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(self).map_err(io::Error::other)?)
+    }
+
+    /// Picks up where the menu was left, for every option that wasn't given
+    /// on the command line.
+    pub fn apply(&self, args: &mut Args, matches: &ArgMatches) {
+        let unset = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+
+        if unset("words") {
+            args.words = self.words;
+        }
+
+        if unset("tape") {
+            args.tape = self.tape;
+        }
+
+        if unset("memory") {
+            args.memory = self.memory;
+        }
+
+        if unset("funbox") && self.random_case != args.funbox.contains(&Funbox::RandomCase) {
+            args.toggle_funbox(Funbox::RandomCase);
+        }
+
+        if unset("live_stats") {
+            args.live_stats = self.live_stats;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches, Parser};
+
+    #[test]
+    fn leaves_flags_given() {
+        let picked = Args::parse_from(["typers", "-w", "100", "--tape", "--funbox", "random-case"]);
+        let picks = Picks::new(&picked);
+
+        let matches = Args::command().get_matches_from(["typers", "--menu", "-w", "10"]);
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        picks.apply(&mut args, &matches);
+
+        assert_eq!(args.words, 10);
+        assert!(args.tape);
+        assert_eq!(args.funbox, [Funbox::RandomCase]);
+        assert!(!args.live_stats);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{config::Step, Args};
+
+/// A named list of steps from the config being worked through, one test
+/// at a time.
+pub struct Playlist {
+    pub name: String,
+    tests: Vec<Step>,
+    at: usize,
+    /// The options from the command line and config, for whatever a step
+    /// leaves out.
+    base: Step,
+}
+
+impl Playlist {
+    pub fn new(name: String, steps: &[Step], args: &Args) -> Self {
+        let tests = steps
+            .iter()
+            .flat_map(|step| std::iter::repeat_n(step, step.tests.get()))
+            .cloned()
+            .collect();
+
+        let base = Step {
+            mode: Some(args.mode),
+            words: Some(args.words),
+            max_time: args.max_time,
+            ..Step::default()
+        };
+
+        Self {
+            name,
+            tests,
+            at: 0,
+            base,
+        }
+    }
+
+    /// Sets `args` up for the test being typed.
+    pub fn apply(&self, args: &mut Args) {
+        let Some(step) = self.current() else {
+            return;
+        };
+
+        args.mode = step.mode.or(self.base.mode).unwrap_or(args.mode);
+        args.words = step.words.or(self.base.words).unwrap_or(args.words);
+        args.max_time = step.max_time.or(self.base.max_time);
+        args.warmup = step.warmup;
+    }
+
+    /// The step the test being typed is from.
+    pub fn current(&self) -> Option<&Step> {
+        self.tests.get(self.at)
+    }
+
+    /// Moves on to the next test, if there is one.
+    pub fn advance(&mut self) -> Option<&Step> {
+        self.at = (self.at + 1).min(self.tests.len());
+        self.current()
+    }
+
+    /// The test being typed, counting from 1, and how many there are.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.at + 1, self.tests.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Mode;
+    use clap::Parser;
+
+    #[test]
+    fn steps_through_every_test() {
+        let steps: Vec<Step> = toml::from_str::<toml::Table>(
+            "steps = [{ warmup = true }, { tests = 2, max-time = 60 }, { mode = \"symbols\" }]",
+        )
+        .unwrap()["steps"]
+            .clone()
+            .try_into()
+            .unwrap();
+        let mut args = Args::parse_from(["typers", "--words", "50"]);
+        let mut playlist = Playlist::new("morning".to_string(), &steps, &args);
+
+        assert_eq!(playlist.progress(), (1, 4));
+        assert!(playlist.current().unwrap().warmup);
+        assert_eq!(playlist.advance().unwrap().max_time, Some(60));
+        assert_eq!(playlist.advance().unwrap().max_time, Some(60));
+        assert_eq!(playlist.advance().unwrap().mode, Some(Mode::Symbols));
+
+        playlist.apply(&mut args);
+        assert_eq!(
+            (args.mode, args.words, args.max_time),
+            (Mode::Symbols, 50, None)
+        );
+        assert_eq!(playlist.advance(), None);
+        assert_eq!(playlist.advance(), None);
+    }
+}
@@ -0,0 +1,109 @@
+/// Encodes `pixels`, rows of RGB from the top, as a PNG `width` wide. The
+/// image data goes in uncompressed deflate blocks, which need nothing more
+/// than checksums to write.
+#[expect(clippy::cast_possible_truncation)]
+pub fn encode(width: usize, pixels: &[[u8; 3]]) -> Vec<u8> {
+    let height = pixels.len() / width.max(1);
+
+    // Every row starts with the filter it was encoded with, none here.
+    let mut raw = Vec::with_capacity(pixels.len() * 3 + height);
+    for row in pixels.chunks(width.max(1)) {
+        raw.push(0);
+        raw.extend(row.iter().flatten());
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // Eight bits for each of red, green and blue.
+    header.extend([8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    chunk(&mut png, *b"IHDR", &header);
+    chunk(&mut png, *b"IDAT", &zlib(&raw));
+    chunk(&mut png, *b"IEND", &[]);
+    png
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn chunk(png: &mut Vec<u8>, kind: [u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(kind.iter().chain(data)).to_be_bytes());
+}
+
+/// `data` as a zlib stream of stored blocks, each at most 65535 bytes.
+#[expect(clippy::cast_possible_truncation)]
+fn zlib(data: &[u8]) -> Vec<u8> {
+    const BLOCK: usize = 65_535;
+
+    let mut stream = vec![0x78, 0x01];
+    let blocks = data.len().div_ceil(BLOCK).max(1);
+
+    for i in 0..blocks {
+        let block = &data[i * BLOCK..data.len().min((i + 1) * BLOCK)];
+        let len = block.len() as u16;
+
+        stream.push(u8::from(i + 1 == blocks));
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(block);
+    }
+
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32<'a>(bytes: impl Iterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65_521;
+
+    let (mut a, mut b) = (1, 0);
+
+    for byte in data {
+        a = (a + u32::from(*byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(crc32(b"IEND".iter()), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+
+    #[test]
+    fn splits_long_data_into_blocks() {
+        let data = vec![7; 70_000];
+        let stream = zlib(&data);
+
+        // Two block headers of five bytes, the zlib header and checksum.
+        assert_eq!(stream.len(), data.len() + 2 * 5 + 2 + 4);
+        assert_eq!(stream[2], 0);
+        assert_eq!(stream[2 + 5 + 65_535], 1);
+    }
+}
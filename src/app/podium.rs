@@ -0,0 +1,97 @@
+use std::{cmp::Ordering, fmt::Write as _, time::Duration};
+
+/// How long the steps take to rise.
+pub const RISE: Duration = Duration::from_millis(600);
+
+/// Rows of the step for each place, first to third.
+const HEIGHTS: [usize; 3] = [3, 2, 1];
+
+/// Left to right, the places stand second, first, third.
+const ORDER: [usize; 3] = [1, 0, 2];
+
+/// The podium for `places`, by name and speed in the order they finished,
+/// with its steps `rise` of the way up, from 0 to 1. There are always as
+/// many rows, so nothing around it moves while it rises.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+pub fn render(places: &[(&str, f64)], rise: f64) -> Vec<String> {
+    let places = &places[..places.len().min(HEIGHTS.len())];
+    let labels: Vec<String> = places
+        .iter()
+        .enumerate()
+        .map(|(place, (_, wpm))| format!("{} {wpm:.0}", ordinal(place + 1)))
+        .collect();
+    let width = places
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .chain(labels.iter().map(String::len))
+        .max()
+        .unwrap_or_default()
+        + 2;
+
+    let shown = |place: usize| (HEIGHTS[place] as f64 * rise.clamp(0.0, 1.0)).ceil() as usize;
+    let top = HEIGHTS[0];
+    let columns: Vec<usize> = ORDER.into_iter().filter(|i| *i < places.len()).collect();
+
+    let mut rows = vec![String::new(); top + 2];
+
+    for &place in &columns {
+        let height = shown(place);
+
+        for (row, text) in rows.iter_mut().enumerate() {
+            let cell = match row.cmp(&(top - height)) {
+                _ if row > top => labels[place].clone(),
+                Ordering::Less => String::new(),
+                Ordering::Equal => places[place].0.to_string(),
+                Ordering::Greater => "█".repeat(width - 2),
+            };
+            let _ = write!(text, "{cell:^width$}");
+        }
+    }
+
+    for row in &mut rows {
+        row.truncate(row.trim_end().len());
+    }
+
+    rows
+}
+
+fn ordinal(n: usize) -> String {
+    let suffix = match n {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+
+    format!("{n}{suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raises_the_winner_highest() {
+        let places = [("you", 72.4), ("ada", 65.0)];
+
+        assert_eq!(
+            render(&places, 1.0),
+            [
+                "          you",
+                "  ada    ██████",
+                " ██████  ██████",
+                " ██████  ██████",
+                " 2nd 65  1st 72",
+            ]
+        );
+
+        let rising = render(&places, 0.4);
+        assert_eq!(rising.len(), 5);
+        assert_eq!(rising[2], "  ada    ██████");
+        assert_eq!(render(&places, 0.0)[3], "  ada     you");
+    }
+}
@@ -0,0 +1,83 @@
+use std::{fmt::Write, time::Duration};
+
+/// A standalone page that plays the test back in a browser, coloring the
+/// text the way the terminal did as each keystroke lands.
+pub fn html(text: &str, typed: &[char], times: &[Duration]) -> String {
+    // Characters go in as code points so nothing in the text needs escaping.
+    let codes = |chars: &mut dyn Iterator<Item = char>| {
+        chars.fold(String::new(), |mut list, c| {
+            let _ = write!(list, "{},", u32::from(c));
+            list
+        })
+    };
+
+    let times = times.iter().fold(String::new(), |mut list, time| {
+        let _ = write!(list, "{},", time.as_millis());
+        list
+    });
+
+    TEMPLATE
+        .replace("{text}", &codes(&mut text.chars()))
+        .replace("{typed}", &codes(&mut typed.iter().copied()))
+        .replace("{times}", &times)
+}
+
+const TEMPLATE: &str = r#"<!doctype html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>typers replay</title>
+<style>
+body { background: #111; color: #666; font: 24px monospace; max-width: 60ch; margin: 4em auto; line-height: 1.6; }
+.correct { color: #eee; }
+.incorrect { color: #e55; }
+.caret { border-left: 2px solid #eb3; margin-left: -2px; }
+button { font: inherit; margin-top: 2em; }
+</style>
+</head>
+<body>
+<div id="text"></div>
+<button id="play">replay</button>
+<script>
+const text = [{text}].map(c => String.fromCodePoint(c));
+const typed = [{typed}].map(c => String.fromCodePoint(c));
+const times = [{times}];
+const spans = text.map(c => {
+  const span = document.createElement("span");
+  span.textContent = c;
+  document.getElementById("text").appendChild(span);
+  return span;
+});
+let timers = [];
+function show(n) {
+  spans.forEach((span, i) => {
+    span.className = i < n ? (typed[i] === text[i] ? "correct" : "incorrect") : "";
+  });
+  if (spans[n]) spans[n].classList.add("caret");
+}
+function play() {
+  timers.forEach(clearTimeout);
+  show(0);
+  timers = times.map((time, i) => setTimeout(() => show(i + 1), time));
+}
+document.getElementById("play").onclick = play;
+play();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embeds_the_keystrokes() {
+        let times = [Duration::from_millis(0), Duration::from_millis(120)];
+        let page = html("a<", &['a', 'b'], &times);
+
+        assert!(page.contains("const text = [97,60,]"));
+        assert!(page.contains("const typed = [97,98,]"));
+        assert!(page.contains("const times = [0,120,]"));
+    }
+}
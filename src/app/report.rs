@@ -0,0 +1,146 @@
+use super::{
+    date::Date,
+    history::{History, Record},
+};
+use std::{collections::BTreeSet, fmt::Write as _};
+
+/// The tests of the `days` days up to `today`, counted from the Unix epoch,
+/// against the same number of days before, for `typers report`.
+#[expect(clippy::cast_possible_wrap, clippy::cast_precision_loss)]
+pub fn render(history: &History, days: i64, today: i64) -> String {
+    let first = today - days + 1;
+    let within = |from: i64, to: i64| -> Vec<&Record> {
+        history
+            .tests
+            .iter()
+            .filter(|test| (from..=to).contains(&((test.at / 86_400) as i64)))
+            .collect()
+    };
+    let tests = within(first, today);
+    let before = within(first - days, first - 1);
+
+    let mut report = format!(
+        "typers · {} to {}\n\n",
+        Date::from_days(first),
+        Date::from_days(today)
+    );
+
+    let change = tests.len() as f64 - before.len() as f64;
+    let _ = writeln!(
+        report,
+        "tests        {}{}",
+        tests.len(),
+        against(change, "", before.is_empty())
+    );
+
+    if tests.is_empty() {
+        let _ = writeln!(report, "streak       {}", streak(history, today));
+        return report;
+    }
+
+    let minutes = tests.iter().map(|test| test.time).sum::<f64>() / 60.0;
+    let best = tests.iter().map(|test| test.wpm).fold(0.0, f64::max);
+    let (wpm, accuracy) = averages(&tests);
+    let (wpm_before, accuracy_before) = averages(&before);
+
+    let _ = writeln!(report, "practiced    {minutes:.0} min");
+    let _ = writeln!(
+        report,
+        "speed        {wpm:.0} wpm on average{}, {best:.0} at best",
+        against(wpm.round() - wpm_before.round(), "", before.is_empty())
+    );
+    let _ = writeln!(
+        report,
+        "accuracy     {accuracy:.0}%{}",
+        against(
+            accuracy.round() - accuracy_before.round(),
+            "%",
+            before.is_empty()
+        )
+    );
+    let _ = writeln!(report, "streak       {}", streak(history, today));
+    report
+}
+
+/// How something changed from the days before, unless there were none.
+fn against(change: f64, unit: &str, nothing_before: bool) -> String {
+    if nothing_before {
+        String::new()
+    } else {
+        format!(" ({change:+}{unit} on the days before)")
+    }
+}
+
+/// Mean speed and accuracy of `tests`.
+#[expect(clippy::cast_precision_loss)]
+fn averages(tests: &[&Record]) -> (f64, f64) {
+    let n = tests.len().max(1) as f64;
+    let sum = |stat: fn(&Record) -> f64| tests.iter().map(|test| stat(test)).sum::<f64>() / n;
+    (sum(|test| test.wpm), sum(|test| test.accuracy))
+}
+
+/// The days in a row with a test up to `today`, which doesn't break until
+/// today is over without one.
+#[expect(clippy::cast_possible_wrap)]
+fn streak(history: &History, today: i64) -> String {
+    let days: BTreeSet<i64> = history
+        .tests
+        .iter()
+        .map(|test| (test.at / 86_400) as i64)
+        .collect();
+    let done = days.contains(&today);
+    let mut day = if done { today } else { today - 1 };
+    let mut streak = 0;
+
+    while days.contains(&day) {
+        streak += 1;
+        day -= 1;
+    }
+
+    let days = if streak == 1 { "day" } else { "days" };
+
+    match (streak, done) {
+        (0, _) => "none going, a test today starts one".to_string(),
+        (_, true) => format!("{streak} {days}, with a test today"),
+        (_, false) => format!("{streak} {days}, kept going with a test today"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_on_the_days() {
+        let test = |day: u64, wpm, time| Record {
+            at: day * 86_400,
+            wpm,
+            accuracy: 90.0,
+            time,
+            ..Record::default()
+        };
+        let history = History {
+            tests: vec![
+                test(6, 50.0, 60.0),
+                test(8, 60.0, 60.0),
+                test(9, 70.0, 120.0),
+                test(10, 80.0, 60.0),
+            ],
+        };
+
+        let report = render(&history, 3, 10);
+        assert!(report.starts_with("typers · 1970-01-09 to 1970-01-11\n"));
+        assert!(report.contains("tests        3 (+2 on the days before)\n"));
+        assert!(report.contains("practiced    4 min\n"));
+        assert!(report
+            .contains("speed        70 wpm on average (+20 on the days before), 80 at best\n"));
+        assert!(report.contains("streak       3 days, with a test today\n"));
+        assert!(render(&history, 3, 11).contains("3 days, kept going with a test today"));
+        assert_eq!(
+            render(&history, 1, 20),
+            "typers · 1970-01-21 to 1970-01-21\n\n\
+             tests        0\n\
+             streak       none going, a test today starts one\n"
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use crate::paths::Paths;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The keystrokes of a test. One quit part way through is kept so the next
+/// launch can pick it up, and a finished one can be exported as a ghost to
+/// race against.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Saved {
+    pub words: Vec<String>,
+    pub typed: String,
+    /// Elapsed time of each keystroke, in milliseconds.
+    pub times: Vec<u64>,
+}
+
+impl Saved {
+    #[expect(clippy::cast_possible_truncation)]
+    pub fn new(words: &[String], typed: &[char], times: &[Duration]) -> Self {
+        Self {
+            words: words.to_vec(),
+            typed: typed.iter().collect(),
+            times: times.iter().map(|time| time.as_millis() as u64).collect(),
+        }
+    }
+
+    pub fn typed(&self) -> Vec<char> {
+        self.typed.chars().collect()
+    }
+
+    pub fn times(&self) -> Vec<Duration> {
+        self.times
+            .iter()
+            .copied()
+            .map(Duration::from_millis)
+            .collect()
+    }
+
+    fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.data.join("resume.toml"))
+    }
+
+    pub fn to_toml(&self) -> io::Result<String> {
+        toml::to_string(self).map_err(io::Error::other)
+    }
+
+    /// Reads a keystroke log exported from another run.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let invalid = |reason: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid ghost {}: {reason}", path.display()),
+            )
+        };
+
+        let saved: Self =
+            toml::from_str(&fs::read_to_string(path)?).map_err(|err| invalid(err.to_string()))?;
+
+        if saved.typed.chars().count() != saved.times.len() {
+            return Err(invalid("keystrokes and times don't match up".to_string()));
+        }
+
+        Ok(saved)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+        let text = self.to_toml()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, text)
+    }
+
+    /// The saved test, if there is one that still makes sense to continue.
+    pub fn load() -> Option<Self> {
+        let text = fs::read_to_string(Self::path().ok()?).ok()?;
+        let saved: Self = toml::from_str(&text).ok()?;
+        let len = saved.words.join(" ").chars().count();
+        let typed = saved.typed.chars().count();
+
+        (typed > 0 && typed < len && typed == saved.times.len()).then_some(saved)
+    }
+
+    pub fn clear() {
+        if let Ok(path) = Self::path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let words = ["ab".to_string(), "cd".to_string()];
+        let times = [Duration::from_millis(0), Duration::from_millis(150)];
+        let saved = Saved::new(&words, &['a', 'x'], &times);
+
+        let text = toml::to_string(&saved).unwrap();
+        let loaded: Saved = toml::from_str(&text).unwrap();
+
+        assert_eq!(loaded, saved);
+        assert_eq!(loaded.typed(), ['a', 'x']);
+        assert_eq!(loaded.times(), times);
+    }
+
+    #[test]
+    fn rejects_mismatched_ghosts() {
+        let path = std::env::temp_dir().join("typers-ghost-test.toml");
+        fs::write(&path, "words = [\"ab\"]\ntyped = \"ab\"\ntimes = [0]\n").unwrap();
+
+        let read = Saved::read(&path);
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(read.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,43 @@
+use super::{center, keymap::Action, theme::Theme};
+use crate::Args;
+use ratatui::{
+    layout::{Constraint, Rect},
+    Frame,
+};
+
+/// What the app lends a screen to be drawn with.
+pub struct Context<'a> {
+    pub args: &'a Args,
+    pub theme: &'a Theme,
+    /// Shown in place of the screen's own hints, if there's one.
+    pub notice: Option<&'a str>,
+}
+
+/// A screen drawn on its own in the middle of the terminal, like the menu
+/// or the history, taking the keys while it's up. The app only picks which
+/// one is showing and acts on the choices it hands back.
+pub trait Screen {
+    /// What picking something on the screen asks the app to do.
+    type Choice;
+
+    /// The size it's drawn at, which is also the least it needs.
+    const WIDTH: u16;
+    const HEIGHT: u16;
+
+    /// Where it's drawn in `area`, the whole terminal.
+    fn area(&self, area: Rect) -> Rect {
+        center(
+            area,
+            Constraint::Length(Self::WIDTH),
+            Constraint::Length(Self::HEIGHT),
+        )
+    }
+
+    /// Acts on `action`, returning what the app should do next if anything
+    /// was picked.
+    fn handle(&mut self, _action: Action, _args: &mut Args) -> Option<Self::Choice> {
+        None
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect, context: &Context);
+}
@@ -0,0 +1,142 @@
+/// The outcome of one test in a multi-test session.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Run {
+    pub wpm: f64,
+    pub accuracy: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    pub median: f64,
+    pub best: f64,
+    /// Accuracy of the last test minus that of the first.
+    pub accuracy_change: f64,
+}
+
+#[expect(clippy::cast_precision_loss)]
+pub fn summarize(runs: &[Run]) -> Option<Summary> {
+    let (first, last) = (runs.first()?, runs.last()?);
+
+    let mut speeds: Vec<f64> = runs.iter().map(|run| run.wpm).collect();
+    speeds.sort_by(f64::total_cmp);
+
+    let middle = speeds.len() / 2;
+    let median = if speeds.len().is_multiple_of(2) {
+        f64::midpoint(speeds[middle - 1], speeds[middle])
+    } else {
+        speeds[middle]
+    };
+
+    Some(Summary {
+        mean: speeds.iter().sum::<f64>() / speeds.len() as f64,
+        median,
+        best: speeds[speeds.len() - 1],
+        accuracy_change: last.accuracy - first.accuracy,
+    })
+}
+
+/// Rounds won by each player of a versus series, with the runs taking turns
+/// between the players and the faster run of a round winning it.
+pub fn wins(runs: &[Run]) -> [usize; 2] {
+    let mut wins = [0, 0];
+
+    for round in runs.chunks_exact(2) {
+        wins[usize::from(round[1].wpm > round[0].wpm)] += 1;
+    }
+
+    wins
+}
+
+/// Whether a best-of-`rounds` series is over, by a player having won most
+/// of the rounds or all of them having been raced.
+pub fn is_decided(runs: &[Run], rounds: usize) -> bool {
+    runs.len() / 2 >= rounds || wins(runs).iter().any(|wins| *wins > rounds / 2)
+}
+
+/// A row for each of `names` with their run, fastest first, for a
+/// `--roster` class.
+pub fn table(names: &[String], runs: &[Run]) -> Vec<String> {
+    let mut ranked: Vec<(&String, &Run)> = names.iter().zip(runs).collect();
+    ranked.sort_by(|a, b| b.1.wpm.total_cmp(&a.1.wpm));
+
+    let width = names
+        .iter()
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or_default();
+
+    ranked
+        .into_iter()
+        .enumerate()
+        .map(|(i, (name, run))| {
+            format!(
+                "{:>2}. {name:<width$}  {:>4.0} wpm  {:>3.0}% acc",
+                i + 1,
+                run.wpm,
+                run.accuracy
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(wpm: f64, accuracy: f64) -> Run {
+        Run { wpm, accuracy }
+    }
+
+    #[test]
+    fn summarizes_runs() {
+        let runs = [
+            run(60.0, 90.0),
+            run(80.0, 95.0),
+            run(40.0, 96.0),
+            run(70.0, 97.0),
+        ];
+
+        let summary = summarize(&runs).unwrap();
+
+        assert_eq!(
+            summary,
+            Summary {
+                mean: 62.5,
+                median: 65.0,
+                best: 80.0,
+                accuracy_change: 7.0,
+            }
+        );
+        assert_eq!(summarize(&[]), None);
+    }
+
+    #[test]
+    fn ranks_the_class() {
+        let names = ["ann".to_string(), "bartholomew".to_string()];
+        let runs = [run(48.0, 91.0), run(72.4, 98.0)];
+
+        assert_eq!(
+            table(&names, &runs),
+            [
+                " 1. bartholomew    72 wpm   98% acc",
+                " 2. ann            48 wpm   91% acc",
+            ]
+        );
+    }
+
+    #[test]
+    fn scores_series() {
+        let runs = [run(60.0, 90.0), run(70.0, 90.0), run(80.0, 90.0)];
+        assert_eq!(wins(&runs), [0, 1]);
+        assert!(!is_decided(&runs, 3));
+
+        let runs = [&runs[..], &[run(75.0, 90.0)]].concat();
+        assert_eq!(wins(&runs), [1, 1]);
+        assert!(!is_decided(&runs, 3));
+        assert!(is_decided(&runs, 2));
+
+        let runs = [&runs[..], &[run(50.0, 90.0), run(55.0, 90.0)]].concat();
+        assert!(is_decided(&runs, 3));
+    }
+}
@@ -0,0 +1,221 @@
+use super::{
+    keymap::Action,
+    menu::{cycle, WORDS},
+    screen::{Context, Screen},
+    strings::{text, Text},
+};
+use crate::{
+    args::{Cursor, KeymapPreset, Spaces, ThemeName, WpmFormula},
+    config::Config,
+    Args,
+};
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Widget},
+    Frame,
+};
+
+const CURSORS: [Cursor; 3] = [Cursor::Bar, Cursor::Block, Cursor::Underline];
+const THEMES: [ThemeName; 4] = [
+    ThemeName::Default,
+    ThemeName::HighContrast,
+    ThemeName::Light,
+    ThemeName::Auto,
+];
+const FORMULAS: [WpmFormula; 2] = [WpmFormula::Chars, WpmFormula::Words];
+const KEYMAPS: [KeymapPreset; 2] = [KeymapPreset::Default, KeymapPreset::Vim];
+const SPACES: [Spaces; 2] = [Spaces::Strict, Spaces::Lenient];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Item {
+    Words,
+    Display,
+    LiveStats,
+    Cursor,
+    Blink,
+    Glide,
+    Motion,
+    Flash,
+    Theme,
+    Formula,
+    Spaces,
+    Keymap,
+}
+
+impl Item {
+    const ALL: [Self; 12] = [
+        Self::Words,
+        Self::Display,
+        Self::LiveStats,
+        Self::Cursor,
+        Self::Blink,
+        Self::Glide,
+        Self::Motion,
+        Self::Flash,
+        Self::Theme,
+        Self::Formula,
+        Self::Spaces,
+        Self::Keymap,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Words => "words",
+            Self::Display => "display",
+            Self::LiveStats => "live stats",
+            Self::Cursor => "cursor",
+            Self::Blink => "blink",
+            Self::Glide => "glide",
+            Self::Motion => "motion",
+            Self::Flash => "error flash",
+            Self::Theme => "theme",
+            Self::Formula => "wpm",
+            Self::Spaces => "spaces",
+            Self::Keymap => "keys",
+        }
+    }
+
+    fn value(self, config: &Config) -> String {
+        let toggle = |on: bool| if on { "on" } else { "off" }.to_string();
+
+        match self {
+            Self::Words => config.words.to_string(),
+            Self::Display => if config.tape { "tape" } else { "paragraph" }.to_string(),
+            Self::LiveStats => toggle(config.live_stats),
+            Self::Cursor => match config.cursor {
+                Cursor::Bar => "bar",
+                Cursor::Block => "block",
+                Cursor::Underline => "underline",
+            }
+            .to_string(),
+            Self::Blink => toggle(!config.steady_cursor),
+            Self::Glide => toggle(!config.instant_caret),
+            Self::Motion => toggle(!config.reduced_motion),
+            Self::Flash => toggle(config.flash_errors),
+            Self::Theme => match config.theme {
+                ThemeName::Default => "default",
+                ThemeName::HighContrast => "high contrast",
+                ThemeName::Light => "light",
+                ThemeName::Auto => "auto",
+            }
+            .to_string(),
+            Self::Formula => config.wpm_formula.label().to_string(),
+            Self::Spaces => match config.spaces {
+                Spaces::Strict => "strict",
+                Spaces::Lenient => "lenient",
+            }
+            .to_string(),
+            Self::Keymap => match config.keymap {
+                KeymapPreset::Default => "default",
+                KeymapPreset::Vim => "vim",
+            }
+            .to_string(),
+        }
+    }
+
+    fn step(self, config: &mut Config, forward: bool) {
+        match self {
+            Self::Words => config.words = cycle(&WORDS, &config.words, forward),
+            Self::Display => config.tape = !config.tape,
+            Self::LiveStats => config.live_stats = !config.live_stats,
+            Self::Cursor => config.cursor = cycle(&CURSORS, &config.cursor, forward),
+            Self::Blink => config.steady_cursor = !config.steady_cursor,
+            Self::Glide => config.instant_caret = !config.instant_caret,
+            Self::Motion => config.reduced_motion = !config.reduced_motion,
+            Self::Flash => config.flash_errors = !config.flash_errors,
+            Self::Theme => config.theme = cycle(&THEMES, &config.theme, forward),
+            Self::Formula => {
+                config.wpm_formula = cycle(&FORMULAS, &config.wpm_formula, forward);
+            }
+            Self::Spaces => config.spaces = cycle(&SPACES, &config.spaces, forward),
+            Self::Keymap => config.keymap = cycle(&KEYMAPS, &config.keymap, forward),
+        }
+    }
+}
+
+/// Edits a draft of the config, which only replaces the real one on save.
+pub struct Settings {
+    pub draft: Config,
+    selected: usize,
+}
+
+impl Settings {
+    pub fn new(config: Config) -> Self {
+        Self {
+            draft: config,
+            selected: 0,
+        }
+    }
+}
+
+impl Screen for Settings {
+    type Choice = ();
+
+    const WIDTH: u16 = 40;
+    #[expect(clippy::cast_possible_truncation)]
+    const HEIGHT: u16 = Item::ALL.len() as u16 + 6;
+
+    fn handle(&mut self, action: Action, _args: &mut Args) -> Option<()> {
+        let item = Item::ALL[self.selected];
+
+        match action {
+            Action::Up => self.selected = self.selected.saturating_sub(1),
+            Action::Down => self.selected = (self.selected + 1).min(Item::ALL.len() - 1),
+            Action::Left => item.step(&mut self.draft, false),
+            Action::Right => item.step(&mut self.draft, true),
+            _ => {}
+        }
+
+        None
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    fn render(&self, frame: &mut Frame, area: Rect, context: &Context) {
+        let (theme, notice) = (context.theme, context.notice);
+        let block = Block::bordered()
+            .title(Span::styled("settings", theme.accent))
+            .title_bottom(Span::styled(
+                notice.unwrap_or(text(Text::EnterSaveEscBack)),
+                theme.muted,
+            ))
+            .border_style(theme.accent)
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        let inner = block.inner(area);
+        block.render(area, frame.buffer_mut());
+
+        let [options, _, hint] = Layout::vertical([
+            Constraint::Length(Item::ALL.len() as u16),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(inner);
+
+        let lines: Vec<Line> = Item::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let selected = i == self.selected;
+                let marker = if selected { "› " } else { "  " };
+                let line = Line::raw(format!(
+                    "{marker}{:<14}‹ {} ›",
+                    item.label(),
+                    item.value(&self.draft)
+                ));
+
+                if selected {
+                    line.style(theme.selected)
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        Paragraph::new(lines).render(options, frame.buffer_mut());
+
+        let path = Config::path().map_or_else(|_| String::new(), |path| path.display().to_string());
+        Paragraph::new(Span::styled(path, theme.muted)).render(hint, frame.buffer_mut());
+    }
+}
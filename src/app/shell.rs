@@ -0,0 +1,15 @@
+use std::process::Command;
+
+/// `command` run through the platform's shell, so users can write pipes and
+/// quoting as they would in a terminal.
+pub fn shell(command: &str) -> Command {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    let mut process = Command::new(shell);
+    process.args([flag, command]);
+    process
+}
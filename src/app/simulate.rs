@@ -0,0 +1,145 @@
+use super::{stats, words::Words};
+use rand::Rng;
+use std::{fmt::Write, time::Duration};
+use typers::{Scoring, TestEngine};
+
+/// How far each keystroke's gap strays from the typist's average, either
+/// way.
+const JITTER: f64 = 0.3;
+
+/// What one simulated test came out at.
+pub struct Sample {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub consistency: f64,
+}
+
+/// Types `words` the way someone at `wpm` and `accuracy` would: a steady
+/// pace with some jitter, and a wrong letter left in place now and then.
+/// The test is scored with `scoring`, as a test in the app would be.
+pub fn run(
+    words: &[String],
+    scoring: Scoring,
+    wpm: f64,
+    accuracy: f64,
+    rng: &mut impl Rng,
+) -> Sample {
+    let text = words.join(" ");
+    let gap = 60.0 / (wpm * 5.0);
+    let wrong = (1.0 - accuracy / 100.0).clamp(0.0, 1.0);
+
+    let mut engine = TestEngine::new(&text).scoring(scoring);
+    let mut at = 0.0;
+
+    for c in text.chars() {
+        let typed = if rng.gen_bool(wrong) {
+            wrong_letter(c, rng)
+        } else {
+            c
+        };
+
+        engine.type_char(typed, Duration::from_secs_f64(at));
+        at += gap * rng.gen_range(1.0 - JITTER..1.0 + JITTER);
+    }
+
+    let result = engine
+        .result()
+        .expect("every character of the text to have been typed");
+    let speeds = stats::word_speeds(words, engine.times());
+
+    Sample {
+        wpm: result.wpm,
+        accuracy: result.accuracy,
+        consistency: stats::consistency(&speeds).unwrap_or_default(),
+    }
+}
+
+fn wrong_letter(c: char, rng: &mut impl Rng) -> char {
+    loop {
+        let letter = rng.gen_range('a'..='z');
+
+        if letter != c {
+            return letter;
+        }
+    }
+}
+
+/// `runs` tests of `n` words from `generator`, with how their stats spread
+/// as a table, for `typers simulate`.
+pub fn report(
+    generator: &Words,
+    scoring: Scoring,
+    n: usize,
+    wpm: f64,
+    accuracy: f64,
+    runs: usize,
+    rng: &mut impl Rng,
+) -> String {
+    let samples: Vec<Sample> = (0..runs)
+        .map(|_| {
+            run(
+                &generator.generate_with(n, rng),
+                scoring,
+                wpm,
+                accuracy,
+                rng,
+            )
+        })
+        .collect();
+
+    let mut text = format!(
+        "{runs} simulated tests of {n} words at {wpm} wpm and {accuracy}% accuracy\n\n{:<12}{:>8}{:>8}{:>8}{:>8}\n",
+        "", "mean", "sd", "min", "max"
+    );
+
+    let column = |value: fn(&Sample) -> f64| samples.iter().map(value).collect::<Vec<_>>();
+    let rows = [
+        ("wpm", column(|sample| sample.wpm)),
+        ("accuracy", column(|sample| sample.accuracy)),
+        ("consistency", column(|sample| sample.consistency)),
+    ];
+
+    for (label, values) in rows {
+        let (mean, sd) = spread(&values);
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+        let _ = writeln!(text, "{label:<12}{mean:>8.1}{sd:>8.1}{min:>8.1}{max:>8.1}");
+    }
+
+    text
+}
+
+/// The mean and standard deviation of `values`.
+#[expect(clippy::cast_precision_loss)]
+fn spread(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let variance = values
+        .iter()
+        .map(|value| (value - mean).powi(2))
+        .sum::<f64>()
+        / n;
+
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn lands_near_the_target() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let words = Words::default().generate_with(50, &mut rng);
+
+        let sample = run(&words, Scoring::default(), 80.0, 100.0, &mut rng);
+        assert!((sample.wpm - 80.0).abs() < 4.0, "{}", sample.wpm);
+        assert!((sample.accuracy - 100.0).abs() < 1e-9);
+
+        let sample = run(&words, Scoring::default(), 80.0, 90.0, &mut rng);
+        assert!((sample.accuracy - 90.0).abs() < 5.0, "{}", sample.accuracy);
+        assert!(sample.consistency > 50.0, "{}", sample.consistency);
+    }
+}
@@ -0,0 +1,146 @@
+use super::{
+    chunks::Progress, date::Date, numbers, shell::shell, symbols, vocab::Schedule, words::Words,
+};
+use std::{path::PathBuf, process::Stdio};
+
+/// Longest text taken from the clipboard, in characters.
+const MAX_TEXT_LEN: usize = 1000;
+
+/// Where the words of a test come from.
+pub enum Source {
+    Random(Words),
+    Numbers,
+    Symbols,
+    Text(Vec<String>),
+    /// Words printed by a shell command, run again for every test.
+    Command(String),
+    /// Terms from a vocabulary file, picked by when they are due.
+    Vocab(Vec<String>),
+    /// A whole file, typed through a test's worth of words at a time.
+    Chunks {
+        file: PathBuf,
+        words: Vec<String>,
+    },
+}
+
+impl Source {
+    /// Turns `text` into typeable words, swapping typographic punctuation for
+    /// its plain equivalent and dropping anything else that can't be typed.
+    pub fn text(text: &str) -> Self {
+        Self::Text(words(text).collect())
+    }
+
+    /// Like [`Source::text`], but cut off at [`MAX_TEXT_LEN`] on a word
+    /// boundary, as whatever was last copied could be anything.
+    pub fn clipboard(text: &str) -> Self {
+        let mut len = 0;
+
+        let words = words(text)
+            .take_while(|word| {
+                len += word.chars().count() + 1;
+                len <= MAX_TEXT_LEN + 1
+            })
+            .collect();
+
+        Self::Text(words)
+    }
+
+    /// All of `text`, however long, to be split up by `--chunks`.
+    pub fn chunks(file: PathBuf, text: &str) -> Self {
+        Self::Chunks {
+            file,
+            words: words(text).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Random(words) => words.is_empty(),
+            Self::Numbers | Self::Symbols => false,
+            Self::Text(words) | Self::Vocab(words) | Self::Chunks { words, .. } => words.is_empty(),
+            Self::Command(_) => self.generate(1).is_empty(),
+        }
+    }
+
+    /// Random sources pick `n` words, fixed text is always used in full and
+    /// commands are asked for `n` words but may print fewer.
+    pub fn generate(&self, n: usize) -> Vec<String> {
+        match self {
+            Self::Random(words) => words.generate(n),
+            Self::Numbers => numbers::generate(n, &mut rand::thread_rng()),
+            Self::Symbols => symbols::generate(n, &mut rand::thread_rng()),
+            Self::Text(words) => words.clone(),
+            Self::Vocab(terms) => Schedule::load().pick(terms, Date::epoch_days(), n),
+            Self::Chunks { file, words } => Progress::load(file).chunk(words, n),
+            Self::Command(command) => {
+                let output = shell(command)
+                    .env("TYPERS_WORDS", n.to_string())
+                    .stdin(Stdio::null())
+                    .stderr(Stdio::null())
+                    .output();
+
+                match output {
+                    Ok(output) if output.status.success() => {
+                        let mut words =
+                            Self::text(&String::from_utf8_lossy(&output.stdout)).generate(n);
+                        words.truncate(n);
+                        words
+                    }
+                    _ => Vec::new(),
+                }
+            }
+        }
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split_whitespace()
+        .map(sanitize)
+        .filter(|word| !word.is_empty())
+}
+
+fn sanitize(word: &str) -> String {
+    word.chars()
+        .filter_map(|c| match c {
+            '‘' | '’' | '′' => Some('\''),
+            '“' | '”' | '″' => Some('"'),
+            '–' | '—' | '−' => Some('-'),
+            '…' => Some('.'),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(source: &Source) -> Vec<String> {
+        source.generate(0)
+    }
+
+    #[test]
+    fn sanitizes_text() {
+        let source = Source::text("  “Don’t”\tpanic —\n\u{7}\r\nnow  ");
+        assert_eq!(words(&source), ["\"Don't\"", "panic", "-", "now"]);
+    }
+
+    #[test]
+    fn caps_clipboard_length() {
+        let source = Source::clipboard(&"abcd ".repeat(500));
+        let len = words(&source).join(" ").len();
+        assert!(len <= MAX_TEXT_LEN && len > MAX_TEXT_LEN - 5);
+    }
+
+    #[test]
+    fn loads_long_saved_tests() {
+        let saved: Vec<String> = (0..2000).map(|i| format!("word{i}")).collect();
+        let path = std::env::temp_dir().join("typers-saved-test.txt");
+        std::fs::write(&path, saved.join(" ")).unwrap();
+        let text = std::fs::read_to_string(&path);
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(words(&Source::text(&text.unwrap())), saved);
+    }
+}
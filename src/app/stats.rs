@@ -0,0 +1,443 @@
+use super::keyboard::{self, Finger};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+pub struct WordSpeed<'a> {
+    pub word: &'a str,
+    pub wpm: f64,
+}
+
+/// Burst speed of every fully typed word, timed from the keystroke before
+/// the word to its last character. `times` holds the elapsed time of each
+/// keystroke.
+#[expect(clippy::cast_precision_loss)]
+pub fn word_speeds<'a>(words: &'a [String], times: &[Duration]) -> Vec<WordSpeed<'a>> {
+    let mut speeds = Vec::with_capacity(words.len());
+    let mut start = 0;
+
+    for word in words {
+        let end = start + word.chars().count();
+
+        if end > times.len() {
+            break;
+        }
+
+        // The first keystroke starts the timer, so the first word has one
+        // less interval to measure.
+        let from = start.saturating_sub(1);
+        let secs = times[end - 1].saturating_sub(times[from]).as_secs_f64();
+
+        if secs > 0.0 {
+            let keystrokes = (end - 1 - from) as f64;
+            speeds.push(WordSpeed {
+                word,
+                wpm: keystrokes / 5.0 / secs * 60.0,
+            });
+        }
+
+        start = end + 1;
+    }
+
+    speeds
+}
+
+/// How steady the word speeds were, as 100% minus their coefficient of
+/// variation.
+#[expect(clippy::cast_precision_loss)]
+pub fn consistency(speeds: &[WordSpeed]) -> Option<f64> {
+    if speeds.is_empty() {
+        return None;
+    }
+
+    let n = speeds.len() as f64;
+    let mean = speeds.iter().map(|speed| speed.wpm).sum::<f64>() / n;
+    let variance = speeds
+        .iter()
+        .map(|speed| (speed.wpm - mean).powi(2))
+        .sum::<f64>()
+        / n;
+
+    Some((100.0 - variance.sqrt() / mean * 100.0).clamp(0.0, 100.0))
+}
+
+/// Time between the samples of [`pace`].
+pub const PACE_STEP: Duration = Duration::from_millis(500);
+
+/// How far back each sample of [`pace`] looks.
+const PACE_WINDOW: Duration = Duration::from_secs(3);
+
+/// The speed over the [`PACE_WINDOW`] up to each of the last `samples`
+/// steps of [`PACE_STEP`] until `now`, oldest first, with five characters
+/// to a word. Steps from before the test started are left out.
+#[expect(clippy::cast_precision_loss)]
+pub fn pace(times: &[Duration], now: Duration, samples: usize) -> Vec<f64> {
+    (0..samples)
+        .rev()
+        .filter_map(|back| now.checked_sub(PACE_STEP * u32::try_from(back).ok()?))
+        .filter(|end| !end.is_zero())
+        .map(|end| {
+            let start = end.saturating_sub(PACE_WINDOW);
+            let typed = times
+                .iter()
+                .filter(|time| **time > start && **time <= end)
+                .count();
+
+            typed as f64 / 5.0 / end.saturating_sub(start).as_secs_f64() * 60.0
+        })
+        .collect()
+}
+
+/// Width of one bar in [`rhythm`].
+pub const RHYTHM_BUCKET: Duration = Duration::from_millis(50);
+
+/// Counts the gaps between keystrokes in [`RHYTHM_BUCKET`] wide buckets,
+/// with the last bucket collecting everything slower.
+pub fn rhythm(times: &[Duration], buckets: usize) -> Vec<usize> {
+    let mut counts = vec![0; buckets];
+
+    for pair in times.windows(2) {
+        let gap = pair[1].saturating_sub(pair[0]);
+        let bucket = (gap.as_millis() / RHYTHM_BUCKET.as_millis()) as usize;
+
+        if let Some(count) = counts.get_mut(bucket.min(buckets.saturating_sub(1))) {
+            *count += 1;
+        }
+    }
+
+    counts
+}
+
+/// The speed over each of `stretches` equal stretches of a test that took
+/// `total`, with five characters to a word.
+#[expect(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+pub fn fatigue(times: &[Duration], total: Duration, stretches: usize) -> Vec<f64> {
+    if total.is_zero() || stretches == 0 {
+        return Vec::new();
+    }
+
+    let mut counts = vec![0; stretches];
+
+    for time in times {
+        let stretch = (time.as_secs_f64() / total.as_secs_f64() * stretches as f64) as usize;
+        counts[stretch.min(stretches - 1)] += 1;
+    }
+
+    let minutes = total.as_secs_f64() / 60.0 / stretches as f64;
+    counts
+        .into_iter()
+        .map(|count| f64::from(count) / 5.0 / minutes)
+        .collect()
+}
+
+/// How much of the opening speed was held to the end, as the last fifth of
+/// `speeds` against the first, in percent.
+#[expect(clippy::cast_precision_loss)]
+pub fn endurance(speeds: &[f64]) -> Option<f64> {
+    let n = speeds.len() / 5;
+
+    if n == 0 {
+        return None;
+    }
+
+    let opening = speeds[..n].iter().sum::<f64>() / n as f64;
+    let closing = speeds[speeds.len() - n..].iter().sum::<f64>() / n as f64;
+
+    (opening > 0.0).then(|| closing / opening * 100.0)
+}
+
+pub struct CharStats {
+    pub c: char,
+    pub typed: usize,
+    pub wrong: usize,
+}
+
+impl CharStats {
+    #[expect(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        (self.typed - self.wrong) as f64 / self.typed as f64 * 100.0
+    }
+}
+
+/// How often each character of `text` picked by `keep` was typed, and how
+/// often wrongly, in character order.
+pub fn char_stats(text: &[char], typed: &[char], keep: impl Fn(char) -> bool) -> Vec<CharStats> {
+    let mut stats: Vec<CharStats> = Vec::new();
+
+    for (&want, &got) in text.iter().zip(typed).filter(|(want, _)| keep(**want)) {
+        let at = match stats.binary_search_by_key(&want, |stats| stats.c) {
+            Ok(at) => at,
+            Err(at) => {
+                stats.insert(
+                    at,
+                    CharStats {
+                        c: want,
+                        typed: 0,
+                        wrong: 0,
+                    },
+                );
+                at
+            }
+        };
+
+        stats[at].typed += 1;
+        stats[at].wrong += usize::from(want != got);
+    }
+
+    stats
+}
+
+pub struct FingerStats {
+    pub finger: Finger,
+    pub typed: usize,
+    pub wrong: usize,
+    /// Average time taken to reach the finger's keys.
+    pub time: Duration,
+}
+
+impl FingerStats {
+    #[expect(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        (self.typed - self.wrong) as f64 / self.typed as f64 * 100.0
+    }
+}
+
+/// Keystrokes, mistakes and average time per key for every finger that
+/// typed part of `text`, in order across the keyboard.
+#[expect(clippy::cast_possible_truncation)]
+pub fn finger_stats(text: &[char], typed: &[char], times: &[Duration]) -> Vec<FingerStats> {
+    let mut fingers: BTreeMap<Finger, (usize, usize, Vec<Duration>)> = BTreeMap::new();
+
+    for (i, (&want, &got)) in text.iter().zip(typed).enumerate() {
+        let Some(finger) = keyboard::finger(want) else {
+            continue;
+        };
+
+        let (typed, wrong, gaps) = fingers.entry(finger).or_default();
+        *typed += 1;
+        *wrong += usize::from(want != got);
+
+        // The first keystroke starts the timer, so it has no gap.
+        if let (Some(before), Some(at)) =
+            (i.checked_sub(1).and_then(|i| times.get(i)), times.get(i))
+        {
+            gaps.push(at.saturating_sub(*before));
+        }
+    }
+
+    fingers
+        .into_iter()
+        .map(|(finger, (typed, wrong, gaps))| FingerStats {
+            finger,
+            typed,
+            wrong,
+            time: gaps
+                .iter()
+                .sum::<Duration>()
+                .checked_div(gaps.len() as u32)
+                .unwrap_or_default(),
+        })
+        .collect()
+}
+
+/// The finger with the most mistakes for how much it typed, the slower one
+/// on a tie, leaving out fingers too little used to tell.
+#[expect(clippy::cast_precision_loss)]
+pub fn weakest(stats: &[FingerStats]) -> Option<Finger> {
+    const MIN_KEYSTROKES: usize = 5;
+
+    stats
+        .iter()
+        .filter(|stats| stats.typed >= MIN_KEYSTROKES)
+        .max_by(|a, b| {
+            let rate = |stats: &FingerStats| stats.wrong as f64 / stats.typed as f64;
+            rate(a).total_cmp(&rate(b)).then(a.time.cmp(&b.time))
+        })
+        .map(|stats| stats.finger)
+}
+
+pub struct Hesitation {
+    pub from: char,
+    pub to: char,
+    pub pause: Duration,
+}
+
+/// The `n` letter transitions in `text` with the longest average pause
+/// before the second letter, slowest first.
+#[expect(clippy::cast_possible_truncation)]
+pub fn hesitations(text: &[char], times: &[Duration], n: usize) -> Vec<Hesitation> {
+    let mut pauses: HashMap<(char, char), Vec<Duration>> = HashMap::new();
+
+    for (i, pair) in times.windows(2).enumerate() {
+        if let (Some(&from), Some(&to)) = (text.get(i), text.get(i + 1)) {
+            pauses
+                .entry((from, to))
+                .or_default()
+                .push(pair[1].saturating_sub(pair[0]));
+        }
+    }
+
+    let mut hesitations: Vec<_> = pauses
+        .into_iter()
+        .map(|((from, to), pauses)| Hesitation {
+            from,
+            to,
+            pause: pauses.iter().sum::<Duration>() / pauses.len() as u32,
+        })
+        .collect();
+
+    hesitations.sort_by(|a, b| {
+        b.pause
+            .cmp(&a.pause)
+            .then((a.from, a.to).cmp(&(b.from, b.to)))
+    });
+    hesitations.truncate(n);
+    hesitations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_the_pace() {
+        // A keystroke every 100ms is 120 wpm.
+        let times: Vec<Duration> = (1..=40).map(|i| Duration::from_millis(i * 100)).collect();
+        let now = Duration::from_secs(4);
+
+        let pace = pace(&times, now, 10);
+        assert_eq!(pace.len(), 8);
+        assert!((pace[7] - 120.0).abs() < 1e-9);
+        assert!((pace[0] - 120.0).abs() < 1e-9);
+
+        let later = super::pace(&times, now + Duration::from_secs(2), 1);
+        assert!((later[0] - 40.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn times_each_word() {
+        let words = ["ab".to_string(), "cde".to_string()];
+        let times: Vec<_> = [0, 1, 2, 3, 4, 7]
+            .into_iter()
+            .map(Duration::from_secs)
+            .collect();
+
+        let speeds = word_speeds(&words, &times);
+
+        assert_eq!(speeds.len(), 2);
+        assert_eq!(speeds[0].word, "ab");
+        assert!((speeds[0].wpm - 12.0).abs() < 1e-9);
+        assert_eq!(speeds[1].word, "cde");
+        assert!((speeds[1].wpm - 7.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn steady_speeds_are_consistent() {
+        let speeds = [
+            WordSpeed {
+                word: "a",
+                wpm: 60.0,
+            },
+            WordSpeed {
+                word: "b",
+                wpm: 60.0,
+            },
+        ];
+        assert_eq!(consistency(&speeds), Some(100.0));
+
+        let speeds = [
+            WordSpeed {
+                word: "a",
+                wpm: 30.0,
+            },
+            WordSpeed {
+                word: "b",
+                wpm: 90.0,
+            },
+        ];
+        assert_eq!(consistency(&speeds), Some(50.0));
+        assert_eq!(consistency(&[]), None);
+    }
+
+    #[test]
+    fn skips_unfinished_words() {
+        let words = ["ab".to_string(), "cde".to_string()];
+        let times: Vec<_> = [0, 1, 2, 3].into_iter().map(Duration::from_secs).collect();
+
+        assert_eq!(word_speeds(&words, &times).len(), 1);
+    }
+
+    #[test]
+    fn buckets_keystroke_gaps() {
+        let times: Vec<_> = [0, 20, 120, 140, 1000]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+
+        assert_eq!(rhythm(&times, 4), [2, 0, 1, 1]);
+    }
+
+    #[test]
+    fn charts_slowing_down() {
+        // 120 wpm for five seconds, then half that for five more.
+        let times: Vec<_> = (0..50)
+            .map(|i| 50 + i * 100)
+            .chain((0..25).map(|i| 5050 + i * 200))
+            .map(Duration::from_millis)
+            .collect();
+
+        let speeds = fatigue(&times, Duration::from_secs(10), 10);
+        assert_eq!(speeds.len(), 10);
+        assert!((speeds[0] - 120.0).abs() < 1e-9);
+        assert!((speeds[9] - 60.0).abs() < 1e-9);
+        assert!((endurance(&speeds).unwrap() - 50.0).abs() < 1e-9);
+        assert_eq!(endurance(&speeds[..4]), None);
+    }
+
+    #[test]
+    fn counts_each_character() {
+        let text: Vec<_> = "1a21".chars().collect();
+        let typed: Vec<_> = "1a2".chars().collect();
+
+        let stats = char_stats(&text, &typed, |c| c.is_ascii_digit());
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].c, stats[0].typed, stats[0].wrong), ('1', 1, 0));
+        assert_eq!((stats[1].c, stats[1].typed, stats[1].wrong), ('2', 1, 0));
+    }
+
+    #[test]
+    fn picks_the_weakest_finger() {
+        let text: Vec<_> = "ffffffjjjjjj".chars().collect();
+        let typed: Vec<_> = "ffffffjjjxjj".chars().collect();
+        let times: Vec<_> = (0..12).map(Duration::from_secs).collect();
+
+        let stats = finger_stats(&text, &typed, &times);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].finger, Finger::LeftIndex);
+        assert_eq!((stats[1].typed, stats[1].wrong), (6, 1));
+        assert_eq!(stats[1].time, Duration::from_secs(1));
+        assert_eq!(weakest(&stats), Some(Finger::RightIndex));
+    }
+
+    #[test]
+    fn finds_slowest_transitions() {
+        let text: Vec<_> = "abab".chars().collect();
+        let times: Vec<_> = [0, 100, 400, 500]
+            .into_iter()
+            .map(Duration::from_millis)
+            .collect();
+
+        let hesitations = hesitations(&text, &times, 1);
+
+        assert_eq!(hesitations.len(), 1);
+        assert_eq!((hesitations[0].from, hesitations[0].to), ('b', 'a'));
+        assert_eq!(hesitations[0].pause, Duration::from_millis(300));
+    }
+}
@@ -0,0 +1,253 @@
+use super::{
+    history::{History, Record},
+    json,
+};
+use crate::args::HistoryStore;
+use toml::{Table, Value};
+
+/// Version of the format tests are kept in. Histories from before there
+/// was a version count as version 0.
+pub const VERSION: u32 = 1;
+
+/// Upgrades a test from each version to the next, starting from 0.
+const MIGRATIONS: [fn(&mut Table); VERSION as usize] = [
+    // Version 1 only added the version number.
+    |_| {},
+];
+
+/// A way of keeping the history in a file, picked with `history-store` in
+/// the config. Tests are appended as they finish, so a store needs a form
+/// that can be added to without writing the rest again.
+pub trait Store: Sync {
+    /// Name of the file in the data directory.
+    fn file(&self) -> &'static str;
+
+    /// Reads a history of any version up to this build's, migrating the
+    /// tests of older ones.
+    fn parse(&self, text: &str) -> Result<History, String>;
+
+    /// The start of a new file, giving its version.
+    fn header(&self) -> String;
+
+    /// The text that adds `record` to the end of the file.
+    fn entry(&self, record: &Record) -> Result<String, String>;
+
+    /// The whole of `history`, to write over the file.
+    fn write(&self, history: &History) -> Result<String, String> {
+        history
+            .tests
+            .iter()
+            .map(|test| self.entry(test))
+            .collect::<Result<String, _>>()
+            .map(|entries| self.header() + &entries)
+    }
+}
+
+/// The store for `store`.
+pub fn of(store: HistoryStore) -> &'static dyn Store {
+    match store {
+        HistoryStore::Toml => &Toml,
+        HistoryStore::JsonLines => &JsonLines,
+    }
+}
+
+/// Every store, to find a history kept in another.
+pub const ALL: [HistoryStore; 2] = [HistoryStore::Toml, HistoryStore::JsonLines];
+
+/// An array of tables, easy to read and edit by hand.
+struct Toml;
+
+impl Store for Toml {
+    fn file(&self) -> &'static str {
+        "history.toml"
+    }
+
+    fn parse(&self, text: &str) -> Result<History, String> {
+        let mut table: Table = toml::from_str(text).map_err(|err| err.to_string())?;
+        let migrations = migrations(table.remove("version").as_ref())?;
+
+        let tests = match table.remove("tests") {
+            None => Vec::new(),
+            Some(Value::Array(tests)) => tests,
+            Some(_) => return Err("tests is not an array".to_string()),
+        };
+
+        let tests = tests
+            .into_iter()
+            .map(|test| upgrade(migrations, test))
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { tests })
+    }
+
+    fn header(&self) -> String {
+        format!("version = {VERSION}\n")
+    }
+
+    /// `record` as a table of its own, which TOML allows after any others
+    /// of the same array.
+    fn entry(&self, record: &Record) -> Result<String, String> {
+        let entry = History {
+            tests: vec![record.clone()],
+        };
+
+        Ok(format!(
+            "\n{}",
+            toml::to_string(&entry).map_err(|err| err.to_string())?
+        ))
+    }
+
+    fn write(&self, history: &History) -> Result<String, String> {
+        let tests = toml::to_string(history).map_err(|err| err.to_string())?;
+        Ok(format!("{}\n{tests}", self.header()))
+    }
+}
+
+/// A JSON object on each line, quicker to read back for a long history and
+/// easy to load into other tools.
+struct JsonLines;
+
+impl Store for JsonLines {
+    fn file(&self) -> &'static str {
+        "history.jsonl"
+    }
+
+    /// The version is on a line of its own before the tests.
+    fn parse(&self, text: &str) -> Result<History, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(n, line)| (n + 1, json::parse(line)))
+            .peekable();
+
+        let header = lines.next_if(
+            |(_, value)| matches!(value, Ok(Value::Table(table)) if table.contains_key("version")),
+        );
+        let migrations = match header {
+            Some((n, Ok(Value::Table(table)))) => {
+                migrations(table.get("version")).map_err(|err| format!("line {n}: {err}"))?
+            }
+            _ => migrations(None)?,
+        };
+
+        let tests = lines
+            .map(|(n, value)| {
+                value
+                    .and_then(|value| upgrade(migrations, value))
+                    .map_err(|err| format!("line {n}: {err}"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { tests })
+    }
+
+    fn header(&self) -> String {
+        format!("{{\"version\":{VERSION}}}\n")
+    }
+
+    fn entry(&self, record: &Record) -> Result<String, String> {
+        let value = Value::try_from(record).map_err(|err| err.to_string())?;
+        let mut line = String::new();
+        json::write(&mut line, &value);
+        line.push('\n');
+        Ok(line)
+    }
+}
+
+/// The migrations a history of `version` needs, which is 0 when there's
+/// none.
+fn migrations(version: Option<&Value>) -> Result<&'static [fn(&mut Table)], String> {
+    let version = match version {
+        None => 0,
+        Some(Value::Integer(version)) => u32::try_from(*version).unwrap_or(u32::MAX),
+        Some(_) => return Err("version is not a number".to_string()),
+    };
+
+    MIGRATIONS
+        .get(version as usize..)
+        .ok_or_else(|| format!("version {version} is from a newer typers"))
+}
+
+/// Reads the test in `value`, run through `migrations` first.
+fn upgrade(migrations: &[fn(&mut Table)], mut value: Value) -> Result<Record, String> {
+    if let Value::Table(table) = &mut value {
+        for migrate in migrations {
+            migrate(table);
+        }
+    }
+
+    value.try_into().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(text: &str, speeds: &[(&str, f64)]) -> Record {
+        Record {
+            id: "0123456789ab".to_string(),
+            text: text.to_string(),
+            wpm: 60.0,
+            speeds: speeds
+                .iter()
+                .map(|(word, wpm)| ((*word).to_string(), *wpm))
+                .collect(),
+            daily: Some("2026-10-14".to_string()),
+            ..Record::default()
+        }
+    }
+
+    #[test]
+    fn appended_tests_read_back() {
+        let first = record("a b", &[("a", 50.0)]);
+        let second = record("say \"hi\"\n\té 🦀\u{1}", &[]);
+
+        for store in ALL.map(of) {
+            let text = store.entry(&first).unwrap() + &store.entry(&second).unwrap();
+            let history = store.parse(&text).unwrap();
+            assert_eq!(history.tests, [first.clone(), second.clone()]);
+            assert_eq!(store.parse(&store.write(&history).unwrap()), Ok(history));
+        }
+    }
+
+    #[test]
+    fn migrates_old_versions() {
+        let test = record("a", &[]);
+
+        for store in ALL.map(of) {
+            let old = store.entry(&test).unwrap();
+            assert_eq!(store.parse(&old).unwrap().tests, vec![test.clone()]);
+
+            let written = store.write(&store.parse(&old).unwrap()).unwrap();
+            assert!(written.starts_with(&store.header()), "{written}");
+            assert!(store.parse(&written).is_ok());
+
+            let newer = store.header().replace(&VERSION.to_string(), "99") + &old;
+            assert!(store
+                .parse(&newer)
+                .is_err_and(|err| err.contains("version 99 is from a newer typers")));
+        }
+    }
+
+    #[test]
+    fn reads_json_lines() {
+        let history = JsonLines
+            .parse(
+                "{ \"text\": \"\\ud83e\\udd80 \\u00e9\", \"wpm\": 61, \"daily\": null, \"tags\": [] }\n\n\
+                 {\"text\":\"b\",\"accuracy\":9.5e1}\n",
+            )
+            .unwrap();
+
+        assert_eq!(history.tests[0].text, "🦀 é");
+        assert_eq!(history.tests[0].daily, None);
+        assert_eq!(
+            (history.tests[0].wpm, history.tests[1].accuracy),
+            (61.0, 95.0)
+        );
+        assert!(JsonLines
+            .parse("{\"text\": \"a\"} x")
+            .is_err_and(|err| err.starts_with("line 1:")));
+        assert!(JsonLines.parse("[1, null]").is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::args::UiLang;
+use std::sync::RwLock;
+
+/// The language the interface is shown in, set once the options are read.
+static LANGUAGE: RwLock<UiLang> = RwLock::new(UiLang::En);
+
+/// A piece of the interface, written out by the catalogue of the language in
+/// use. What's typed, and the words of the word lists, aren't among them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    // The options and actions of the menu.
+    Words,
+    Display,
+    Memory,
+    RandomCase,
+    LiveStats,
+    Profile,
+    Start,
+    History,
+    Achievements,
+    Leaderboard,
+    Settings,
+    // The labels of the results.
+    Time,
+    Reaction,
+    Accuracy,
+    Difficulty,
+    Fastest,
+    Slowest,
+    Opponent,
+    // Hints.
+    EnterToStart,
+    AnyKeyToBegin,
+    StartingIn,
+    EscBack,
+    EnterSaveEscBack,
+    Retry,
+    New,
+    Quit,
+}
+
+/// Shows the interface in `language` from here on, or in the one of the
+/// locale for `auto`.
+pub fn use_language(language: UiLang) {
+    let language = match language {
+        UiLang::Auto => detect(),
+        language => language,
+    };
+
+    if let Ok(mut current) = LANGUAGE.write() {
+        *current = language;
+    }
+}
+
+/// `text` in the language in use.
+pub fn text(text: Text) -> &'static str {
+    match LANGUAGE.read().map_or(UiLang::En, |language| *language) {
+        UiLang::De => german(text),
+        UiLang::Es => spanish(text),
+        UiLang::En | UiLang::Auto => english(text),
+    }
+}
+
+/// The language of the locale messages are shown in, English for any
+/// without a catalogue.
+fn detect() -> UiLang {
+    let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+    let locale = var("LC_ALL")
+        .or_else(|| var("LC_MESSAGES"))
+        .or_else(|| var("LANG"));
+
+    language_of(locale.as_deref().unwrap_or_default())
+}
+
+/// The language of a locale like `de_DE.UTF-8`.
+fn language_of(locale: &str) -> UiLang {
+    match locale.split(['_', '.', '@', '-']).next() {
+        Some("de") => UiLang::De,
+        Some("es") => UiLang::Es,
+        _ => UiLang::En,
+    }
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::Words => "words",
+        Text::Display => "display",
+        Text::Memory => "memory",
+        Text::RandomCase => "random case",
+        Text::LiveStats => "live stats",
+        Text::Profile => "profile",
+        Text::Start => "start",
+        Text::History => "history",
+        Text::Achievements => "achievements",
+        Text::Leaderboard => "leaderboard",
+        Text::Settings => "settings",
+        Text::Time => "Time",
+        Text::Reaction => "Reaction",
+        Text::Accuracy => "Accuracy",
+        Text::Difficulty => "Difficulty",
+        Text::Fastest => "Fastest",
+        Text::Slowest => "Slowest",
+        Text::Opponent => "Opponent",
+        Text::EnterToStart => "enter to start",
+        Text::AnyKeyToBegin => "any key to begin",
+        Text::StartingIn => "starting in",
+        Text::EscBack => "esc back",
+        Text::EnterSaveEscBack => "enter save · esc back",
+        Text::Retry => "retry",
+        Text::New => "new",
+        Text::Quit => "quit",
+    }
+}
+
+fn german(text: Text) -> &'static str {
+    match text {
+        Text::Words => "Wörter",
+        Text::Display => "Anzeige",
+        Text::Memory => "Gedächtnis",
+        Text::RandomCase => "Groß/klein",
+        Text::LiveStats => "Live-Werte",
+        Text::Profile => "Profil",
+        Text::Start => "Start",
+        Text::History => "Verlauf",
+        Text::Achievements => "Erfolge",
+        Text::Leaderboard => "Bestenliste",
+        Text::Settings => "Einstellungen",
+        Text::Time => "Zeit",
+        Text::Reaction => "Reaktion",
+        Text::Accuracy => "Genauigkeit",
+        Text::Difficulty => "Schwierigkeit",
+        Text::Fastest => "Am schnellsten",
+        Text::Slowest => "Am langsamsten",
+        Text::Opponent => "Gegner",
+        Text::EnterToStart => "Enter zum Starten",
+        Text::AnyKeyToBegin => "beliebige Taste zum Beginnen",
+        Text::StartingIn => "Start in",
+        Text::EscBack => "Esc zurück",
+        Text::EnterSaveEscBack => "Enter speichern · Esc zurück",
+        Text::Retry => "nochmal",
+        Text::New => "neu",
+        Text::Quit => "beenden",
+    }
+}
+
+fn spanish(text: Text) -> &'static str {
+    match text {
+        Text::Words => "palabras",
+        Text::Display => "vista",
+        Text::Memory => "memoria",
+        Text::RandomCase => "mayús. azar",
+        Text::LiveStats => "datos en vivo",
+        Text::Profile => "perfil",
+        Text::Start => "empezar",
+        Text::History => "historial",
+        Text::Achievements => "logros",
+        Text::Leaderboard => "clasificación",
+        Text::Settings => "ajustes",
+        Text::Time => "Tiempo",
+        Text::Reaction => "Reacción",
+        Text::Accuracy => "Precisión",
+        Text::Difficulty => "Dificultad",
+        Text::Fastest => "Más rápida",
+        Text::Slowest => "Más lenta",
+        Text::Opponent => "Rival",
+        Text::EnterToStart => "enter para empezar",
+        Text::AnyKeyToBegin => "cualquier tecla para empezar",
+        Text::StartingIn => "empieza en",
+        Text::EscBack => "esc volver",
+        Text::EnterSaveEscBack => "enter guardar · esc volver",
+        Text::Retry => "repetir",
+        Text::New => "nueva",
+        Text::Quit => "salir",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn picks_the_language_of_the_locale() {
+        assert_eq!(language_of("de_DE.UTF-8"), UiLang::De);
+        assert_eq!(language_of("es"), UiLang::Es);
+        assert_eq!(language_of("C.UTF-8"), UiLang::En);
+        assert_eq!(language_of(""), UiLang::En);
+
+        assert_eq!(english(Text::Accuracy), "Accuracy");
+        assert_eq!(german(Text::Accuracy), "Genauigkeit");
+    }
+}
@@ -0,0 +1,41 @@
+use rand::{seq::SliceRandom, Rng};
+
+const SYMBOLS: &[u8] = b"{}[]()<>=|&:;-+*/!?#%^~$@";
+
+/// Pieces of code that come up all the time, practised whole.
+const IDIOMS: [&str; 24] = [
+    "=>", "->", "::", "&&", "||", "!=", "==", "<=", ">=", "+=", "|x|", "&mut", "#[test]", "()?",
+    "Vec<T>", "fn()", "x[i]", "{}", "[]", "()", "<T>", "&[u8]", "Ok(())", "#{}",
+];
+
+/// Picks `n` groups of programming symbols, half of them common idioms and
+/// half random runs of brackets and operators.
+pub fn generate(n: usize, rng: &mut impl Rng) -> Vec<String> {
+    (0..n)
+        .map(|_| {
+            if rng.gen() {
+                IDIOMS.choose(rng).copied().unwrap_or_default().to_string()
+            } else {
+                (0..rng.gen_range(2..=4))
+                    .filter_map(|_| SYMBOLS.choose(rng).copied().map(char::from))
+                    .collect()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn no_blank_groups() {
+        let groups = generate(100, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(groups.len(), 100);
+        assert!(groups
+            .iter()
+            .all(|group| !group.is_empty() && !group.contains(' ')));
+    }
+}
@@ -0,0 +1,162 @@
+use super::{
+    history::History,
+    http::{self, Url},
+    store,
+};
+use crate::args::HistoryStore;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Name of the history in a folder or at a URL ending in `/`.
+const FILE: &str = "history.toml";
+
+/// Where another machine's history is kept for `typers sync`.
+pub enum Remote {
+    /// A file, or a folder to keep it in, shared some other way such as a
+    /// synced drive.
+    Path(PathBuf),
+    /// A folder checked out with git, pulled before and pushed after.
+    Git(PathBuf),
+    /// A plain HTTP URL to get and put the file at, as on a `WebDAV` server.
+    Http(Url),
+}
+
+impl Remote {
+    pub fn parse(remote: &str) -> io::Result<Self> {
+        if remote.starts_with("https://") {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "https isn't supported, sync over plain http on a trusted network or with a shared folder",
+            ));
+        }
+
+        if remote.starts_with("http://") {
+            return http::Url::parse(remote)
+                .map(|url| Self::Http(url.in_folder(FILE)))
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, format!("invalid URL {remote}"))
+                });
+        }
+
+        let path = PathBuf::from(remote);
+
+        if path.join(".git").exists() {
+            Ok(Self::Git(path))
+        } else {
+            Ok(Self::Path(path))
+        }
+    }
+
+    /// Merges the history here with the one at the remote, saving the whole
+    /// of it in both places. Returns how many tests came from the remote and
+    /// how many went to it.
+    pub fn sync(&self, local: &mut History) -> io::Result<(usize, usize)> {
+        if let Self::Git(dir) = self {
+            git(dir, &["pull", "--quiet", "--ff-only"])?;
+        }
+
+        let mut remote = match self {
+            Self::Path(path) | Self::Git(path) => read(&file(path))?,
+            Self::Http(url) => get(url)?,
+        };
+
+        let pulled = local.merge(remote.tests.clone());
+        let pushed = remote.merge(local.tests.clone());
+        local.save()?;
+
+        if pushed == 0 {
+            return Ok((pulled, pushed));
+        }
+
+        let text = store::of(HistoryStore::Toml)
+            .write(&remote)
+            .map_err(io::Error::other)?;
+
+        match self {
+            Self::Path(path) => write(&file(path), &text)?,
+            Self::Git(dir) => {
+                write(&dir.join(FILE), &text)?;
+                git(dir, &["add", FILE])?;
+                git(
+                    dir,
+                    &["commit", "--quiet", "--message", "Sync typers history"],
+                )?;
+                git(dir, &["push", "--quiet"])?;
+            }
+            Self::Http(url) => put(url, &text)?,
+        }
+
+        Ok((pulled, pushed))
+    }
+}
+
+/// The history file at `path`, or in it when it's a folder.
+fn file(path: &Path) -> PathBuf {
+    if path.is_dir() {
+        path.join(FILE)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn read(path: &Path) -> io::Result<History> {
+    match fs::read_to_string(path) {
+        Ok(text) => parse(&text, &path.display().to_string()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+        Err(err) => Err(err),
+    }
+}
+
+fn write(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, text)
+}
+
+fn parse(text: &str, from: &str) -> io::Result<History> {
+    store::of(HistoryStore::Toml).parse(text).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("invalid history at {from}: {err}"),
+        )
+    })
+}
+
+fn git(dir: &Path, args: &[&str]) -> io::Result<()> {
+    let status = Command::new("git").arg("-C").arg(dir).args(args).status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!(
+            "git {} failed in {}",
+            args[0],
+            dir.display()
+        )))
+    }
+}
+
+/// The history at `url`, which is empty until something was put there.
+fn get(url: &Url) -> io::Result<History> {
+    match url.request("GET", None, "")? {
+        (200, body) => parse(&body, &url.to_string()),
+        (404, _) => Ok(History::default()),
+        (status, _) => Err(failed("GET", url, status)),
+    }
+}
+
+fn put(url: &Url, text: &str) -> io::Result<()> {
+    match url.request("PUT", None, text)? {
+        (200..300, _) => Ok(()),
+        (status, _) => Err(failed("PUT", url, status)),
+    }
+}
+
+fn failed(method: &str, url: &Url, status: u16) -> io::Error {
+    io::Error::other(format!("{method} {url} answered {status}"))
+}
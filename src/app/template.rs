@@ -0,0 +1,68 @@
+use super::json;
+use toml::Value;
+
+/// Fills `{name}` placeholders in `template` from `values`, leaving unknown
+/// ones as they are so typos show up in the output.
+pub fn render(template: &str, values: &[(&str, String)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// The line printed on exit when there is no `--format`.
+pub const SUMMARY: &str =
+    "typers · {words} words · {wpm} wpm · {acc}% acc · {consistency}% consistency · {time}s";
+
+/// `values` as a flat JSON object, with the ones that read as numbers left
+/// unquoted.
+pub fn json(values: &[(&str, String)]) -> String {
+    let mut object = String::from("{");
+
+    for (i, (name, value)) in values.iter().enumerate() {
+        if i > 0 {
+            object.push(',');
+        }
+
+        json::write_string(&mut object, name);
+        object.push(':');
+        let value = match (value.parse::<i64>(), value.parse::<f64>()) {
+            (Ok(n), _) => Value::Integer(n),
+            (_, Ok(n)) if n.is_finite() => Value::Float(n),
+            _ => Value::String(value.clone()),
+        };
+        json::write(&mut object, &value);
+    }
+
+    object.push('}');
+    object
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders() {
+        let values = [("wpm", "72".to_string()), ("acc", "98".to_string())];
+        assert_eq!(
+            render("{wpm} wpm / {acc}% {nope}", &values),
+            "72 wpm / 98% {nope}"
+        );
+    }
+
+    #[test]
+    fn writes_json() {
+        let values = [
+            ("wpm", "72".to_string()),
+            ("date", "2026-10-14".to_string()),
+            ("acc", "97.5".to_string()),
+            ("quote", "a\"b\nc\u{1}".to_string()),
+        ];
+        assert_eq!(
+            json(&values),
+            r#"{"wpm":72,"date":"2026-10-14","acc":97.5,"quote":"a\"b\nc\u0001"}"#
+        );
+    }
+}
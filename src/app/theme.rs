@@ -0,0 +1,133 @@
+use crate::args::ThemeName;
+use ratatui::style::{Style, Stylize as RatatuiStylize};
+
+/// Every style the UI draws with, so switching theme restyles everything.
+#[derive(Debug, Clone, Copy)]
+pub struct Theme {
+    pub correct: Style,
+    pub incorrect: Style,
+    /// Right in the end, after being typed wrong and erased.
+    pub corrected: Style,
+    pub pending: Style,
+    /// Laid over the word under the caret.
+    pub current: Style,
+    /// Where the ghost being raced is up to.
+    pub ghost: Style,
+    pub accent: Style,
+    pub selected: Style,
+    pub muted: Style,
+    /// Banners about something that is about to spoil the test.
+    pub warning: Style,
+    /// Bars of the sparkline and rhythm histogram.
+    pub chart: Style,
+    /// The scale given beside a chart.
+    pub axis: Style,
+    /// Accuracies of fingers and keys from worst to best, see [`Theme::heat`].
+    pub heat: [Style; 3],
+}
+
+impl Theme {
+    pub fn new(name: ThemeName) -> Self {
+        match name {
+            // Auto is picked between by the caller, this is only for when
+            // it can't be.
+            ThemeName::Default | ThemeName::Auto => Self {
+                correct: Style::new().white(),
+                incorrect: Style::new().red(),
+                corrected: Style::new().yellow(),
+                pending: Style::new().dim(),
+                current: Style::new().underlined().not_dim(),
+                ghost: Style::new().on_dark_gray(),
+                accent: Style::new().yellow(),
+                selected: Style::new().yellow().bold(),
+                muted: Style::new().dark_gray(),
+                warning: Style::new().black().on_yellow().bold(),
+                chart: Style::new().yellow(),
+                axis: Style::new().dark_gray(),
+                heat: [
+                    Style::new().red(),
+                    Style::new().yellow(),
+                    Style::new().green(),
+                ],
+            },
+            ThemeName::Light => Self {
+                correct: Style::new().black(),
+                incorrect: Style::new().red(),
+                corrected: Style::new().magenta(),
+                pending: Style::new().dark_gray(),
+                current: Style::new().underlined(),
+                ghost: Style::new().on_gray(),
+                accent: Style::new().blue(),
+                selected: Style::new().blue().bold(),
+                muted: Style::new().dark_gray(),
+                warning: Style::new().white().on_red().bold(),
+                chart: Style::new().blue(),
+                axis: Style::new().dark_gray(),
+                heat: [
+                    Style::new().red(),
+                    Style::new().magenta(),
+                    Style::new().blue(),
+                ],
+            },
+            // Tells text apart by attributes alone, for limited color
+            // perception or terminals with few colors.
+            ThemeName::HighContrast => Self {
+                correct: Style::new().bold(),
+                incorrect: Style::new().reversed().underlined(),
+                corrected: Style::new().bold().italic(),
+                pending: Style::new().dim(),
+                current: Style::new().underlined().not_dim(),
+                ghost: Style::new().reversed(),
+                accent: Style::new().bold(),
+                selected: Style::new().reversed(),
+                muted: Style::new().dim(),
+                warning: Style::new().reversed().bold(),
+                chart: Style::new().bold(),
+                axis: Style::new().dim(),
+                heat: [
+                    Style::new().reversed(),
+                    Style::new().underlined(),
+                    Style::new().bold(),
+                ],
+            },
+        }
+    }
+
+    /// The style for a finger or key typed with `accuracy`: the worst stop
+    /// below 90%, the middle one up to 98% and the best above.
+    pub fn heat(&self, accuracy: f64) -> Style {
+        match accuracy {
+            accuracy if accuracy >= 98.0 => self.heat[2],
+            accuracy if accuracy >= 90.0 => self.heat[1],
+            _ => self.heat[0],
+        }
+    }
+}
+
+/// Whether the terminal's background is light, going by a `COLORFGBG` of
+/// `fg;bg`, where the last field is the background's color index.
+pub fn is_light_background(colorfgbg: &str) -> Option<bool> {
+    let background: u8 = colorfgbg.rsplit(';').next()?.parse().ok()?;
+    Some(matches!(background, 7 | 9..=15))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heats_up_with_accuracy() {
+        let theme = Theme::new(ThemeName::Default);
+        assert_eq!(theme.heat(72.0), theme.heat[0]);
+        assert_eq!(theme.heat(90.0), theme.heat[1]);
+        assert_eq!(theme.heat(100.0), theme.heat[2]);
+    }
+
+    #[test]
+    fn reads_the_background() {
+        assert_eq!(is_light_background("15;0"), Some(false));
+        assert_eq!(is_light_background("0;default;15"), Some(true));
+        assert_eq!(is_light_background("0;7"), Some(true));
+        assert_eq!(is_light_background("default"), None);
+    }
+}
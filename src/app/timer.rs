@@ -11,18 +11,40 @@ impl Timer {
         self.start.is_some()
     }
 
-    pub fn start(&mut self) {
-        self.start = Some(Instant::now());
+    pub fn start_at(&mut self, at: Instant) {
+        self.start = Some(at);
+    }
+
+    /// Starts as if `elapsed` had already gone by, to continue a test.
+    pub fn resume(&mut self, elapsed: Duration) {
+        let now = Instant::now();
+        self.start = Some(now.checked_sub(elapsed).unwrap_or(now));
     }
 
     pub fn end(&mut self) {
+        self.end_at(Instant::now());
+    }
+
+    pub fn end_at(&mut self, at: Instant) {
         self.end = Some(
-            self.start
-                .expect("start to have been called before end")
-                .elapsed(),
+            at.saturating_duration_since(self.start.expect("start to have been called before end")),
         );
     }
 
+    /// Time since the start, frozen once ended.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed_at(Instant::now())
+    }
+
+    /// Time from the start to `at`, frozen once ended.
+    pub fn elapsed_at(&self, at: Instant) -> Duration {
+        match (self.start, self.end) {
+            (_, Some(end)) => end,
+            (Some(start), None) => at.saturating_duration_since(start),
+            (None, None) => Duration::ZERO,
+        }
+    }
+
     pub fn duration(&self) -> Duration {
         self.end.expect("end to have been called before duration")
     }
@@ -0,0 +1,119 @@
+use super::keymap::{self, Action, Keymap, Screen};
+use crate::{args::Mode, config::Config, paths::Paths};
+use clap::ValueEnum;
+use std::{fs, io, path::PathBuf};
+
+/// How many pages there are before the calibration test.
+pub const PAGES: usize = 4;
+
+/// Marks the tutorial as seen, so it isn't shown again even if its test
+/// was never finished.
+fn path() -> io::Result<PathBuf> {
+    Ok(Paths::new()?.data.join("tutorial"))
+}
+
+/// Whether typers has never run here: no config saved and nothing in the
+/// data directory.
+pub fn is_first_run() -> bool {
+    let exists = |path: io::Result<PathBuf>| path.is_ok_and(|path| path.exists());
+    !exists(Config::path()) && !exists(Paths::new().map(|paths| paths.data))
+}
+
+pub fn mark_done() -> io::Result<()> {
+    let path = path()?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, "")
+}
+
+/// The title and lines of `page`, the keys being read from `keymap` so
+/// they are the ones that work.
+pub fn page(page: usize, keymap: &Keymap) -> (&'static str, Vec<String>) {
+    let lines = |lines: &[&str]| lines.iter().map(ToString::to_string).collect();
+
+    match page {
+        0 => (
+            "welcome to typers",
+            lines(&[
+                "Type the words on screen as quickly and accurately as you can.",
+                "",
+                "The timer starts on the first key. Mistakes show in red, and backspace takes them back.",
+                "",
+                "The results come up as soon as the last word is typed.",
+            ]),
+        ),
+        1 => {
+            let mut lines = keys(keymap, Screen::Playing, None);
+            lines.push(String::new());
+            lines.push("on the results".to_string());
+            lines.extend(keys(
+                keymap,
+                Screen::Finished,
+                Some(&[Action::Retry, Action::NewTest, Action::Help]),
+            ));
+            ("keys while typing", lines)
+        }
+        2 => {
+            let mut lines: Vec<String> = Mode::value_variants()
+                .iter()
+                .filter_map(ValueEnum::to_possible_value)
+                .map(|mode| {
+                    let help = mode.get_help().map(ToString::to_string).unwrap_or_default();
+                    format!("{:<12}{help}", mode.get_name())
+                })
+                .collect();
+            lines.push(String::new());
+            lines.push("Pick one in the menu, or with --mode.".to_string());
+            ("kinds of test", lines)
+        }
+        _ => (
+            "the results",
+            lines(&[
+                "wpm         words per minute, five characters to a word",
+                "accuracy    the share of keys typed right",
+                "consistency how steady the speed was from word to word",
+                "",
+                "Next, a short test to set the speed to beat.",
+            ]),
+        ),
+    }
+}
+
+/// The keys bound on `screen` and what they do, only for `actions` if
+/// given.
+fn keys(keymap: &Keymap, screen: Screen, actions: Option<&[Action]>) -> Vec<String> {
+    keymap
+        .bindings(screen)
+        .into_iter()
+        .filter(|(_, action)| actions.is_none_or(|actions| actions.contains(action)))
+        .map(|(keys, action)| {
+            let keys: Vec<String> = keys.into_iter().map(keymap::key_name).collect();
+            format!("{:<12}{}", keys.join(" / "), action.description())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_working_keys() {
+        let keymap = Keymap::default();
+        let (_, lines) = page(1, &keymap);
+
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("tab") && line.ends_with("new test")));
+        assert!(lines
+            .iter()
+            .any(|line| line == &format!("{:<12}retry the same words", "r")));
+        assert!(!lines.iter().any(|line| line.ends_with("copy results")));
+
+        let (_, lines) = page(2, &keymap);
+        assert!(lines[0].starts_with("words") && lines[0].ends_with("Common english words"));
+    }
+}
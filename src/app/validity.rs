@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+/// A gap between keystrokes this long is a pause.
+const PAUSE: Duration = Duration::from_secs(3);
+
+/// A gap this long means the typist walked away.
+const AFK: Duration = Duration::from_secs(10);
+
+/// Keystrokes closer together than this can't have been typed by hand.
+const BURST: Duration = Duration::from_millis(2);
+
+/// How many keystrokes in a row that close together count as a paste.
+const PASTE: usize = 5;
+
+/// Erasing everything back to the start this often is restarting in all
+/// but name.
+const RESTARTS: usize = 3;
+
+/// Why a result doesn't reflect steady typing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    Paused,
+    Afk,
+    Pasted,
+    Restarted,
+}
+
+impl Flag {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Paused => "paused",
+            Self::Afk => "afk",
+            Self::Pasted => "pasted",
+            Self::Restarted => "restarted",
+        }
+    }
+}
+
+/// What is off about a test typed at `times`, erased back to the start
+/// `restarts` times along the way.
+pub fn flags(times: &[Duration], restarts: usize) -> Vec<Flag> {
+    let gaps: Vec<Duration> = times
+        .windows(2)
+        .map(|pair| pair[1].saturating_sub(pair[0]))
+        .collect();
+
+    let longest = gaps.iter().max().copied().unwrap_or_default();
+    let mut flags = Vec::new();
+
+    if longest >= AFK {
+        flags.push(Flag::Afk);
+    } else if longest >= PAUSE {
+        flags.push(Flag::Paused);
+    }
+
+    let pasted = gaps
+        .split(|gap| *gap >= BURST)
+        .any(|burst| burst.len() + 1 >= PASTE);
+
+    if pasted {
+        flags.push(Flag::Pasted);
+    }
+
+    if restarts >= RESTARTS {
+        flags.push(Flag::Restarted);
+    }
+
+    flags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn times(ms: &[u64]) -> Vec<Duration> {
+        ms.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn passes_steady_typing() {
+        assert_eq!(flags(&times(&[0, 150, 300, 420, 600]), 0), []);
+    }
+
+    #[test]
+    fn flags_pauses() {
+        assert_eq!(flags(&times(&[0, 150, 3500]), 0), [Flag::Paused]);
+        assert_eq!(flags(&times(&[0, 150, 12_000]), 0), [Flag::Afk]);
+    }
+
+    #[test]
+    fn flags_pastes_and_restarts() {
+        assert_eq!(
+            flags(&times(&[0, 200, 200, 200, 201, 201, 400]), 3),
+            [Flag::Pasted, Flag::Restarted]
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::paths::Paths;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Highest box a term climbs to, reviewed every `2^(MAX_BOX - 1)` days.
+const MAX_BOX: u8 = 6;
+
+/// The terms of a vocabulary file, from the first column of each line of
+/// tab or comma separated values. Comment lines starting with `#`, as in
+/// Anki exports, are skipped.
+pub fn read(path: &Path) -> io::Result<Vec<String>> {
+    Ok(parse(&fs::read_to_string(path)?))
+}
+
+fn parse(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let separator = if line.contains('\t') { '\t' } else { ',' };
+            let term = line.split(separator).next()?;
+            let term = term.split_whitespace().collect::<Vec<_>>().join(" ");
+            (!term.is_empty()).then_some(term)
+        })
+        .filter(|term| seen.insert(term.clone()))
+        .collect()
+}
+
+/// Where a term is in its Leitner box schedule.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+struct Card {
+    #[serde(rename = "box")]
+    level: u8,
+    /// Day since the Unix epoch it is next due on.
+    due: i64,
+}
+
+/// When every term practiced so far comes up next, kept in the data
+/// directory so it carries over between runs.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+pub struct Schedule(BTreeMap<String, Card>);
+
+impl Schedule {
+    fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.data.join("vocab.toml"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(fs::read_to_string)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, toml::to_string(self).map_err(io::Error::other)?)
+    }
+
+    /// Up to `n` of `terms`, the most overdue first, then ones never
+    /// practiced, then the ones coming up soonest so there's always
+    /// something to type.
+    pub fn pick(&self, terms: &[String], today: i64, n: usize) -> Vec<String> {
+        let mut ranked: Vec<(i64, usize, &String)> = terms
+            .iter()
+            .enumerate()
+            .map(|(i, term)| match self.0.get(term) {
+                Some(card) if card.due <= today => (card.due - today, i, term),
+                Some(card) => (card.due - today + 1, i, term),
+                None => (1, i, term),
+            })
+            .collect();
+
+        ranked.sort_unstable();
+        ranked
+            .into_iter()
+            .take(n)
+            .map(|(.., term)| term.clone())
+            .collect()
+    }
+
+    /// Moves `term` up a box when typed right, due again after twice as
+    /// long, or back to the first box and due today when typed wrong.
+    pub fn review(&mut self, term: &str, correct: bool, today: i64) {
+        let card = self.0.entry(term.to_string()).or_insert(Card {
+            level: 0,
+            due: today,
+        });
+
+        if correct {
+            card.level = (card.level + 1).min(MAX_BOX);
+            card.due = today + (1 << (card.level - 1));
+        } else {
+            card.level = 1;
+            card.due = today;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(terms: &[&str]) -> Vec<String> {
+        terms.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn reads_tsv_and_csv() {
+        let text = "#separator:tab\nla  maison\thouse\nle chien,dog\n\nle chien,hound\n";
+        assert_eq!(parse(text), terms(&["la maison", "le chien"]));
+    }
+
+    #[test]
+    fn schedules_terms() {
+        let all = terms(&["un", "deux", "trois"]);
+        let mut schedule = Schedule::default();
+
+        schedule.review("un", true, 100);
+        schedule.review("deux", false, 100);
+        assert_eq!(schedule.pick(&all, 100, 2), terms(&["deux", "trois"]));
+        assert_eq!(schedule.pick(&all, 101, 3), terms(&["deux", "un", "trois"]));
+
+        schedule.review("un", true, 101);
+        assert_eq!(schedule.0["un"].due, 103);
+    }
+}
@@ -0,0 +1,36 @@
+use super::http::Url;
+use std::{
+    io,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+/// Tries before giving up on a result.
+const ATTEMPTS: u32 = 4;
+
+/// Wait after the first failed try, doubling after each one after it.
+const BACKOFF: Duration = Duration::from_secs(1);
+
+/// Posts `json` to `url` once, which the server has to take with a 2xx.
+fn post(url: &Url, json: &str) -> io::Result<()> {
+    match url.request("POST", Some("application/json"), json)? {
+        (200..300, _) => Ok(()),
+        (status, _) => Err(io::Error::other(format!("server answered {status}"))),
+    }
+}
+
+/// Posts `json` to `url` in the background, trying again a few times with
+/// longer and longer waits in between should it fail.
+pub fn send(url: Url, json: String) -> JoinHandle<()> {
+    thread::spawn(move || {
+        for attempt in 0..ATTEMPTS {
+            if post(&url, &json).is_ok() {
+                return;
+            }
+
+            if attempt + 1 < ATTEMPTS {
+                thread::sleep(BACKOFF * 2u32.pow(attempt));
+            }
+        }
+    })
+}
@@ -1,12 +1,140 @@
-use rand::seq::IteratorRandom;
+use super::keyboard::{self, Finger};
+use crate::args::Hand;
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
+use regex::Regex;
 
 const WORDS: &str = include_str!("../../words/en1000");
 
-pub struct Words;
+#[derive(Debug, Clone)]
+pub enum Filter {
+    MinLen(usize),
+    MaxLen(usize),
+    Regex(Regex),
+    Alphabetic,
+    Charset(Vec<char>),
+    Hand(Hand),
+    /// Words using the finger at least once.
+    Finger(Finger),
+}
+
+impl Filter {
+    fn matches(&self, word: &str) -> bool {
+        match self {
+            Self::MinLen(n) => word.chars().count() >= *n,
+            Self::MaxLen(n) => word.chars().count() <= *n,
+            Self::Regex(regex) => regex.is_match(word),
+            Self::Alphabetic => word.chars().all(char::is_alphabetic),
+            Self::Charset(set) => word.chars().all(|c| set.contains(&c)),
+            Self::Hand(hand) => word
+                .chars()
+                .all(|c| keyboard::finger(c).map(Finger::hand) == Some(*hand)),
+            Self::Finger(finger) => word.chars().any(|c| keyboard::finger(c) == Some(*finger)),
+        }
+    }
+}
+
+/// Every word in the list, as written there.
+pub fn all() -> impl Iterator<Item = &'static str> {
+    WORDS.lines()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Transform {
+    Lowercase,
+    RandomCase,
+}
+
+impl Transform {
+    fn apply(self, word: &str) -> String {
+        match self {
+            Self::Lowercase => word.to_lowercase(),
+            Self::RandomCase => {
+                let mut rng = rand::thread_rng();
+
+                word.chars()
+                    .map(|c| {
+                        if rng.gen() {
+                            c.to_ascii_uppercase()
+                        } else {
+                            c.to_ascii_lowercase()
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Words {
+    filters: Vec<Filter>,
+    transforms: Vec<Transform>,
+    modifiers: Vec<Transform>,
+}
 
 impl Words {
-    pub fn generate(n: usize) -> Vec<&'static str> {
-        WORDS.lines().choose_multiple(&mut rand::thread_rng(), n)
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    pub fn transform(mut self, transform: Transform) -> Self {
+        self.transforms.push(transform);
+        self
+    }
+
+    /// Modifiers run on every picked word after filtering, so random ones
+    /// differ between repeats of the same word.
+    pub fn modifier(mut self, modifier: Transform) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    /// Transforms run before filters, so filters see the words as they will
+    /// be typed.
+    fn pool(&self) -> impl Iterator<Item = String> + '_ {
+        WORDS
+            .lines()
+            .map(|word| {
+                self.transforms
+                    .iter()
+                    .fold(word.to_string(), |word, transform| transform.apply(&word))
+            })
+            .filter(|word| self.filters.iter().all(|filter| filter.matches(word)))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pool().next().is_none()
+    }
+
+    /// Picks `n` distinct words, repeating words only when fewer than `n`
+    /// pass the filters.
+    pub fn generate(&self, n: usize) -> Vec<String> {
+        self.generate_with(n, &mut rand::thread_rng())
+    }
+
+    /// Like [`Words::generate`], picking with `rng` so a seeded one gives
+    /// the same words every time.
+    pub fn generate_with(&self, n: usize, rng: &mut impl Rng) -> Vec<String> {
+        let pool: Vec<_> = self.pool().collect();
+
+        let words = if pool.len() >= n {
+            pool.into_iter().choose_multiple(rng, n)
+        } else {
+            (0..n).filter_map(|_| pool.choose(rng).cloned()).collect()
+        };
+
+        words
+            .into_iter()
+            .map(|word| {
+                self.modifiers
+                    .iter()
+                    .fold(word, |word, modifier| modifier.apply(&word))
+            })
+            .collect()
     }
 }
 
@@ -17,7 +145,70 @@ mod tests {
     #[test]
     fn generates_exact_words() {
         const LENGTH: usize = 50;
-        let words = Words::generate(LENGTH);
+        let words = Words::default().generate(LENGTH);
         assert_eq!(words.len(), LENGTH);
     }
+
+    #[test]
+    fn applies_every_filter() {
+        let words = Words::default()
+            .filter(Filter::MinLen(3))
+            .filter(Filter::MaxLen(5))
+            .filter(Filter::Regex(Regex::new("th").unwrap()))
+            .generate(50);
+
+        assert_eq!(words.len(), 50);
+        assert!(words
+            .iter()
+            .all(|word| (3..=5).contains(&word.len()) && word.contains("th")));
+    }
+
+    #[test]
+    fn transforms_before_filtering() {
+        let words = Words::default()
+            .transform(Transform::Lowercase)
+            .filter(Filter::Charset(vec!['i']))
+            .generate(3);
+
+        assert_eq!(words, ["i", "i", "i"]);
+    }
+
+    #[test]
+    fn random_case_keeps_letters() {
+        let words = Words::default()
+            .filter(Filter::Charset(vec!['a']))
+            .modifier(Transform::RandomCase)
+            .generate(20);
+
+        assert_eq!(words.len(), 20);
+        assert!(words.iter().all(|word| word.eq_ignore_ascii_case("a")));
+    }
+
+    #[test]
+    fn same_seed_same_words() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let words = Words::default();
+        let a = words.generate_with(10, &mut StdRng::seed_from_u64(20_261_014));
+        let b = words.generate_with(10, &mut StdRng::seed_from_u64(20_261_014));
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn one_handed_words() {
+        let words = Words::default()
+            .filter(Filter::Hand(Hand::Left))
+            .generate(20);
+
+        assert!(words.iter().all(|word| word
+            .chars()
+            .all(|c| "qwertasdfgzxcvbQWERTASDFGZXCVB".contains(c))));
+    }
+
+    #[test]
+    fn empty_when_nothing_matches() {
+        let words = Words::default().filter(Filter::MinLen(100));
+        assert!(words.is_empty());
+        assert!(words.generate(10).is_empty());
+    }
 }
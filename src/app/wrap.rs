@@ -0,0 +1,185 @@
+use ratatui::text::{Line, Span};
+use std::ops::Range;
+use unicode_width::UnicodeWidthChar;
+
+/// How many terminal columns `c` takes up, two for most CJK characters.
+pub fn width(c: char) -> usize {
+    c.width().unwrap_or_default()
+}
+
+/// Greedily breaks `text` into lines at most `width` columns wide, breaking
+/// after spaces where possible. A space landing just past the edge hangs off
+/// the end of its line instead of starting the next one. Every character is
+/// followed by `spacing` blank columns.
+pub fn lines(text: &[char], width: usize, spacing: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let cell = |c| self::width(c) + spacing;
+    let mut lines = Vec::new();
+    let mut start = 0;
+    let mut column = 0;
+    let mut space = None;
+    let mut i = 0;
+
+    while i < text.len() {
+        if i > start && column + cell(text[i]) > width {
+            let end = match space {
+                _ if text[i] == ' ' => i + 1,
+                Some(space) => space + 1,
+                None => i,
+            };
+
+            lines.push(start..end);
+            start = end;
+            column = 0;
+            space = None;
+            i = end;
+            continue;
+        }
+
+        if text[i] == ' ' {
+            space = Some(i);
+        }
+
+        column += cell(text[i]);
+        i += 1;
+    }
+
+    if start < text.len() || lines.is_empty() {
+        lines.push(start..text.len());
+    }
+
+    lines
+}
+
+/// The line and column of character `index` within `lines` of `text`, with
+/// `spacing` as given to [`lines`].
+pub fn position(
+    text: &[char],
+    lines: &[Range<usize>],
+    index: usize,
+    spacing: usize,
+) -> (usize, usize) {
+    let line = lines
+        .iter()
+        .rposition(|line| line.start <= index)
+        .unwrap_or_default();
+
+    let start = lines[line].start;
+    let column = text[start..index.min(text.len())]
+        .iter()
+        .map(|c| width(*c) + spacing)
+        .sum();
+
+    (line, column)
+}
+
+/// The word the character at `index` belongs to, or the one just before it
+/// when `index` is on a space.
+pub fn word_at(text: &[char], index: usize) -> Range<usize> {
+    let index = index.min(text.len());
+    let start = text[..index]
+        .iter()
+        .rposition(|c| *c == ' ')
+        .map_or(0, |space| space + 1);
+    let end = text[index..]
+        .iter()
+        .position(|c| *c == ' ')
+        .map_or(text.len(), |space| index + space);
+
+    start..end
+}
+
+/// Puts `spacing` blank columns after every span, unstyled so that
+/// highlights don't run into the gaps.
+pub fn spaced<'a>(spans: impl IntoIterator<Item = Span<'a>>, spacing: usize) -> Vec<Span<'a>> {
+    if spacing == 0 {
+        return spans.into_iter().collect();
+    }
+
+    let gap = " ".repeat(spacing);
+
+    spans
+        .into_iter()
+        .flat_map(|span| [span, Span::raw(gap.clone())])
+        .collect()
+}
+
+/// Breaks styled text into lines as [`lines`] would.
+pub fn wrap<'a>(line: &Line<'a>, width: usize) -> Vec<Line<'a>> {
+    let cells: Vec<_> = line
+        .spans
+        .iter()
+        .flat_map(|span| span.content.chars().map(|c| (c, span.style)))
+        .collect();
+
+    let chars: Vec<char> = cells.iter().map(|(c, _)| *c).collect();
+
+    lines(&chars, width, 0)
+        .into_iter()
+        .map(|range| {
+            cells[range]
+                .iter()
+                .map(|(c, style)| Span::styled(c.to_string(), *style))
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(s: &str) -> Vec<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn breaks_after_spaces() {
+        assert_eq!(lines(&chars("ab cd ef"), 4, 0), [0..3, 3..6, 6..8]);
+    }
+
+    #[test]
+    fn hangs_space_at_the_edge() {
+        assert_eq!(lines(&chars("ab cd ef"), 5, 0), [0..6, 6..8]);
+    }
+
+    #[test]
+    fn splits_long_words() {
+        assert_eq!(lines(&chars("abcdefg"), 3, 0), [0..3, 3..6, 6..7]);
+    }
+
+    #[test]
+    fn finds_positions() {
+        let lines = lines(&chars("ab cd ef"), 4, 0);
+        assert_eq!(position(&chars("ab cd ef"), &lines, 0, 0), (0, 0));
+        assert_eq!(position(&chars("ab cd ef"), &lines, 4, 0), (1, 1));
+        assert_eq!(position(&chars("ab cd ef"), &lines, 8, 0), (2, 2));
+    }
+
+    #[test]
+    fn counts_wide_characters_twice() {
+        let text = chars("日本 語");
+        let lines = lines(&text, 4, 0);
+        assert_eq!(lines, [0..3, 3..4]);
+        assert_eq!(position(&text, &lines, 1, 0), (0, 2));
+        assert_eq!(position(&text, &lines, 4, 0), (1, 2));
+    }
+
+    #[test]
+    fn finds_the_word_at_the_caret() {
+        let text = chars("ab cd");
+        assert_eq!(word_at(&text, 0), 0..2);
+        assert_eq!(word_at(&text, 2), 0..2);
+        assert_eq!(word_at(&text, 4), 3..5);
+        assert_eq!(word_at(&text, 5), 3..5);
+    }
+
+    #[test]
+    fn counts_letter_spacing() {
+        let text = chars("ab cd");
+        let lines = lines(&text, 6, 1);
+        assert_eq!(lines, [0..3, 3..5]);
+        assert_eq!(position(&text, &lines, 2, 1), (0, 4));
+        assert_eq!(position(&text, &lines, 4, 1), (1, 2));
+    }
+}
@@ -1,7 +1,1041 @@
-use clap::Parser;
+use clap::{
+    builder::RangedU64ValueParser, error::ErrorKind, Args as ClapArgs, CommandFactory, Parser,
+    Subcommand, ValueEnum,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{convert::Infallible, num::NonZeroUsize, path::PathBuf, str::FromStr};
+pub use typers::{Grace, WpmFormula};
 
-#[derive(Parser, Debug, Clone, Copy)]
+/// Most words a test can have.
+pub const MAX_WORDS: u64 = 5000;
+
+/// Most lines `--lines-ahead` shows after the caret's.
+pub const MAX_LINES_AHEAD: u8 = 5;
+
+/// Most blank columns `--letter-spacing` draws after a letter.
+pub const MAX_LETTER_SPACING: u64 = 4;
+
+/// Longest an `--endurance` run can go on, in minutes.
+pub const MAX_ENDURANCE: u64 = 600;
+
+/// A path given to read from, checked before the terminal is taken over so
+/// a typo is reported without starting a test.
+fn existing_file(path: &str) -> Result<PathBuf, String> {
+    let path = PathBuf::from(path);
+
+    if path.is_file() {
+        Ok(path)
+    } else if path.exists() {
+        Err(format!("{} isn't a file", path.display()))
+    } else {
+        Err(format!("there's no file at {}", path.display()))
+    }
+}
+
+fn profile_name(name: &str) -> Result<String, String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(name.to_string())
+    } else {
+        Err("a profile is named with letters, digits, - and _".to_string())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Charset {
+    LettersOnly,
+    Lowercase,
+    Allowed(Vec<char>),
+}
+
+impl FromStr for Charset {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "letters-only" => Self::LettersOnly,
+            "lowercase" => Self::Lowercase,
+            set => Self::Allowed(set.chars().collect()),
+        })
+    }
+}
+
+/// Speed and accuracy to reach in a number of tests in a row, written as
+/// `WPM/ACC/TESTS`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Goal {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub tests: NonZeroUsize,
+}
+
+impl Goal {
+    #[must_use]
+    pub fn is_met(&self, wpm: f64, accuracy: f64) -> bool {
+        wpm >= self.wpm && accuracy >= self.accuracy
+    }
+}
+
+impl FromStr for Goal {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected WPM/ACC/TESTS such as 60/95/3, got {s}");
+
+        let [wpm, accuracy, tests] = s.split('/').collect::<Vec<_>>()[..] else {
+            return Err(invalid());
+        };
+
+        Ok(Self {
+            wpm: wpm.parse().map_err(|_| invalid())?,
+            accuracy: accuracy.parse().map_err(|_| invalid())?,
+            tests: tests.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Sprints of typing with rests in between, written as
+/// `SPRINT/REST/ROUNDS` in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Intervals {
+    pub sprint: u64,
+    pub rest: u64,
+    pub rounds: NonZeroUsize,
+}
+
+impl FromStr for Intervals {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("expected SPRINT/REST/ROUNDS such as 15/15/8, got {s}");
+
+        let [sprint, rest, rounds] = s.split('/').collect::<Vec<_>>()[..] else {
+            return Err(invalid());
+        };
+
+        let sprint = sprint.parse().map_err(|_| invalid())?;
+
+        if sprint == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            sprint,
+            rest: rest.parse().map_err(|_| invalid())?,
+            rounds: rounds.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Funbox {
+    /// Randomize the case of every letter
+    RandomCase,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    /// Common english words
+    Words,
+    /// Digit runs, amounts and phone numbers for ten-key practice
+    Numbers,
+    /// Brackets, operators and code idioms
+    Symbols,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cursor {
+    Bar,
+    Block,
+    Underline,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    Default,
+    /// Bold, underline and reverse instead of color
+    HighContrast,
+    /// Dark text for light backgrounds
+    Light,
+    /// The dark or light theme from the config, by the background the
+    /// terminal reports in `COLORFGBG`
+    Auto,
+}
+
+/// Something happening that can ring the bell or notify the desktop.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Alert {
+    /// A test typed to the end
+    Finish,
+    /// A test stopped by `--max-time`
+    TimeUp,
+    /// A break suggested by `--break-after`
+    Break,
+    /// The summary of a session or playlist
+    Session,
+}
+
+/// A way of giving typing speed.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Unit {
+    /// Words per minute, counted by `--wpm-formula`
+    Wpm,
+    /// Characters per minute
+    Cpm,
+    /// Keystrokes per second, backspaces included
+    Kps,
+}
+
+/// What `typers export-result` writes a results card as.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardFormat {
+    Png,
+    Svg,
+    /// Text colored with escape codes, to print in a terminal
+    Ansi,
+}
+
+/// A panel of detail under the results.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Panel {
+    /// The text as typed, with mistakes marked
+    Review,
+    /// The speed of each word
+    Words,
+    /// Gaps between keys and the longest pauses
+    Rhythm,
+    /// The speed from start to end of an `--endurance` run
+    Fatigue,
+    /// The accuracy of each finger
+    Fingers,
+    /// The accuracy of each digit or symbol, in those tests
+    Keys,
+}
+
+impl Panel {
+    pub const ALL: [Self; 6] = [
+        Self::Review,
+        Self::Words,
+        Self::Rhythm,
+        Self::Fatigue,
+        Self::Fingers,
+        Self::Keys,
+    ];
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeymapPreset {
+    Default,
+    /// hjkl to move through menus
+    Vim,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Glyphs {
+    /// ASCII on terminals and locales that look like they can't do better
+    Auto,
+    Unicode,
+    /// ASCII borders and marks, the eight basic colors and the terminal's
+    /// own cursor
+    Ascii,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum UiLang {
+    /// The language of the locale, or English without a catalogue for it
+    Auto,
+    En,
+    De,
+    Es,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Start {
+    /// Start the timer on the first key typed
+    Keypress,
+    /// Start the timer on Enter
+    Enter,
+    /// Show the text until any key is pressed, starting the timer without
+    /// typing that key
+    AnyKey,
+    /// Start the timer after counting down three seconds
+    Countdown,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Spaces {
+    /// Every space typed counts, extra ones as mistakes
+    Strict,
+    /// Ignore spaces typed at the start or after another space, where the
+    /// text has none
+    Lenient,
+}
+
+/// How the history file is kept, set in the config.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum HistoryStore {
+    /// `history.toml`, easy to read and edit by hand
+    Toml,
+    /// `history.jsonl`, an object on each line, quicker to read back once
+    /// there are many tests
+    JsonLines,
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Align {
+    Left,
+    /// Center every line on its own
+    Center,
+}
+
+/// Which tests of the history a report covers.
+#[derive(ClapArgs, Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    /// Only tests tagged with this label, or with all of them when given
+    /// more than once
+    #[arg(long, value_name = "LABEL")]
+    pub tag: Vec<String>,
+
+    /// Only the first try at each day's challenge
+    #[arg(long)]
+    pub daily: bool,
+
+    /// Only tests without pauses, pasting or restarts
+    #[arg(long)]
+    pub valid: bool,
+
+    /// Only tests with this in their mode, such as `words 24`
+    #[arg(long = "mode", value_name = "MODE")]
+    pub mode: Option<String>,
+
+    /// Only tests with a note saying this, such as `tired`
+    #[arg(long, value_name = "TEXT")]
+    pub note: Option<String>,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Print where typers keeps its files
+    Paths,
+    /// Take today's challenge, the same words for everyone on a given UTC day
+    Daily,
+    /// Measure key delivery, redraw time and key repeat in this terminal
+    Diagnose,
+    /// Show which keys register while many are held down at once, and the
+    /// most that did
+    Rollover,
+    /// Save the settings and everything in the data directory to one file
+    Backup { path: PathBuf },
+    /// Put back the files saved by `backup`, replacing those already there
+    Restore {
+        #[arg(value_parser = existing_file)]
+        path: PathBuf,
+    },
+    /// Race someone else running typers, connected directly over TCP
+    Duel {
+        /// Wait for the opponent to connect on this port, and pick the words
+        #[arg(
+            long,
+            value_name = "PORT",
+            required_unless_present = "connect",
+            conflicts_with = "connect"
+        )]
+        listen: Option<u16>,
+
+        /// Connect to an opponent waiting at this address
+        #[arg(long, value_name = "HOST:PORT")]
+        connect: Option<String>,
+
+        /// What the opponent sees you called, your user name by default
+        #[arg(long, value_name = "NAME")]
+        name: Option<String>,
+    },
+    /// Take the challenge for a code of three words, such as
+    /// river-stone-apple, or a new one with a code to pass on
+    Challenge { code: Vec<String> },
+    /// Run the tests of a playlist from the config in turn, ending with a
+    /// summary of them all
+    Playlist { name: String },
+    /// Report on every finished test so far, with the words that were
+    /// slowest and most often typed wrong
+    Stats {
+        #[command(flatten)]
+        filter: HistoryFilter,
+    },
+    /// Sum up the last week or month of tests, in plain text to pass on, as
+    /// against the one before
+    Report {
+        /// The last seven days, the default
+        #[arg(long, conflicts_with = "month")]
+        week: bool,
+
+        /// The last thirty days
+        #[arg(long)]
+        month: bool,
+
+        #[command(flatten)]
+        filter: HistoryFilter,
+    },
+    /// Chart the average speed of each day, from the history
+    Graph {
+        /// How many days back to go, up to a year
+        #[arg(
+            long,
+            default_value_t = 30,
+            value_parser = RangedU64ValueParser::<u64>::new().range(1..=366)
+        )]
+        days: u64,
+
+        #[command(flatten)]
+        filter: HistoryFilter,
+    },
+    /// Look through the finished tests, sorting, deleting and typing them
+    /// again
+    History {
+        #[command(subcommand)]
+        action: Option<HistoryAction>,
+    },
+    /// Show the achievements unlocked so far, and what the rest take
+    Achievements,
+    /// Rank the profiles on this machine by their best speed in each mode
+    Leaderboard,
+    /// Show how the speed to reach by a day is coming along, from the trend
+    /// in the history
+    Goal {
+        #[command(subcommand)]
+        action: Option<GoalAction>,
+    },
+    /// Add the tests exported from another typing site to the history
+    Import {
+        #[command(subcommand)]
+        from: ImportFrom,
+    },
+    /// Merge the history with the one kept for another machine, so both
+    /// have every test
+    Sync {
+        /// A shared file or folder, a folder checked out with git to pull and
+        /// push, or an http:// URL on a server that takes the file put
+        /// there
+        #[arg(long)]
+        remote: String,
+    },
+    /// Show two tests in the history side by side, by their ids or the
+    /// start of them
+    Compare { a: String, b: String },
+    /// Write the results card of a test in the history to a file, with its
+    /// speed graph, to share
+    ExportResult {
+        /// The test to export, by its id or the start of it, or else the last
+        /// one finished
+        id: Option<String>,
+
+        #[arg(long, value_enum, default_value_t = CardFormat::Svg)]
+        format: CardFormat,
+
+        /// Where to write the card, instead of the data directory
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Which tests to pick the last one from, when no id is given
+        #[command(flatten)]
+        filter: HistoryFilter,
+    },
+    /// Score made-up tests typed at a set speed and accuracy, and show how
+    /// the results spread
+    Simulate {
+        /// Speed the keystrokes are spaced for
+        #[arg(long, default_value_t = 80.0)]
+        wpm: f64,
+
+        /// Share of characters typed right, the rest being wrong letters
+        #[arg(long, default_value_t = 95.0)]
+        accuracy: f64,
+
+        #[arg(long, default_value_t = NonZeroUsize::new(100).unwrap())]
+        runs: NonZeroUsize,
+
+        /// Seed for the words and keystrokes, to get the same results again
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum HistoryAction {
+    /// Take every test out of the history
+    Clear,
+    /// Take a test out of the history, by its id or the start of it
+    Delete { id: String },
+    /// Take out the tests finished before a UTC day, such as 2026-01-31
+    Prune {
+        #[arg(long, value_name = "DATE")]
+        before: String,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum GoalAction {
+    /// Set the speed to reach and the UTC day to reach it by
+    Set {
+        #[arg(long)]
+        wpm: f64,
+
+        #[arg(long, value_name = "DATE")]
+        by: String,
+    },
+    /// Stop tracking the goal
+    Clear,
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ImportFrom {
+    /// The results CSV downloaded from the account page of Monkeytype
+    Monkeytype {
+        #[arg(value_parser = existing_file)]
+        path: PathBuf,
+    },
+}
+
+#[derive(Parser, Debug, Clone)]
+#[expect(clippy::struct_excessive_bools)]
 pub struct Args {
-    #[arg(short, long, default_value_t = 24)]
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Words to practice instead of generated ones, such as
+    /// `typers -- their there they're`
+    #[arg(
+        value_name = "TEXT",
+        conflicts_with_all = ["clipboard", "file", "generator", "ghost"]
+    )]
+    pub text: Vec<String>,
+
+    /// How many words to type, from 1 to 5000
+    #[arg(
+        short,
+        long,
+        default_value_t = 24,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..=MAX_WORDS)
+    )]
     pub words: usize,
+
+    /// What kind of text to generate
+    #[arg(long, value_enum, default_value_t = Mode::Words)]
+    pub mode: Mode,
+
+    /// Only use words with at least this many characters
+    #[arg(long)]
+    pub min_word_len: Option<usize>,
+
+    /// Only use words with at most this many characters
+    #[arg(long)]
+    pub max_word_len: Option<usize>,
+
+    /// Only use words matching this regex
+    #[arg(long, value_parser = Regex::new)]
+    pub word_filter: Option<Regex>,
+
+    /// Only use words typed entirely with one hand on a QWERTY keyboard
+    #[arg(long, value_enum)]
+    pub hand: Option<Hand>,
+
+    /// Restrict the characters used: `letters-only`, `lowercase`, or an
+    /// explicit set of allowed characters
+    #[arg(long)]
+    pub charset: Option<Charset>,
+
+    /// Fun modifiers applied to the generated words, can be repeated
+    #[arg(long, value_enum)]
+    pub funbox: Vec<Funbox>,
+
+    /// When the timer starts, typing being ignored until then
+    #[arg(long, value_enum, default_value_t = Start::Keypress)]
+    pub start: Start,
+
+    /// Show the text for this many seconds, then type it from memory
+    #[arg(long, value_name = "SECS")]
+    pub memory: Option<u64>,
+
+    /// Move the word count up a step after every test at least this
+    /// accurate and down a step otherwise, picking up where it was left
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        conflicts_with_all = ["clipboard", "file", "generator", "ghost"]
+    )]
+    pub ladder: Option<u8>,
+
+    /// Start over on new words when accuracy falls below this within the
+    /// first --restart-within words
+    #[arg(
+        long,
+        value_name = "PERCENT",
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        conflicts_with_all = ["versus", "roster"]
+    )]
+    pub restart_below: Option<u8>,
+
+    /// How many words into a test --restart-below watches the accuracy
+    #[arg(
+        long,
+        value_name = "WORDS",
+        default_value_t = 5,
+        requires = "restart_below"
+    )]
+    pub restart_within: usize,
+
+    /// Stop a test that runs longer than this, marking it incomplete
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_time: Option<u64>,
+
+    /// Suggest a break after typing for this long, counting the time until
+    /// there's a gap of five minutes between tests
+    #[arg(long, value_name = "MINS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub break_after: Option<u64>,
+
+    /// Scroll the text through a fixed caret on a single line
+    #[arg(long)]
+    pub tape: bool,
+
+    /// Write the test as plain lines of text instead of drawing a screen,
+    /// for screen readers
+    #[arg(long)]
+    pub screen_reader: bool,
+
+    /// Show the word being typed in large letters above the text
+    #[arg(long)]
+    pub big_word: bool,
+
+    /// Draw nothing but the text and caret, filling the terminal from the
+    /// top left, for small panes
+    #[arg(long)]
+    pub minimal: bool,
+
+    /// Let held keys repeat while typing a test, where terminals report
+    /// repeats apart from presses
+    #[arg(long)]
+    pub key_repeat: bool,
+
+    /// Show live WPM, accuracy and error count while typing
+    #[arg(long)]
+    pub live_stats: bool,
+
+    /// Draw the speed over the last 15 seconds as bars under the text
+    #[arg(long)]
+    pub sparkline: bool,
+
+    /// Hide the live stats, sparkline, status bar and error flash while
+    /// typing, bringing them back on a pause and at the end
+    #[arg(long)]
+    pub focus: bool,
+
+    /// Start with a short warm-up that isn't counted, tab skips it
+    #[arg(long)]
+    pub warmup: bool,
+
+    /// Run this many tests back to back, then show a summary of them
+    #[arg(long, value_name = "N")]
+    pub tests: Option<NonZeroUsize>,
+
+    /// Train in rounds of timed sprints with rests in between, such as
+    /// 15/15/8, then show how each round went
+    #[arg(
+        long,
+        value_name = "SPRINT/REST/ROUNDS",
+        conflicts_with_all = ["tests", "max_time", "roster"]
+    )]
+    pub intervals: Option<Intervals>,
+
+    /// Type for this many minutes on end, then chart how well the speed
+    /// held up
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        value_parser = clap::value_parser!(u64).range(1..=MAX_ENDURANCE),
+        conflicts_with_all = ["tests", "max_time", "intervals", "roster"]
+    )]
+    pub endurance: Option<u64>,
+
+    /// Race a friend on one keyboard, taking turns on the same words
+    #[arg(long, conflicts_with_all = ["tests", "intervals"])]
+    pub versus: bool,
+
+    /// Have the students named in this file, one per line, take turns on
+    /// the same words, then rank them
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_parser = existing_file,
+        conflicts_with_all = ["tests", "versus"]
+    )]
+    pub roster: Option<PathBuf>,
+
+    /// Race --versus as a series of this many rounds, won by whoever takes
+    /// most of them
+    #[arg(long, value_name = "ROUNDS", requires = "versus")]
+    pub best_of: Option<NonZeroUsize>,
+
+    /// Practice the text currently in the clipboard
+    #[arg(long)]
+    pub clipboard: bool,
+
+    /// Shell command printing the words to practice, run for every test with
+    /// the number of words wanted in `TYPERS_WORDS`
+    #[arg(long, value_name = "COMMAND", conflicts_with_all = ["clipboard", "file"])]
+    pub generator: Option<String>,
+
+    /// Practice the text in a file, such as one saved from the results screen
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_parser = existing_file,
+        conflicts_with = "clipboard"
+    )]
+    pub file: Option<PathBuf>,
+
+    /// Type through the whole file a test's worth of words at a time,
+    /// picking up where the last test left off
+    #[arg(long, requires = "file")]
+    pub chunks: bool,
+
+    /// Drill the terms in the first column of a TSV or CSV file, such as an
+    /// Anki export, bringing back the ones typed wrong sooner
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_parser = existing_file,
+        conflicts_with_all = ["clipboard", "file", "generator", "text"]
+    )]
+    pub vocab: Option<PathBuf>,
+
+    /// Race a ghost saved with `g` on the results screen, typing its words
+    /// while its caret follows the recorded keystrokes
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_parser = existing_file,
+        conflicts_with_all = ["clipboard", "file", "generator", "versus"]
+    )]
+    pub ghost: Option<PathBuf>,
+
+    /// Race a rival typing a little faster than your last tests of the same
+    /// mode, tuned from the history again before each test
+    #[arg(long, conflicts_with_all = ["ghost", "versus"])]
+    pub rival: bool,
+
+    /// Save a certificate once this many tests in a row reach the speed and
+    /// accuracy, such as 60/95/3
+    #[arg(long, value_name = "WPM/ACC/TESTS")]
+    pub certify: Option<Goal>,
+
+    /// Where certificates from --certify are saved, certificate.txt in the
+    /// data directory by default
+    #[arg(long, value_name = "PATH", requires = "certify")]
+    pub certificate: Option<PathBuf>,
+
+    /// Where `w` on the results screen saves the words, saved.txt in the data
+    /// directory by default
+    #[arg(long, value_name = "PATH")]
+    pub save: Option<PathBuf>,
+
+    /// Where `e` on the results screen exports a replay page, replay.html in
+    /// the data directory by default
+    #[arg(long, value_name = "PATH")]
+    pub replay: Option<PathBuf>,
+
+    /// Pick options from a menu before starting, the default when no
+    /// arguments are given
+    #[arg(long)]
+    pub menu: bool,
+
+    /// Shape of the caret
+    #[arg(long, value_enum, default_value_t = Cursor::Bar)]
+    pub cursor: Cursor,
+
+    /// Don't blink the caret
+    #[arg(long)]
+    pub steady_cursor: bool,
+
+    /// Move the caret straight to the next cell instead of gliding there
+    #[arg(long)]
+    pub instant_caret: bool,
+
+    /// Keep the screen still: no gliding or blinking caret, ticking
+    /// countdowns, error flashes or confetti
+    #[arg(long)]
+    pub reduced_motion: bool,
+
+    /// Flash the border of the screen for a moment on each wrong key
+    #[arg(long)]
+    pub flash_errors: bool,
+
+    /// Colors and attributes to draw with
+    #[arg(long, value_enum, default_value_t = ThemeName::Default)]
+    pub theme: ThemeName,
+
+    /// How extra spaces typed between words are treated
+    #[arg(long, value_enum, default_value_t = Spaces::Strict)]
+    pub spaces: Spaces,
+
+    /// Color a whole word as wrong once any of its letters is
+    #[arg(long)]
+    pub word_errors: bool,
+
+    /// Widest the text gets before wrapping, in columns
+    #[arg(long, value_name = "COLUMNS")]
+    pub max_width: Option<u16>,
+
+    /// Show only the caret's line and this many after it, from 1 to 5,
+    /// with the caret's line staying put as the text scrolls up
+    #[arg(
+        long,
+        value_name = "LINES",
+        value_parser = clap::value_parser!(u8).range(1..=i64::from(MAX_LINES_AHEAD)),
+        conflicts_with_all = ["tape", "minimal"]
+    )]
+    pub lines_ahead: Option<u8>,
+
+    /// Blank columns drawn after every letter
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 0,
+        value_parser = RangedU64ValueParser::<usize>::new().range(0..=MAX_LETTER_SPACING)
+    )]
+    pub letter_spacing: usize,
+
+    /// How the lines of the text line up with each other
+    #[arg(long, value_enum, default_value_t = Align::Left)]
+    pub align: Align,
+
+    /// How words per minute are counted
+    #[arg(long, value_enum, default_value_t = WpmFormula::Chars)]
+    pub wpm_formula: WpmFormula,
+
+    /// Leave the start of each test out of its speed and accuracy, either
+    /// the first `word` or a number of keystrokes, so the time taken to
+    /// get going doesn't count
+    #[arg(long, value_name = "GRACE")]
+    pub grace: Option<Grace>,
+
+    /// Which speeds to show on the results and in live stats, separated
+    /// by commas
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [Unit::Wpm])]
+    pub units: Vec<Unit>,
+
+    /// Which panels to show under the results and in what order, separated
+    /// by commas
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = Panel::ALL)]
+    pub panels: Vec<Panel>,
+
+    /// Show the results as a single line, as on terminals too small for
+    /// the box
+    #[arg(long)]
+    pub compact_results: bool,
+
+    /// Ring the terminal bell on these events, separated by commas
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "EVENTS")]
+    pub bell: Vec<Alert>,
+
+    /// Show a desktop notification on these events, separated by commas,
+    /// in terminals that pass them on
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "EVENTS")]
+    pub notify: Vec<Alert>,
+
+    /// Log input events, screen changes and slow frames to a file
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Keep the history, personal bests and settings apart under this
+    /// name, for someone else at the same computer or another layout
+    #[arg(long, global = true, value_name = "NAME", value_parser = profile_name)]
+    pub profile: Option<String>,
+
+    /// Label the results with this in the history, to report on tests with
+    /// it apart from the rest, such as a new keyboard. Can be given more
+    /// than once
+    #[arg(long, value_name = "LABEL")]
+    pub tag: Vec<String>,
+
+    /// Template for the results printed on exit and copied with `c`, using
+    /// {wpm}, {cpm}, {kps}, {acc}, {consistency}, {time}, {words},
+    /// {errors}, {date}, {flags}, {grace}, the keystrokes left out by
+    /// --grace, {difficulty}, how hard the text was from 0 to 100,
+    /// {endurance}, the share of its opening speed an --endurance run held,
+    /// and {reaction}, the milliseconds before the first key
+    #[arg(long, value_name = "TEMPLATE")]
+    pub format: Option<String>,
+
+    /// Keep results flagged for pauses, pastes or restarts out of the line
+    /// printed on exit, the finish hook, the ladder, sessions and the daily
+    /// challenge
+    #[arg(long)]
+    pub skip_invalid: bool,
+
+    /// Print the results on exit as JSON instead of a line of text
+    #[arg(long)]
+    pub json: bool,
+
+    /// Shell command to run after each test, with the results in `TYPERS_*`
+    /// environment variables
+    #[arg(long, value_name = "COMMAND")]
+    pub on_finish: Option<String>,
+
+    /// Post the results of each test as JSON to this `http://` URL, in the
+    /// background
+    #[arg(long, value_name = "URL")]
+    pub webhook: Option<String>,
+
+    /// Keep this file up to date with the live speed and accuracy, then
+    /// the results, for streaming overlays to show. A path ending in
+    /// `.json` gets JSON, anything else a line of text
+    #[arg(long, value_name = "PATH")]
+    pub overlay: Option<PathBuf>,
+
+    /// Key bindings to use outside of typing
+    #[arg(long, value_enum, default_value_t = KeymapPreset::Default)]
+    pub keymap: KeymapPreset,
+
+    /// What the UI draws with, beyond the text being typed
+    #[arg(long, value_enum, default_value_t = Glyphs::Auto)]
+    pub glyphs: Glyphs,
+
+    /// The language of the menus, results and hints, whatever language the
+    /// words are in
+    #[arg(long, value_enum, default_value_t = UiLang::Auto)]
+    pub ui_lang: UiLang,
+
+    /// Style with attributes only, also set by a non-empty `NO_COLOR`
+    #[arg(long)]
+    pub no_color: bool,
+}
+
+impl Args {
+    /// A problem with the arguments that clap can't see from one at a time,
+    /// as an error to exit on in clap's own words.
+    #[must_use]
+    pub fn conflict(&self) -> Option<clap::Error> {
+        match (self.min_word_len, self.max_word_len) {
+            (Some(min), Some(max)) if min > max => Some(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                format!("--min-word-len {min} is more than --max-word-len {max}, so no word fits"),
+            )),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn is_daily(&self) -> bool {
+        self.command == Some(Command::Daily)
+    }
+
+    pub fn toggle_funbox(&mut self, funbox: Funbox) {
+        if self.funbox.contains(&funbox) {
+            self.funbox.retain(|on| *on != funbox);
+        } else {
+            self.funbox.push(funbox);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(args: &[&str]) -> ErrorKind {
+        Args::try_parse_from(args).unwrap_err().kind()
+    }
+
+    #[test]
+    fn checks_arguments() {
+        Args::command().debug_assert();
+
+        assert_eq!(
+            Args::try_parse_from(["typers", "-w", "5000"])
+                .unwrap()
+                .words,
+            5000
+        );
+        assert_eq!(error(&["typers", "-w", "0"]), ErrorKind::ValueValidation);
+        assert_eq!(error(&["typers", "-w", "5001"]), ErrorKind::ValueValidation);
+        assert_eq!(
+            error(&["typers", "--max-time", "0"]),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            error(&["typers", "--file", "no/such/file"]),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            error(&["typers", "--file", "src"]),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            error(&["typers", "--clipboard", "--file", "Cargo.toml"]),
+            ErrorKind::ArgumentConflict
+        );
+        assert_eq!(
+            error(&["typers", "--tape", "--lines-ahead", "2"]),
+            ErrorKind::ArgumentConflict
+        );
+        assert_eq!(
+            error(&["typers", "--intervals", "0/15/8"]),
+            ErrorKind::ValueValidation
+        );
+        assert_eq!(
+            error(&["typers", "--intervals", "15/15/8", "--max-time", "30"]),
+            ErrorKind::ArgumentConflict
+        );
+        assert_eq!(
+            error(&["typers", "--restart-within", "3"]),
+            ErrorKind::MissingRequiredArgument
+        );
+    }
+
+    #[test]
+    fn parses_intervals() {
+        assert_eq!(
+            "15/10/8".parse(),
+            Ok(Intervals {
+                sprint: 15,
+                rest: 10,
+                rounds: NonZeroUsize::new(8).unwrap(),
+            })
+        );
+        assert!("15/10".parse::<Intervals>().is_err());
+        assert!("15/10/0".parse::<Intervals>().is_err());
+    }
+
+    #[test]
+    fn checks_arguments_together() {
+        let args = Args::parse_from(["typers", "--min-word-len", "6", "--max-word-len", "4"]);
+        assert_eq!(args.conflict().unwrap().kind(), ErrorKind::ArgumentConflict);
+
+        let args = Args::parse_from(["typers", "--min-word-len", "4", "--max-word-len", "4"]);
+        assert!(args.conflict().is_none());
+    }
 }
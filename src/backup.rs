@@ -0,0 +1,133 @@
+use crate::paths::Paths;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Everything typers keeps in its config and data directories as one TOML
+/// file, for `typers backup` and `typers restore`.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct Backup {
+    pub config: BTreeMap<String, String>,
+    pub data: BTreeMap<String, String>,
+}
+
+impl Backup {
+    /// Reads every file in the config and data directories.
+    pub fn collect() -> io::Result<Self> {
+        let paths = Paths::new()?;
+
+        Ok(Self {
+            config: read_dir(&paths.config)?,
+            data: read_dir(&paths.data)?,
+        })
+    }
+
+    /// Writes the files back, replacing any with the same names.
+    pub fn restore(&self) -> io::Result<()> {
+        let paths = Paths::new()?;
+        write_dir(&paths.config, &self.config)?;
+        write_dir(&paths.data, &self.data)
+    }
+
+    pub fn len(&self) -> usize {
+        self.config.len() + self.data.len()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+
+        fs::write(path, text).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("couldn't write {}: {err}", path.display()),
+            )
+        })
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        toml::from_str(&fs::read_to_string(path)?).map_err(|err| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid backup {}: {err}", path.display()),
+            )
+        })
+    }
+}
+
+fn read_dir(dir: &Path) -> io::Result<BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(files),
+        Err(err) => return Err(err),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+
+        if entry.file_type()?.is_file() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            files.insert(name, fs::read_to_string(entry.path())?);
+        }
+    }
+
+    Ok(files)
+}
+
+fn write_dir(dir: &Path, files: &BTreeMap<String, String>) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    for (name, contents) in files {
+        fs::write(file_path(dir, name)?, contents)?;
+    }
+
+    Ok(())
+}
+
+/// Where `name` goes in `dir`, refusing names that would land anywhere
+/// else.
+fn file_path(dir: &Path, name: &str) -> io::Result<PathBuf> {
+    let path = Path::new(name);
+
+    if path.file_name() != Some(path.as_os_str()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("backup holds a file outside typers' directories: {name}"),
+        ));
+    }
+
+    Ok(dir.join(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_directories() {
+        let dir = std::env::temp_dir().join("typers-backup-test");
+        let files = BTreeMap::from([
+            ("config.toml".to_string(), "words = 10\n".to_string()),
+            ("ladder".to_string(), "30".to_string()),
+        ]);
+
+        write_dir(&dir, &files).unwrap();
+        let read = read_dir(&dir);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(read.unwrap(), files);
+    }
+
+    #[test]
+    fn refuses_paths_outside() {
+        let dir = Path::new("data");
+        assert_eq!(file_path(dir, "ladder").unwrap(), dir.join("ladder"));
+        assert!(file_path(dir, "../ladder").is_err());
+        assert!(file_path(dir, "/etc/passwd").is_err());
+    }
+}
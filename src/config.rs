@@ -0,0 +1,508 @@
+use crate::{
+    args::{
+        Alert, Align, Cursor, Glyphs, Grace, HistoryStore, KeymapPreset, Mode, Panel, Spaces,
+        Start, ThemeName, UiLang, Unit, WpmFormula, MAX_LETTER_SPACING, MAX_LINES_AHEAD, MAX_WORDS,
+    },
+    paths::Paths,
+    Args,
+};
+use clap::{parser::ValueSource, ArgMatches};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, num::NonZeroUsize, path::PathBuf};
+use toml::{Table, Value};
+
+/// Version of the config format this build writes. Files from before there
+/// was a version count as version 0.
+const VERSION: u32 = 1;
+
+/// Upgrades a config from each version to the next, starting from 0.
+const MIGRATIONS: [fn(&mut Table); VERSION as usize] = [
+    // Version 1 only added the version number.
+    |_| {},
+];
+
+/// Persisted defaults for options that would otherwise have to be passed on
+/// every run, read from `config.toml` in the config directory.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case")]
+#[expect(clippy::struct_excessive_bools)]
+pub struct Config {
+    /// Format the file was written in, brought up to date on load.
+    pub version: u32,
+    pub words: usize,
+    pub break_after: Option<u64>,
+    pub start: Start,
+    pub tape: bool,
+    pub minimal: bool,
+    pub big_word: bool,
+    pub screen_reader: bool,
+    pub live_stats: bool,
+    pub sparkline: bool,
+    pub focus: bool,
+    pub key_repeat: bool,
+    pub cursor: Cursor,
+    pub steady_cursor: bool,
+    pub instant_caret: bool,
+    pub reduced_motion: bool,
+    pub flash_errors: bool,
+    pub theme: ThemeName,
+    /// What `theme = "auto"` picks on a dark background.
+    pub dark_theme: ThemeName,
+    /// What `theme = "auto"` picks on a light background.
+    pub light_theme: ThemeName,
+    pub spaces: Spaces,
+    pub glyphs: Glyphs,
+    pub ui_lang: UiLang,
+    pub word_errors: bool,
+    pub max_width: Option<u16>,
+    pub lines_ahead: Option<u8>,
+    pub letter_spacing: usize,
+    pub align: Align,
+    pub wpm_formula: WpmFormula,
+    pub grace: Option<Grace>,
+    pub units: Vec<Unit>,
+    pub panels: Vec<Panel>,
+    pub compact_results: bool,
+    pub bell: Vec<Alert>,
+    pub notify: Vec<Alert>,
+    pub keymap: KeymapPreset,
+    pub on_finish: Option<String>,
+    pub webhook: Option<String>,
+    pub format: Option<String>,
+    pub history_store: HistoryStore,
+    /// Named lists of tests that `typers playlist NAME` runs in turn.
+    /// Kept with the presets after plain values, as TOML needs tables
+    /// last.
+    pub playlists: BTreeMap<String, Vec<Step>>,
+    /// Named word counts that `p` on the results screen cycles through.
+    /// Kept last, as TOML needs tables after plain values.
+    pub presets: BTreeMap<String, usize>,
+}
+
+/// Part of a playlist, like the options for a `--tests` session. What it
+/// leaves out is taken from the command line and config.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Step {
+    pub mode: Option<Mode>,
+    pub words: Option<usize>,
+    pub max_time: Option<u64>,
+    /// How many tests in a row.
+    pub tests: NonZeroUsize,
+    /// A warm-up kept out of the summary.
+    pub warmup: bool,
+}
+
+impl Default for Step {
+    fn default() -> Self {
+        Self {
+            mode: None,
+            words: None,
+            max_time: None,
+            tests: NonZeroUsize::MIN,
+            warmup: false,
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            words: 24,
+            break_after: None,
+            start: Start::Keypress,
+            tape: false,
+            minimal: false,
+            big_word: false,
+            screen_reader: false,
+            live_stats: false,
+            sparkline: false,
+            focus: false,
+            key_repeat: false,
+            cursor: Cursor::Bar,
+            steady_cursor: false,
+            instant_caret: false,
+            reduced_motion: false,
+            flash_errors: false,
+            theme: ThemeName::Default,
+            dark_theme: ThemeName::Default,
+            light_theme: ThemeName::Light,
+            spaces: Spaces::Strict,
+            glyphs: Glyphs::Auto,
+            ui_lang: UiLang::Auto,
+            word_errors: false,
+            max_width: None,
+            lines_ahead: None,
+            letter_spacing: 0,
+            align: Align::Left,
+            wpm_formula: WpmFormula::Chars,
+            grace: None,
+            units: vec![Unit::Wpm],
+            panels: Panel::ALL.to_vec(),
+            compact_results: false,
+            bell: Vec::new(),
+            notify: Vec::new(),
+            keymap: KeymapPreset::Default,
+            on_finish: None,
+            webhook: None,
+            format: None,
+            history_store: HistoryStore::Toml,
+            playlists: BTreeMap::new(),
+            presets: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn path() -> io::Result<PathBuf> {
+        Ok(Paths::new()?.config.join("config.toml"))
+    }
+
+    /// Reads the config file, or the defaults if there isn't one.
+    pub fn load() -> io::Result<Self> {
+        let path = Self::path()?;
+
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("invalid config {}: {err}", path.display()),
+                )
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Reads a config of any version up to this build's, migrating older
+    /// ones first.
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut table: Table = toml::from_str(text).map_err(|err| err.to_string())?;
+
+        let version = match table.get("version") {
+            None => 0,
+            Some(Value::Integer(version)) => u32::try_from(*version).unwrap_or(u32::MAX),
+            Some(_) => return Err("version is not a number".to_string()),
+        };
+
+        let migrations = MIGRATIONS
+            .get(version as usize..)
+            .ok_or_else(|| format!("version {version} is from a newer typers"))?;
+
+        for migrate in migrations {
+            migrate(&mut table);
+        }
+
+        table.insert("version".to_string(), Value::Integer(VERSION.into()));
+        let config: Self = table.try_into().map_err(|err| err.to_string())?;
+        config.check()?;
+        Ok(config)
+    }
+
+    /// Holds values to the same ranges as their options on the command
+    /// line.
+    fn check(&self) -> Result<(), String> {
+        let words = |n: usize| u64::try_from(n).is_ok_and(|n| (1..=MAX_WORDS).contains(&n));
+        let words_error = |name: &str| format!("{name} must be from 1 to {MAX_WORDS}");
+
+        if !words(self.words) {
+            return Err(words_error("words"));
+        }
+
+        if let Some((name, _)) = self.presets.iter().find(|(_, n)| !words(**n)) {
+            return Err(words_error(&format!("preset {name}")));
+        }
+
+        for (name, steps) in &self.playlists {
+            if steps
+                .iter()
+                .any(|step| step.words.is_some_and(|n| !words(n)))
+            {
+                return Err(words_error(&format!("playlist {name}")));
+            }
+
+            if steps.iter().any(|step| step.max_time == Some(0)) {
+                return Err(format!("playlist {name} needs a max-time of at least 1"));
+            }
+        }
+
+        if self
+            .lines_ahead
+            .is_some_and(|lines| !(1..=MAX_LINES_AHEAD).contains(&lines))
+        {
+            return Err(format!("lines-ahead must be from 1 to {MAX_LINES_AHEAD}"));
+        }
+
+        if u64::try_from(self.letter_spacing).map_or(true, |n| n > MAX_LETTER_SPACING) {
+            return Err(format!(
+                "letter-spacing must be from 0 to {MAX_LETTER_SPACING}"
+            ));
+        }
+
+        if self.break_after == Some(0) {
+            return Err("break-after must be at least 1".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()?;
+        let text = toml::to_string(self).map_err(io::Error::other)?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        fs::write(path, text)
+    }
+
+    /// Fills in every option of `args` that wasn't given on the command line,
+    /// or all of them when there are no `matches` to go by.
+    pub fn apply(&self, args: &mut Args, matches: Option<&ArgMatches>) {
+        let unset = |id: &str| {
+            matches.is_none_or(|matches| matches.value_source(id) != Some(ValueSource::CommandLine))
+        };
+
+        if unset("words") {
+            args.words = self.words;
+        }
+
+        if unset("break_after") {
+            args.break_after = self.break_after;
+        }
+
+        if unset("start") {
+            args.start = self.start;
+        }
+
+        self.apply_display(args, &unset);
+
+        if unset("wpm_formula") {
+            args.wpm_formula = self.wpm_formula;
+        }
+
+        if unset("grace") {
+            args.grace = self.grace;
+        }
+
+        if unset("units") {
+            args.units.clone_from(&self.units);
+        }
+
+        if unset("bell") {
+            args.bell.clone_from(&self.bell);
+        }
+
+        if unset("notify") {
+            args.notify.clone_from(&self.notify);
+        }
+
+        if unset("keymap") {
+            args.keymap = self.keymap;
+        }
+
+        if unset("on_finish") {
+            args.on_finish.clone_from(&self.on_finish);
+        }
+
+        if unset("webhook") {
+            args.webhook.clone_from(&self.webhook);
+        }
+
+        if unset("format") {
+            args.format.clone_from(&self.format);
+        }
+    }
+
+    /// The part of [`Config::apply`] for how tests and results are drawn.
+    fn apply_display(&self, args: &mut Args, unset: &impl Fn(&str) -> bool) {
+        if unset("tape") {
+            args.tape = self.tape;
+        }
+
+        if unset("minimal") {
+            args.minimal = self.minimal;
+        }
+
+        if unset("big_word") {
+            args.big_word = self.big_word;
+        }
+
+        if unset("screen_reader") {
+            args.screen_reader = self.screen_reader;
+        }
+
+        if unset("live_stats") {
+            args.live_stats = self.live_stats;
+        }
+
+        if unset("sparkline") {
+            args.sparkline = self.sparkline;
+        }
+
+        if unset("focus") {
+            args.focus = self.focus;
+        }
+
+        if unset("key_repeat") {
+            args.key_repeat = self.key_repeat;
+        }
+
+        if unset("cursor") {
+            args.cursor = self.cursor;
+        }
+
+        if unset("steady_cursor") {
+            args.steady_cursor = self.steady_cursor;
+        }
+
+        if unset("instant_caret") {
+            args.instant_caret = self.instant_caret;
+        }
+
+        if unset("reduced_motion") {
+            args.reduced_motion = self.reduced_motion;
+        }
+
+        if unset("flash_errors") {
+            args.flash_errors = self.flash_errors;
+        }
+
+        if unset("theme") {
+            args.theme = self.theme;
+        }
+
+        if unset("glyphs") {
+            args.glyphs = self.glyphs;
+        }
+
+        if unset("ui_lang") {
+            args.ui_lang = self.ui_lang;
+        }
+
+        if unset("spaces") {
+            args.spaces = self.spaces;
+        }
+
+        if unset("word_errors") {
+            args.word_errors = self.word_errors;
+        }
+
+        if unset("max_width") {
+            args.max_width = self.max_width;
+        }
+
+        if unset("lines_ahead") {
+            args.lines_ahead = self.lines_ahead;
+        }
+
+        if unset("letter_spacing") {
+            args.letter_spacing = self.letter_spacing;
+        }
+
+        if unset("align") {
+            args.align = self.align;
+        }
+
+        if unset("panels") {
+            args.panels.clone_from(&self.panels);
+        }
+
+        if unset("compact_results") {
+            args.compact_results = self.compact_results;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches, Parser};
+
+    #[test]
+    fn command_line_wins() {
+        let matches = Args::command().get_matches_from(["typers", "--words", "10"]);
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+
+        let config = Config {
+            words: 50,
+            live_stats: true,
+            ..Config::default()
+        };
+        config.apply(&mut args, Some(&matches));
+
+        assert_eq!(args.words, 10);
+        assert!(args.live_stats);
+    }
+
+    #[test]
+    fn round_trips_presets() {
+        let config: Config = toml::from_str("words = 10\n[presets]\nquick = 10\n").unwrap();
+        assert_eq!(config.presets["quick"], 10);
+
+        let text = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<Config>(&text).unwrap(), config);
+    }
+
+    #[test]
+    fn round_trips_playlists() {
+        let config: Config = toml::from_str(
+            "[playlists]\nmorning = [{ warmup = true }, { tests = 2, max-time = 60 }]\n",
+        )
+        .unwrap();
+        assert_eq!(config.playlists["morning"][1].tests.get(), 2);
+
+        let text = toml::to_string(&config).unwrap();
+        assert_eq!(toml::from_str::<Config>(&text).unwrap(), config);
+    }
+
+    #[test]
+    fn orders_panels() {
+        let config: Config = toml::from_str("panels = [\"fingers\", \"review\"]").unwrap();
+        let mut args = Args::parse_from(["typers"]);
+        config.apply(&mut args, None);
+
+        assert_eq!(args.panels, [Panel::Fingers, Panel::Review]);
+        assert_eq!(Config::default().panels, Panel::ALL);
+    }
+
+    #[test]
+    fn checks_ranges() {
+        for text in [
+            "words = 0",
+            "words = 99999",
+            "lines-ahead = 9",
+            "break-after = 0",
+            "letter-spacing = 5",
+            "[presets]\nquick = 0",
+            "[playlists]\nmorning = [{ words = 0 }]",
+        ] {
+            assert!(Config::parse(text).is_err(), "{text}");
+        }
+
+        assert!(Config::parse("words = 5000\nlines-ahead = 5").is_ok());
+    }
+
+    #[test]
+    fn migrates_old_versions() {
+        let config = Config::parse("live-stats = true").unwrap();
+        assert_eq!(config.version, VERSION);
+        assert!(config.live_stats);
+
+        assert!(Config::parse("version = 99").is_err());
+    }
+
+    #[test]
+    fn reads_partial_files() {
+        let config: Config = toml::from_str("live-stats = true").unwrap();
+        assert_eq!(
+            config,
+            Config {
+                live_stats: true,
+                ..Config::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use crossterm::{
+    cursor,
+    event::{
+        self, Event, KeyCode, KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    execute, terminal,
+};
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph},
+    DefaultTerminal, Frame,
+};
+use std::{
+    env, io,
+    time::{Duration, Instant},
+};
+
+/// How many times each measurement is taken when `r` is pressed.
+const ROUNDS: usize = 20;
+
+/// Longer than this between two events of a key and it was let go and
+/// pressed again rather than held.
+const RELEASED: Duration = Duration::from_secs(1);
+
+/// Closer together than this, events of a key were pasted, not repeated.
+const PASTED: Duration = Duration::from_millis(5);
+
+/// Measures how the terminal delivers keys and draws frames, for `typers
+/// diagnose`.
+pub fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    // Without the flags, terminals report repeats as more presses and
+    // releases not at all.
+    let enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced {
+        execute!(
+            io::stdout(),
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
+
+    let mut diagnostics = Diagnostics {
+        enhanced,
+        ..Diagnostics::default()
+    };
+    let result = diagnostics.main_loop(terminal);
+
+    if enhanced {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
+
+    result
+}
+
+/// Asks the terminal where the cursor is and times the answer, which only
+/// comes once everything written before it has been handled.
+fn round_trip() -> io::Result<Duration> {
+    let start = Instant::now();
+    cursor::position()?;
+    Ok(start.elapsed())
+}
+
+#[derive(Default)]
+struct Diagnostics {
+    enhanced: bool,
+    round_trip: Samples,
+    redraw: Samples,
+    /// From reading a key to the terminal having handled the frame drawn
+    /// for it.
+    key_to_screen: Samples,
+    repeats: Repeats,
+    last_key: Option<String>,
+}
+
+impl Diagnostics {
+    fn main_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        self.measure(terminal)?;
+        let mut received: Option<Instant> = None;
+
+        loop {
+            terminal.draw(|frame| self.render(frame))?;
+
+            if let Some(at) = received.take() {
+                if round_trip().is_ok() {
+                    self.key_to_screen.push(at.elapsed());
+                }
+            }
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            let at = Instant::now();
+
+            self.repeats.push(key.code, key.kind, at);
+            self.last_key = Some(format!("{:?} {:?}", key.code, key.kind));
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('r') if key.kind == KeyEventKind::Press => self.measure(terminal)?,
+                _ if key.kind != KeyEventKind::Release => received = Some(at),
+                _ => {}
+            }
+        }
+    }
+
+    /// Times round trips to the terminal, and redraws of the whole screen
+    /// up to when the terminal is done with them.
+    fn measure(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        for _ in 0..ROUNDS {
+            if let Ok(took) = round_trip() {
+                self.round_trip.push(took);
+            }
+
+            let start = Instant::now();
+            terminal.clear()?;
+            terminal.draw(|frame| self.render(frame))?;
+
+            if round_trip().is_ok() {
+                self.redraw.push(start.elapsed());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[expect(clippy::cast_possible_truncation)]
+    fn render(&self, frame: &mut Frame) {
+        let terminal = env::var("TERM_PROGRAM")
+            .or_else(|_| env::var("TERM"))
+            .unwrap_or_else(|_| "unknown".to_string());
+        let events = if self.enhanced {
+            "presses, repeats and releases"
+        } else {
+            "presses only, repeats look like presses"
+        };
+
+        let rows = [
+            ("terminal", terminal),
+            ("key events", events.to_string()),
+            ("round trip", self.round_trip.describe()),
+            ("full redraw", self.redraw.describe()),
+            ("key to screen", self.key_to_screen.describe()),
+            ("key repeat", self.repeats.describe()),
+            (
+                "last key",
+                self.last_key.clone().unwrap_or_else(|| "none".to_string()),
+            ),
+        ];
+
+        let lines: Vec<Line> = rows
+            .into_iter()
+            .map(|(label, value)| {
+                Line::from(vec![
+                    Span::raw(format!("{label:<15}")).dim(),
+                    Span::raw(value),
+                ])
+            })
+            .collect();
+
+        let block = Block::bordered()
+            .title(" diagnose ".bold())
+            .title_bottom(" r measure again · q quit ".dim())
+            .border_type(BorderType::Rounded)
+            .padding(Padding::uniform(1));
+
+        let [area] = Layout::vertical([Constraint::Length(lines.len() as u16 + 4)])
+            .flex(Flex::Center)
+            .areas(frame.area());
+        let [area] = Layout::horizontal([Constraint::Length(64)])
+            .flex(Flex::Center)
+            .areas(area);
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+fn ms(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn median(durations: &[Duration]) -> Option<Duration> {
+    let mut sorted = durations.to_vec();
+    sorted.sort_unstable();
+    sorted.get(sorted.len() / 2).copied()
+}
+
+#[derive(Default)]
+struct Samples(Vec<Duration>);
+
+impl Samples {
+    fn push(&mut self, duration: Duration) {
+        self.0.push(duration);
+    }
+
+    fn describe(&self) -> String {
+        match (median(&self.0), self.0.iter().max()) {
+            (Some(median), Some(max)) => format!(
+                "median {} · max {} over {}",
+                ms(median),
+                ms(*max),
+                self.0.len()
+            ),
+            _ => "not measured yet".to_string(),
+        }
+    }
+}
+
+/// How a held key repeats: the wait before the first repeat and the time
+/// between the ones after it.
+#[derive(Default)]
+struct Repeats {
+    /// The key held down and when its last event came.
+    held: Option<(KeyCode, Instant)>,
+    gaps: Vec<Duration>,
+    /// Whether the terminal marks repeats, making every press a new one.
+    marked: bool,
+    delay: Option<Duration>,
+    interval: Option<Duration>,
+}
+
+impl Repeats {
+    fn push(&mut self, code: KeyCode, kind: KeyEventKind, at: Instant) {
+        if kind == KeyEventKind::Release {
+            self.held = None;
+            return;
+        }
+
+        self.marked |= kind == KeyEventKind::Repeat;
+        let pressed_again = self.marked && kind == KeyEventKind::Press;
+
+        match self.held {
+            Some((held, last)) if held == code && at - last < RELEASED && !pressed_again => {
+                self.gaps.push(at - last);
+            }
+            _ => self.gaps.clear(),
+        }
+
+        self.held = Some((code, at));
+
+        // A couple of gaps could be quick typing, more are a held key.
+        if let [delay, ref rest @ ..] = self.gaps[..] {
+            let interval = median(rest).filter(|interval| *interval >= PASTED);
+
+            if rest.len() >= 2 && interval.is_some() {
+                self.delay = Some(delay);
+                self.interval = interval;
+            }
+        }
+    }
+
+    fn describe(&self) -> String {
+        match (self.delay, self.interval) {
+            (Some(delay), Some(interval)) if !interval.is_zero() => format!(
+                "{} delay · {:.0} per second",
+                ms(delay),
+                1.0 / interval.as_secs_f64()
+            ),
+            _ => "hold a key down to measure".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn describes_samples() {
+        let mut samples = Samples::default();
+        assert_eq!(samples.describe(), "not measured yet");
+
+        for ms in [3, 1, 2] {
+            samples.push(ms * MS);
+        }
+        assert_eq!(samples.describe(), "median 2.0ms · max 3.0ms over 3");
+    }
+
+    #[test]
+    fn measures_held_keys() {
+        let start = Instant::now();
+        let mut repeats = Repeats::default();
+
+        for at in [0, 500, 533, 566, 600] {
+            repeats.push(KeyCode::Char('a'), KeyEventKind::Press, start + at * MS);
+        }
+
+        assert_eq!(repeats.delay, Some(500 * MS));
+        assert_eq!(repeats.interval, Some(33 * MS));
+    }
+
+    #[test]
+    fn ignores_pasted_text() {
+        let start = Instant::now();
+        let mut repeats = Repeats::default();
+
+        for at in 0..5 {
+            repeats.push(
+                KeyCode::Char('a'),
+                KeyEventKind::Press,
+                start + at * MS / 10,
+            );
+        }
+
+        assert_eq!(repeats.interval, None);
+    }
+
+    #[test]
+    fn starts_over_on_a_new_press() {
+        let start = Instant::now();
+        let mut repeats = Repeats::default();
+
+        repeats.push(KeyCode::Char('a'), KeyEventKind::Press, start);
+        repeats.push(KeyCode::Char('a'), KeyEventKind::Repeat, start + 500 * MS);
+        repeats.push(KeyCode::Char('a'), KeyEventKind::Press, start + 600 * MS);
+        repeats.push(KeyCode::Char('a'), KeyEventKind::Repeat, start + 650 * MS);
+
+        assert_eq!(repeats.gaps, [50 * MS]);
+    }
+}
@@ -0,0 +1,380 @@
+use crate::align::{self, Alignment};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{str::FromStr, time::Duration};
+
+/// The start of a test left out of its results, written as `word` or a
+/// count of keystrokes.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(try_from = "String", into = "String")]
+pub enum Grace {
+    Word,
+    Keystrokes(usize),
+}
+
+impl Grace {
+    /// How many characters at the start of `text` are left out: the first
+    /// word with its space, or the count of keystrokes.
+    #[must_use]
+    pub fn keystrokes(self, text: &str) -> usize {
+        match self {
+            Self::Word => text
+                .chars()
+                .position(|c| c == ' ')
+                .map_or(0, |space| space + 1),
+            Self::Keystrokes(n) => n,
+        }
+    }
+}
+
+impl FromStr for Grace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "word" => Ok(Self::Word),
+            n => n
+                .parse()
+                .map(Self::Keystrokes)
+                .map_err(|_| format!("expected word or a number of keystrokes, got {s}")),
+        }
+    }
+}
+
+impl TryFrom<String> for Grace {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Grace> for String {
+    fn from(grace: Grace) -> Self {
+        match grace {
+            Grace::Word => "word".to_string(),
+            Grace::Keystrokes(n) => n.to_string(),
+        }
+    }
+}
+
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum WpmFormula {
+    /// Every five characters count as a word
+    #[default]
+    Chars,
+    /// Count the words as they are written
+    Words,
+}
+
+impl WpmFormula {
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Chars => "chars / 5",
+            Self::Words => "words",
+        }
+    }
+}
+
+/// The options that change how a test is scored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scoring {
+    pub grace: Option<Grace>,
+    pub formula: WpmFormula,
+}
+
+/// A test as typed so far, borrowed from wherever it's kept.
+#[derive(Debug, Clone, Copy)]
+pub struct Attempt<'a> {
+    pub target: &'a [char],
+    pub typed: &'a [char],
+    /// How long after the start each of `typed` landed.
+    pub times: &'a [Duration],
+    /// Whether it was stopped at a time cap before the end of the target,
+    /// so that only what was typed counts.
+    pub incomplete: bool,
+}
+
+impl Scoring {
+    /// How many keystrokes at the start the grace leaves out. At least the
+    /// last one always counts.
+    #[must_use]
+    pub fn graced(self, attempt: &Attempt) -> usize {
+        self.grace.map_or(0, |grace| {
+            let text: String = attempt.target.iter().collect();
+            grace
+                .keystrokes(&text)
+                .min(attempt.typed.len().saturating_sub(1))
+        })
+    }
+
+    /// The speed of a finished `attempt` that took `duration`, timed from
+    /// the last keystroke left out by the grace.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn wpm(self, attempt: &Attempt, duration: Duration) -> f64 {
+        let graced = self.graced(attempt);
+        let elapsed = match graced.checked_sub(1) {
+            Some(last) => duration.saturating_sub(attempt.times[last]),
+            None => duration,
+        };
+        let spaces = |chars: &[char]| chars.iter().filter(|c| **c == ' ').count();
+        let target = attempt.target;
+
+        // A test stopped at the time cap only counts what was typed.
+        let words = match (self.formula, attempt.incomplete) {
+            (WpmFormula::Chars, false) => ((target.len() - graced) / 5) as f64,
+            (WpmFormula::Chars, true) => ((attempt.typed.len() - graced) / 5) as f64,
+            (WpmFormula::Words, false) => {
+                (spaces(target) + 1).saturating_sub(spaces(&target[..graced])) as f64
+            }
+            (WpmFormula::Words, true) => spaces(&attempt.typed[graced..]) as f64,
+        };
+
+        per_minute(words, elapsed)
+    }
+
+    /// The speed `elapsed` into a test with `typed` so far.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn live_wpm(self, typed: &[char], elapsed: Duration) -> f64 {
+        let words = match self.formula {
+            WpmFormula::Chars => typed.len() as f64 / 5.0,
+            // Only words ended with a space have been typed in full.
+            WpmFormula::Words => typed.iter().filter(|c| **c == ' ').count() as f64,
+        };
+
+        per_minute(words, elapsed)
+    }
+
+    /// The cheapest alignment of what was typed to the target, past the
+    /// grace.
+    #[must_use]
+    pub fn alignment(self, attempt: &Attempt) -> Alignment {
+        let mut target = attempt.target;
+
+        // The rest of an incomplete test was never reached, not missed.
+        if attempt.incomplete {
+            target = &target[..attempt.typed.len().min(target.len())];
+        }
+
+        let graced = self.graced(attempt).min(target.len());
+        align::align(&attempt.typed[graced..], &target[graced..])
+    }
+}
+
+/// How a test is going partway through, counted position by position
+/// against the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LiveStats {
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub errors: usize,
+}
+
+/// How a finished test went, counted along the cheapest alignment of what
+/// was typed to the text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestResult {
+    /// From the start to the last keystroke.
+    pub time: Duration,
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub alignment: Alignment,
+}
+
+/// One test of typing a text, fed keystrokes with how long after the start
+/// each one landed.
+#[derive(Debug, Clone, Default)]
+pub struct TestEngine {
+    target: Vec<char>,
+    typed: Vec<char>,
+    times: Vec<Duration>,
+    scoring: Scoring,
+}
+
+impl TestEngine {
+    #[must_use]
+    pub fn new(text: &str) -> Self {
+        Self {
+            target: text.chars().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Scores the test with `scoring` rather than the defaults.
+    #[must_use]
+    pub fn scoring(mut self, scoring: Scoring) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// What has been typed so far, less anything erased.
+    #[must_use]
+    pub fn typed(&self) -> &[char] {
+        &self.typed
+    }
+
+    /// When each character of [`typed`](Self::typed) was typed.
+    #[must_use]
+    pub fn times(&self) -> &[Duration] {
+        &self.times
+    }
+
+    /// Types `c` at `at` since the start. Keystrokes after the test is
+    /// finished are ignored.
+    pub fn type_char(&mut self, c: char, at: Duration) {
+        if !self.is_finished() {
+            self.typed.push(c);
+            self.times.push(at);
+        }
+    }
+
+    /// Erases the last character typed, unless the test is finished.
+    pub fn backspace(&mut self) {
+        if !self.is_finished() {
+            self.typed.pop();
+            self.times.pop();
+        }
+    }
+
+    /// Whether as many characters have been typed as the text has.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.typed.len() >= self.target.len()
+    }
+
+    /// Stats at `now` since the start, as shown while typing.
+    #[must_use]
+    #[expect(clippy::cast_precision_loss)]
+    pub fn live(&self, now: Duration) -> LiveStats {
+        let errors = self
+            .typed
+            .iter()
+            .zip(&self.target)
+            .filter(|(c, target)| c != target)
+            .count();
+
+        let accuracy = if self.typed.is_empty() {
+            100.0
+        } else {
+            (self.typed.len() - errors) as f64 / self.typed.len() as f64 * 100.0
+        };
+
+        LiveStats {
+            wpm: self.scoring.live_wpm(&self.typed, now),
+            accuracy,
+            errors,
+        }
+    }
+
+    /// The result, once the test is finished.
+    #[must_use]
+    pub fn result(&self) -> Option<TestResult> {
+        self.is_finished().then(|| self.result_at(false))
+    }
+
+    /// The result as stopped at a time cap, with only what was typed
+    /// counting.
+    #[must_use]
+    pub fn stop(&self) -> TestResult {
+        self.result_at(!self.is_finished())
+    }
+
+    fn result_at(&self, incomplete: bool) -> TestResult {
+        let attempt = Attempt {
+            target: &self.target,
+            typed: &self.typed,
+            times: &self.times,
+            incomplete,
+        };
+        let time = self.times.last().copied().unwrap_or_default();
+        let alignment = self.scoring.alignment(&attempt);
+
+        TestResult {
+            time,
+            wpm: self.scoring.wpm(&attempt, time),
+            accuracy: alignment.accuracy(),
+            alignment,
+        }
+    }
+}
+
+/// `words` typed over `time` as words per minute, nothing before any time
+/// has passed.
+fn per_minute(words: f64, time: Duration) -> f64 {
+    if time.is_zero() {
+        return 0.0;
+    }
+
+    words / time.as_secs_f64() * 60.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn scores_a_run() {
+        let mut engine = TestEngine::new("abcde");
+
+        for (i, c) in "abcxe".chars().enumerate() {
+            if i == 3 {
+                engine.type_char('y', 1000 * MS);
+                engine.backspace();
+            }
+
+            engine.type_char(c, 300 * MS * u32::try_from(i + 1).unwrap());
+        }
+
+        let live = engine.live(1500 * MS);
+        assert_eq!(live.errors, 1);
+        assert!((live.accuracy - 80.0).abs() < 1e-9);
+
+        let result = engine.result().unwrap();
+        assert_eq!(result.time, 1500 * MS);
+        assert!((result.wpm - 40.0).abs() < 1e-9);
+        assert_eq!(result.alignment.substitutions, 1);
+    }
+
+    #[test]
+    fn leaves_out_the_grace() {
+        assert_eq!(Grace::Word.keystrokes("the quick fox"), 4);
+        assert_eq!(Grace::Word.keystrokes("alone"), 0);
+        assert_eq!(Grace::Keystrokes(6).keystrokes("the quick fox"), 6);
+
+        let text = "ab cdefghij";
+        let mut engine = TestEngine::new(text).scoring(Scoring {
+            grace: Some(Grace::Word),
+            formula: WpmFormula::Chars,
+        });
+        for (i, c) in "xb cdefghij".chars().enumerate() {
+            engine.type_char(c, 1000 * MS * u32::try_from(i).unwrap());
+        }
+
+        // Eight characters from the third keystroke's second to the tenth.
+        let result = engine.result().unwrap();
+        assert!((result.wpm - 1.0 / 8.0 * 60.0).abs() < 1e-9);
+        assert!((result.accuracy - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn counts_only_what_was_typed_when_stopped() {
+        let mut engine = TestEngine::new("one two three four").scoring(Scoring {
+            grace: None,
+            formula: WpmFormula::Words,
+        });
+        for (i, c) in "one two t".chars().enumerate() {
+            engine.type_char(c, 1000 * MS * u32::try_from(i + 1).unwrap());
+        }
+
+        assert_eq!(engine.result(), None);
+        let stopped = engine.stop();
+        assert!((stopped.wpm - 2.0 / 9.0 * 60.0).abs() < 1e-9);
+        assert_eq!(stopped.alignment.omissions, 0);
+    }
+}
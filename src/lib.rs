@@ -0,0 +1,28 @@
+//! The typing test at the heart of typers, for frontends other than the
+//! terminal one and for testing it without a terminal.
+//!
+//! A [`TestEngine`] takes the keystrokes typed against a text, each with
+//! the time it landed, and works out its stats, timed from the start to
+//! the last keystroke. It scores through [`Scoring`], which the terminal app
+//! uses for its own results too, so `--grace`, `--wpm-formula` and tests
+//! stopped at the time cap come out the same here as there.
+//!
+//! ```
+//! use std::time::Duration;
+//! use typers::TestEngine;
+//!
+//! let mut engine = TestEngine::new("hello world");
+//!
+//! for (i, c) in "hello world".chars().enumerate() {
+//!     engine.type_char(c, Duration::from_millis(200) * i as u32);
+//! }
+//!
+//! let result = engine.result().unwrap();
+//! assert_eq!(result.accuracy, 100.0);
+//! assert_eq!(result.wpm, 60.0);
+//! ```
+
+pub mod align;
+mod engine;
+
+pub use engine::{Attempt, Grace, LiveStats, Scoring, TestEngine, TestResult, WpmFormula};
@@ -1,16 +1,168 @@
 mod app;
 mod args;
+mod backup;
+mod config;
+mod diagnose;
+mod paths;
+mod rollover;
 
 use app::App;
-pub use args::Args;
-use clap::Parser;
-use std::io;
+pub use args::{Args, Command};
+use backup::Backup;
+use clap::{CommandFactory, FromArgMatches};
+use config::Config;
+use crossterm::{cursor::SetCursorStyle, event::DisableMouseCapture, execute};
+use paths::Paths;
+use ratatui::DefaultTerminal;
+use std::{io, panic, process::ExitCode};
 
-fn main() -> io::Result<()> {
-    let args = Args::parse();
+/// Prints what went wrong in plain words, once the terminal is back to
+/// normal, rather than the debug form `main` returning an error would.
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("typers: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> io::Result<()> {
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    if let Some(err) = args.conflict() {
+        err.exit();
+    }
+
+    Paths::set_profile(args.profile.as_deref());
+    args.menu |= std::env::args_os().len() == 1;
+    args.no_color |= std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+
+    if let Some(Command::Paths) = args.command {
+        Paths::new()?.print();
+        return Ok(());
+    }
+
+    if let Some(Command::Backup { path }) = &args.command {
+        let backup = Backup::collect()?;
+        backup.save(path)?;
+        println!("backed up {} files to {}", backup.len(), path.display());
+        return Ok(());
+    }
+
+    if let Some(Command::Restore { path }) = &args.command {
+        let backup = Backup::load(path)?;
+        backup.restore()?;
+        println!("restored {} files from {}", backup.len(), path.display());
+        return Ok(());
+    }
+
+    let config = Config::load()?;
+    config.apply(&mut args, Some(&matches));
+    app::use_history_store(&config);
+
+    if let Some(report) = args.command.as_ref().and_then(app::history_command) {
+        print!("{}", report?);
+        return Ok(());
+    }
+
+    if let Some(Command::Diagnose) = args.command {
+        let mut terminal = init_terminal()?;
+        let result = diagnose::run(&mut terminal);
+        ratatui::restore();
+        return result;
+    }
+
+    if let Some(Command::Rollover) = args.command {
+        let mut terminal = init_terminal()?;
+        let result = rollover::run(&mut terminal);
+        ratatui::restore();
+        return result;
+    }
+
+    // Loaded the config first, so the card is drawn in the usual theme.
+    if let Some(Command::ExportResult {
+        id,
+        format,
+        output,
+        filter,
+    }) = &args.command
+    {
+        let path = app::export_result(
+            &args,
+            &config,
+            id.as_deref(),
+            *format,
+            output.as_deref(),
+            filter,
+        )?;
+        println!("exported {}", path.display());
+        return Ok(());
+    }
+
+    // Loaded the config first, so the words simulated are picked the same
+    // way as for a test.
+    if let Some(Command::Simulate {
+        wpm,
+        accuracy,
+        runs,
+        seed,
+    }) = args.command
+    {
+        print!("{}", app::simulate(&args, wpm, accuracy, runs, seed)?);
+        return Ok(());
+    }
+
+    // The menu and settings are screens to look at, with nothing to read
+    // out, so a screen reader goes straight to a test.
+    let plain = args.screen_reader;
+    args.menu &= !plain;
+    app::restore_picks(&mut args, &matches);
+
+    let mut app = App::new(args, config)?;
+
+    if plain {
+        app.run_plain()?;
+    } else {
+        let mut terminal = init_terminal()?;
+        restore_on_panic();
+        let app_result = app.run(&mut terminal);
+        ratatui::restore();
+        app_result?;
+    }
+
+    if let Some(summary) = app.summary() {
+        println!("{summary}");
+    }
+
+    app.wait_for_webhooks();
+
+    Ok(())
+}
+
+/// Takes the terminal over, failing with an error to print instead of a
+/// panic when there's no terminal to take.
+fn init_terminal() -> io::Result<DefaultTerminal> {
+    ratatui::try_init().map_err(|err| {
+        ratatui::restore();
+        io::Error::new(err.kind(), format!("couldn't set up the terminal: {err}"))
+    })
+}
+
+/// Undoes what the app turns on beyond what ratatui's own hook restores, so
+/// a crash mid-test doesn't leave the terminal reporting mouse events with a
+/// changed cursor.
+fn restore_on_panic() {
+    let hook = panic::take_hook();
 
-    let mut terminal = ratatui::init();
-    let app_result = App::new(args).run(&mut terminal);
-    ratatui::restore();
-    app_result
+    panic::set_hook(Box::new(move |info| {
+        let _ = execute!(
+            io::stdout(),
+            DisableMouseCapture,
+            SetCursorStyle::DefaultUserShape
+        );
+        hook(info);
+    }));
 }
@@ -0,0 +1,81 @@
+use directories::ProjectDirs;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+/// The profile picked with `--profile` or in the menu, whose files are kept
+/// apart from everyone else's.
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Where typers keeps its files, following the platform's conventions: XDG
+/// directories on Linux, `~/Library` on macOS and `%APPDATA%` on Windows.
+pub struct Paths {
+    pub config: PathBuf,
+    pub data: PathBuf,
+    pub cache: PathBuf,
+}
+
+impl Paths {
+    /// The directories of the current profile, which are the usual ones
+    /// without a profile and a `profiles/NAME` directory in each with one.
+    pub fn new() -> io::Result<Self> {
+        let profile = PROFILE.read().ok().and_then(|profile| profile.clone());
+        Self::of(profile.as_deref())
+    }
+
+    /// The directories of `profile`, whichever is current.
+    pub fn of(profile: Option<&str>) -> io::Result<Self> {
+        let dirs = project_dirs()?;
+
+        let dir = |base: &Path| match profile {
+            Some(name) => base.join("profiles").join(name),
+            None => base.to_path_buf(),
+        };
+
+        Ok(Self {
+            config: dir(dirs.config_dir()),
+            data: dir(dirs.data_dir()),
+            cache: dir(dirs.cache_dir()),
+        })
+    }
+
+    /// Switches every path from here on over to `profile`.
+    pub fn set_profile(profile: Option<&str>) {
+        if let Ok(mut current) = PROFILE.write() {
+            *current = profile.map(str::to_string);
+        }
+    }
+
+    /// The names of the profiles that have kept settings or data so far.
+    pub fn profiles() -> Vec<String> {
+        let Ok(dirs) = project_dirs() else {
+            return Vec::new();
+        };
+
+        let mut profiles: Vec<String> = [dirs.config_dir(), dirs.data_dir()]
+            .into_iter()
+            .filter_map(|base| fs::read_dir(base.join("profiles")).ok())
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+
+        profiles.sort();
+        profiles.dedup();
+        profiles
+    }
+
+    pub fn print(&self) {
+        println!("config  {}", self.config.display());
+        println!("data    {}", self.data.display());
+        println!("cache   {}", self.cache.display());
+    }
+}
+
+fn project_dirs() -> io::Result<ProjectDirs> {
+    ProjectDirs::from("", "", "typers")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory found"))
+}
@@ -0,0 +1,211 @@
+use crossterm::{
+    event::{
+        self, Event, KeyCode, KeyEventKind, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
+    execute, terminal,
+};
+use ratatui::{
+    layout::{Constraint, Flex, Layout},
+    style::Stylize,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Padding, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+/// Without releases to go by, presses closer together than this count as
+/// held at once.
+const CHORD: Duration = Duration::from_millis(60);
+
+/// Shows which keys register while many are held down, and the most that
+/// did at once, for `typers rollover`.
+pub fn run(terminal: &mut DefaultTerminal) -> io::Result<()> {
+    let enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced {
+        execute!(
+            io::stdout(),
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
+
+    let mut held = Held {
+        enhanced,
+        ..Held::default()
+    };
+    let result = main_loop(terminal, &mut held);
+
+    if enhanced {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
+
+    result
+}
+
+fn main_loop(terminal: &mut DefaultTerminal, held: &mut Held) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| render(frame, held))?;
+
+        // Letting go of a chord shows up as no more presses for a while.
+        if !held.enhanced && !held.keys.is_empty() && !event::poll(CHORD)? {
+            held.keys.clear();
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Enter if key.kind == KeyEventKind::Press => {
+                *held = Held {
+                    enhanced: held.enhanced,
+                    ..Held::default()
+                }
+            }
+            code => held.push(code, key.kind, Instant::now()),
+        }
+    }
+}
+
+/// The keys down right now, going by releases where the terminal reports
+/// them, or else by presses coming in close together.
+#[derive(Default)]
+struct Held {
+    enhanced: bool,
+    keys: Vec<KeyCode>,
+    last_press: Option<Instant>,
+    /// The most keys that were down at once, and which they were.
+    most: Vec<KeyCode>,
+}
+
+impl Held {
+    fn push(&mut self, code: KeyCode, kind: KeyEventKind, at: Instant) {
+        match kind {
+            KeyEventKind::Release => self.keys.retain(|key| *key != code),
+            KeyEventKind::Repeat => {}
+            KeyEventKind::Press => {
+                let apart = self.last_press.is_some_and(|last| at - last > CHORD);
+
+                if !self.enhanced && apart {
+                    self.keys.clear();
+                }
+
+                if !self.keys.contains(&code) {
+                    self.keys.push(code);
+                }
+
+                self.last_press = Some(at);
+            }
+        }
+
+        if self.keys.len() > self.most.len() {
+            self.most.clone_from(&self.keys);
+        }
+    }
+}
+
+fn names(keys: &[KeyCode]) -> String {
+    if keys.is_empty() {
+        return "none".to_string();
+    }
+
+    keys.iter()
+        .map(|key| match key {
+            KeyCode::Char(' ') => "space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            key => key.to_string().to_lowercase(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[expect(clippy::cast_possible_truncation)]
+fn render(frame: &mut Frame, held: &Held) {
+    let events = if held.enhanced {
+        "presses and releases".to_string()
+    } else {
+        format!(
+            "presses only, keys within {}ms count as held",
+            CHORD.as_millis()
+        )
+    };
+
+    let rows = [
+        ("key events", events),
+        ("held now", names(&held.keys)),
+        ("most at once", held.most.len().to_string()),
+        ("those were", names(&held.most)),
+    ];
+
+    let mut lines: Vec<Line> = vec![
+        Line::raw("Hold down as many keys as you can. Keys that don't show up here were dropped by the keyboard or terminal."),
+        Line::raw(""),
+    ];
+    lines.extend(rows.into_iter().map(|(label, value)| {
+        Line::from(vec![
+            Span::raw(format!("{label:<15}")).dim(),
+            Span::raw(value),
+        ])
+    }));
+
+    let block = Block::bordered()
+        .title(" rollover ".bold())
+        .title_bottom(" enter start over · esc quit ".dim())
+        .border_type(BorderType::Rounded)
+        .padding(Padding::uniform(1));
+
+    let [area] = Layout::vertical([Constraint::Length(lines.len() as u16 + 5)])
+        .flex(Flex::Center)
+        .areas(frame.area());
+    let [area] = Layout::horizontal([Constraint::Length(64)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: true }).block(block),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn counts_keys_down_together() {
+        let start = Instant::now();
+        let mut held = Held {
+            enhanced: true,
+            ..Held::default()
+        };
+
+        for (at, c) in [(0, 'a'), (200, 's'), (400, 'd'), (600, 'f')] {
+            held.push(KeyCode::Char(c), KeyEventKind::Press, start + at * MS);
+        }
+        held.push(KeyCode::Char('s'), KeyEventKind::Release, start + 900 * MS);
+        held.push(KeyCode::Char('a'), KeyEventKind::Repeat, start + 950 * MS);
+
+        assert_eq!(names(&held.keys), "a d f");
+        assert_eq!(names(&held.most), "a s d f");
+    }
+
+    #[test]
+    fn guesses_chords_from_presses() {
+        let start = Instant::now();
+        let mut held = Held::default();
+
+        for (at, c) in [(0, 'j'), (10, 'k'), (20, 'l'), (500, ';')] {
+            held.push(KeyCode::Char(c), KeyEventKind::Press, start + at * MS);
+        }
+
+        assert_eq!(names(&held.keys), ";");
+        assert_eq!(names(&held.most), "j k l");
+    }
+}